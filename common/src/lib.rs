@@ -9,6 +9,8 @@ pub mod entity {
     #[derive(Clone, Builder)]
     pub struct AggregateRoot<ID: Clone> {
         pub base_entity: BaseEntity<ID>,
+        #[builder(default)]
+        pub version: i64,
     }
 }
 
@@ -27,9 +29,9 @@ pub mod value_object {
         pub base_id: BaseId<uuid::Uuid>,
     }
 
-    impl Into<uuid::Uuid> for CustomerId {
-        fn into(self) -> uuid::Uuid {
-            return self.base_id.value;
+    impl From<CustomerId> for uuid::Uuid {
+        fn from(customer_id: CustomerId) -> Self {
+            customer_id.base_id.value
         }
     }
 
@@ -60,8 +62,15 @@ pub mod value_object {
         }
     }
 
+    impl From<OrderId> for uuid::Uuid {
+        fn from(order_id: OrderId) -> Self {
+            order_id.base_id.value
+        }
+    }
+
     #[derive(Clone, PartialEq)]
     pub enum OrderStatus {
+        Scheduled,
         Pending,
         Paid,
         Approved,
@@ -69,11 +78,31 @@ pub mod value_object {
         Cancelled,
     }
 
+    impl OrderStatus {
+        pub fn open_statuses() -> Vec<OrderStatus> {
+            vec![
+                OrderStatus::Scheduled,
+                OrderStatus::Pending,
+                OrderStatus::Paid,
+                OrderStatus::Cancelling,
+            ]
+        }
+
+        pub fn kitchen_statuses() -> Vec<OrderStatus> {
+            vec![OrderStatus::Paid, OrderStatus::Approved]
+        }
+
+        pub fn is_open(&self) -> bool {
+            OrderStatus::open_statuses().contains(self)
+        }
+    }
+
     impl FromStr for OrderStatus {
         type Err = ();
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
             match s {
+                "Scheduled" => Ok(OrderStatus::Scheduled),
                 "Pending" => Ok(OrderStatus::Pending),
                 "Paid" => Ok(OrderStatus::Paid),
                 "Approved" => Ok(OrderStatus::Approved),
@@ -87,6 +116,7 @@ pub mod value_object {
     impl Display for OrderStatus {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
+                OrderStatus::Scheduled => write!(f, "Scheduled"),
                 OrderStatus::Pending => write!(f, "Pending"),
                 OrderStatus::Paid => write!(f, "Paid"),
                 OrderStatus::Approved => write!(f, "Approved"),
@@ -96,6 +126,43 @@ pub mod value_object {
         }
     }
 
+    #[derive(Clone, Default, PartialEq)]
+    pub enum TaxCategory {
+        #[default]
+        Standard,
+        Food,
+        Beverage,
+        Alcohol,
+        Exempt,
+    }
+
+    impl FromStr for TaxCategory {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Standard" => Ok(TaxCategory::Standard),
+                "Food" => Ok(TaxCategory::Food),
+                "Beverage" => Ok(TaxCategory::Beverage),
+                "Alcohol" => Ok(TaxCategory::Alcohol),
+                "Exempt" => Ok(TaxCategory::Exempt),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for TaxCategory {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                TaxCategory::Standard => write!(f, "Standard"),
+                TaxCategory::Food => write!(f, "Food"),
+                TaxCategory::Beverage => write!(f, "Beverage"),
+                TaxCategory::Alcohol => write!(f, "Alcohol"),
+                TaxCategory::Exempt => write!(f, "Exempt"),
+            }
+        }
+    }
+
     #[derive(Clone)]
     pub enum PaymentStatus {
         Completed,
@@ -129,9 +196,9 @@ pub mod value_object {
         }
     }
 
-    impl Into<uuid::Uuid> for RestaurantId {
-        fn into(self) -> uuid::Uuid {
-            return self.base_id.value;
+    impl From<RestaurantId> for uuid::Uuid {
+        fn from(restaurant_id: RestaurantId) -> Self {
+            restaurant_id.base_id.value
         }
     }
 
@@ -149,6 +216,20 @@ pub mod value_object {
             }
         }
 
+        impl PartialOrd for Money {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.amount.partial_cmp(&other.amount)
+            }
+        }
+
+        impl ops::Add<Money> for Money {
+            type Output = Money;
+
+            fn add(self, rhs: Money) -> Self::Output {
+                Money::new(self.amount + rhs.amount)
+            }
+        }
+
         impl ops::AddAssign<Money> for Money {
             fn add_assign(&mut self, rhs: Money) {
                 self.amount += rhs.amount
@@ -159,7 +240,7 @@ pub mod value_object {
             type Output = Money;
 
             fn mul(self, rhs: u64) -> Self::Output {
-                return Money::new(self.amount * (rhs as f64));
+                Money::new(self.amount * (rhs as f64))
             }
         }
 
@@ -168,7 +249,19 @@ pub mod value_object {
                 Self { amount }
             }
             pub fn is_greater_than_zero(&self) -> bool {
-                return self.amount > 0.0;
+                self.amount > 0.0
+            }
+            pub fn multiply(&self, factor: f64) -> Money {
+                Money::new(self.amount * factor)
+            }
+            pub fn round(&self) -> Money {
+                Money::new(self.amount.round())
+            }
+        }
+
+        impl Default for Money {
+            fn default() -> Self {
+                ZERO
             }
         }
 
@@ -178,9 +271,9 @@ pub mod value_object {
             }
         }
 
-        impl Into<i64> for Money {
-            fn into(self) -> i64 {
-                self.amount as i64
+        impl From<Money> for i64 {
+            fn from(money: Money) -> Self {
+                money.amount.round() as i64
             }
         }
 
@@ -189,6 +282,9 @@ pub mod value_object {
 }
 
 pub mod event {
+    use chrono::{DateTime, Utc};
+    use derive_builder::Builder;
+
     pub mod publisher {
         use super::DomainEvent;
 
@@ -198,12 +294,218 @@ pub mod event {
         }
     }
 
-    pub trait DomainEvent<T> {}
+    #[derive(Clone, Builder)]
+    pub struct EventEnvelope {
+        pub event_id: uuid::Uuid,
+        pub aggregate_id: uuid::Uuid,
+        pub aggregate_version: i64,
+        pub occurred_at: DateTime<Utc>,
+        pub correlation_id: uuid::Uuid,
+        #[builder(default)]
+        pub causation_id: Option<uuid::Uuid>,
+    }
+
+    #[derive(Clone)]
+    pub struct Causation {
+        pub correlation_id: uuid::Uuid,
+        pub causation_id: Option<uuid::Uuid>,
+    }
+
+    impl Causation {
+        pub fn new(correlation_id: uuid::Uuid, causation_id: Option<uuid::Uuid>) -> Self {
+            Self {
+                correlation_id,
+                causation_id,
+            }
+        }
+
+        pub fn caused_by(envelope: &EventEnvelope) -> Self {
+            Self {
+                correlation_id: envelope.correlation_id,
+                causation_id: Some(envelope.event_id),
+            }
+        }
+    }
+
+    pub trait DomainEvent<T> {
+        fn envelope(&self) -> &EventEnvelope;
+    }
+}
+
+pub mod clock {
+    use chrono::{DateTime, Utc};
+
+    pub trait Clock: Send + Sync {
+        fn now(&self) -> DateTime<Utc>;
+    }
+
+    #[derive(Clone, Default)]
+    pub struct SystemClock {}
+
+    impl Clock for SystemClock {
+        fn now(&self) -> DateTime<Utc> {
+            Utc::now()
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct FixedClock {
+        now: DateTime<Utc>,
+    }
+
+    impl FixedClock {
+        pub fn new(now: DateTime<Utc>) -> Self {
+            Self { now }
+        }
+    }
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now
+        }
+    }
+}
+
+pub mod id {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    pub trait IdGenerator: Send + Sync {
+        fn generate(&self) -> uuid::Uuid;
+    }
+
+    #[derive(Clone, Default)]
+    pub struct SystemIdGenerator {}
+
+    impl IdGenerator for SystemIdGenerator {
+        fn generate(&self) -> uuid::Uuid {
+            uuid::Uuid::new_v4()
+        }
+    }
+
+    #[derive(Clone, Default)]
+    pub struct SequentialIdGenerator {
+        next: Arc<AtomicU64>,
+    }
+
+    impl SequentialIdGenerator {
+        pub fn new(start: u64) -> Self {
+            Self {
+                next: Arc::new(AtomicU64::new(start)),
+            }
+        }
+    }
+
+    impl IdGenerator for SequentialIdGenerator {
+        fn generate(&self) -> uuid::Uuid {
+            uuid::Uuid::from_u128(self.next.fetch_add(1, Ordering::SeqCst) as u128)
+        }
+    }
+}
+
+pub mod validation {
+    use std::fmt::Display;
+
+    use crate::error::OrderDomainError;
+
+    #[derive(Clone, Debug)]
+    pub struct FieldError {
+        pub field: String,
+        pub message: String,
+        pub unavailable_product: Option<uuid::Uuid>,
+    }
+
+    #[derive(Clone, Debug, Default)]
+    pub struct ValidationReport {
+        pub errors: Vec<FieldError>,
+    }
+
+    impl ValidationReport {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+            self.errors.push(FieldError {
+                field: field.into(),
+                message: message.into(),
+                unavailable_product: None,
+            });
+        }
+
+        pub fn add_unavailable_product(
+            &mut self,
+            field: impl Into<String>,
+            product_id: uuid::Uuid,
+            message: impl Into<String>,
+        ) {
+            self.errors.push(FieldError {
+                field: field.into(),
+                message: message.into(),
+                unavailable_product: Some(product_id),
+            });
+        }
+
+        pub fn unavailable_products(&self) -> Vec<uuid::Uuid> {
+            let mut unavailable_products: Vec<uuid::Uuid> = vec![];
+            for product_id in self
+                .errors
+                .iter()
+                .filter_map(|error| error.unavailable_product)
+            {
+                if !unavailable_products.contains(&product_id) {
+                    unavailable_products.push(product_id);
+                }
+            }
+            unavailable_products
+        }
+
+        pub fn check(&mut self, field: impl Into<String>, result: Result<(), OrderDomainError>) {
+            if let Err(error) = result {
+                self.add(field, error.to_string());
+            }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.errors.is_empty()
+        }
+
+        pub fn into_result(self) -> Result<(), OrderDomainError> {
+            if self.is_empty() {
+                return Ok(());
+            }
+            if self
+                .errors
+                .iter()
+                .all(|error| error.unavailable_product.is_some())
+            {
+                return Err(OrderDomainError::ProductsUnavailable(
+                    self.unavailable_products(),
+                ));
+            }
+            Err(OrderDomainError::ValidationFailed(self))
+        }
+    }
+
+    impl Display for ValidationReport {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let errors: Vec<String> = self
+                .errors
+                .iter()
+                .map(|error| format!("{}: {}", error.field, error.message))
+                .collect();
+            write!(f, "{}", errors.join("; "))
+        }
+    }
 }
 
 pub mod error {
     use thiserror::Error;
 
+    use crate::validation::ValidationReport;
+
     #[derive(Error, Debug)]
     pub enum OrderDomainError {
         #[error("the total price of order should be greater than zero")]
@@ -220,11 +522,77 @@ pub mod error {
         CustomerNotFound,
         #[error("restaurant not found")]
         RestaurantNotFound,
+        #[error("the restaurant configuration is invalid: {0}")]
+        InvalidRestaurantConfiguration(String),
         #[error("could not save order")]
         SaveOrderError,
+        #[error("the data store could not be accessed: {0}")]
+        PersistenceError(String),
         #[error("order not found")]
         OrderNotFound,
         #[error("order item not found")]
         OrderItemNotFound,
+        #[error("the delivery address is outside of the restaurant delivery zones")]
+        UndeliverableAddress,
+        #[error("the order total is below the minimum order amount of the delivery zone")]
+        DeliveryZoneMinimumNotMet,
+        #[error("the restaurant is closed at {0}")]
+        RestaurantClosed(String),
+        #[error("the restaurant is temporarily not accepting orders: {0}")]
+        RestaurantPaused(String),
+        #[error("the requested delivery time is invalid: {0}")]
+        InvalidScheduledTime(String),
+        #[error("the selected product options are invalid: {0}")]
+        InvalidProductOptions(String),
+        #[error("the following products are unavailable: {0:?}")]
+        ProductsUnavailable(Vec<uuid::Uuid>),
+        #[error("the order total is below the restaurant minimum order amount of {0}")]
+        BelowMinimumOrderAmount(f64),
+        #[error("the customer already has {0} open orders")]
+        TooManyOpenOrders(u64),
+        #[error("the tip is invalid: {0}")]
+        InvalidTip(String),
+        #[error("the order adjustment is invalid: {0}")]
+        InvalidAdjustment(String),
+        #[error("the order was modified concurrently")]
+        ConcurrentModification,
+        #[error("the order is invalid: {0}")]
+        ValidationFailed(ValidationReport),
+        #[error("invalid page cursor: {0}")]
+        InvalidCursor(String),
+        #[error("access to the order was denied")]
+        OrderAccessDenied,
+        #[error("none of the previous order items can be reordered")]
+        NothingToReorder,
+        #[error("the reorder total no longer matches the quote, the current total is {0}")]
+        ReorderQuoteChanged(f64),
+        #[error("the group order does not allow {0}")]
+        InvalidGroupOrderStatus(String),
+        #[error("only the host can change the group order")]
+        NotGroupOrderHost,
+        #[error("only participants can change the group order")]
+        NotGroupOrderParticipant,
+        #[error("the group order has no items")]
+        EmptyGroupOrder,
+        #[error("the order has no payment share for customer {0}")]
+        PaymentShareNotFound(uuid::Uuid),
+        #[error("the basket has no items")]
+        EmptyBasket,
+        #[error("the subscription schedule is invalid: {0}")]
+        InvalidSubscriptionSchedule(String),
+        #[error("the subscription status is invalid for {0} operation")]
+        InvalidSubscriptionStatus(String),
+        #[error("subscription not found")]
+        SubscriptionNotFound,
+        #[error("the subscription occurrence on {0} is already claimed")]
+        SubscriptionOccurrenceClaimed(String),
+        #[error("the order item conflicts with the dietary preferences")]
+        DietaryConflict,
+        #[error("the order was denied by risk checks: {0}")]
+        RiskCheckDenied(String),
+        #[error("the risk rule is invalid: {0}")]
+        InvalidRiskRule(String),
+        #[error("the tax rule is invalid: {0}")]
+        InvalidTaxRule(String),
     }
 }
@@ -29,11 +29,68 @@ pub mod model {
                 pub customer_id: uuid::Uuid,
                 pub order_id: uuid::Uuid,
                 pub price: i64,
+                #[avro(default = "0")]
+                pub tax: i64,
+                #[avro(default = "0")]
+                pub tip: i64,
                 pub created_at: i64,
                 pub payment_order_status: PaymentOrderStatus,
             }
         }
 
+        pub mod payment_refund_request {
+            use apache_avro::AvroSchema;
+            use derive_builder::Builder;
+            use serde::Serialize;
+
+            #[derive(AvroSchema, Builder, Serialize, Clone)]
+            pub struct PaymentRefundRequest {
+                pub id: uuid::Uuid,
+                pub saga_id: uuid::Uuid,
+                pub customer_id: uuid::Uuid,
+                pub order_id: uuid::Uuid,
+                pub adjustment_id: uuid::Uuid,
+                pub refund_amount: i64,
+                pub reason: String,
+                pub created_at: i64,
+            }
+        }
+
+        pub mod subscription_failure_notification {
+            use apache_avro::AvroSchema;
+            use derive_builder::Builder;
+            use serde::Serialize;
+
+            #[derive(AvroSchema, Builder, Serialize, Clone)]
+            pub struct SubscriptionFailureNotification {
+                pub id: uuid::Uuid,
+                pub subscription_id: uuid::Uuid,
+                pub customer_id: uuid::Uuid,
+                pub restaurant_id: uuid::Uuid,
+                pub occurrence_date: String,
+                pub failure_messages: Vec<String>,
+                pub created_at: i64,
+            }
+        }
+
+        pub mod order_risk_review_request {
+            use apache_avro::AvroSchema;
+            use derive_builder::Builder;
+            use serde::Serialize;
+
+            #[derive(AvroSchema, Builder, Serialize, Clone)]
+            pub struct OrderRiskReviewRequest {
+                pub id: uuid::Uuid,
+                pub saga_id: uuid::Uuid,
+                pub order_id: uuid::Uuid,
+                pub customer_id: uuid::Uuid,
+                pub restaurant_id: uuid::Uuid,
+                pub price: i64,
+                pub risk_reasons: Vec<String>,
+                pub created_at: i64,
+            }
+        }
+
         pub mod payment_response {
             use apache_avro::AvroSchema;
             #[derive(apache_avro::AvroSchema)]
@@ -66,11 +123,20 @@ pub mod model {
                 PAID,
             }
 
+            #[derive(AvroSchema, Serialize, Clone)]
+            pub struct ProductOption {
+                pub id: uuid::Uuid,
+                pub group_id: uuid::Uuid,
+                pub name: String,
+            }
+
             #[derive(AvroSchema, Serialize, Clone)]
 
             pub struct Product {
                 pub id: uuid::Uuid,
                 pub quantity: i64,
+                #[avro(default = "[]")]
+                pub options: Vec<ProductOption>,
             }
             #[derive(AvroSchema, Serialize, Clone, Builder)]
             pub struct RestaurantApprovalRequest {
@@ -82,6 +148,12 @@ pub mod model {
                 pub price: i64,
                 pub products: Vec<Product>,
                 pub created_at: i64,
+                #[avro(default = "[]")]
+                pub declared_allergies: Vec<String>,
+                #[avro(default = "[]")]
+                pub dietary_requirements: Vec<String>,
+                #[avro(default = "[]")]
+                pub dietary_conflicts: Vec<String>,
             }
         }
 
@@ -126,6 +198,20 @@ pub struct Message<T: AvroSchema + Serialize + Send + Sync> {
     value: T,
 }
 
+impl<T: AvroSchema + Serialize + Send + Sync> Message<T> {
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
 pub mod producer {
     use apache_avro::AvroSchema;
     use serde::Serialize;
@@ -1,17 +1,40 @@
 pub mod entity {
+    use std::{fmt::Display, str::FromStr};
+
+    fn parse_tags<T: FromStr>(tags: Option<&str>) -> Vec<T> {
+        tags.unwrap_or_default()
+            .split(",")
+            .filter_map(|tag| tag.trim().parse().ok())
+            .collect()
+    }
+
+    fn format_tags<T: Display>(tags: &[T]) -> Option<String> {
+        if tags.is_empty() {
+            return None;
+        }
+        let tags: Vec<String> = tags.iter().map(|tag| tag.to_string()).collect();
+        Some(tags.join(","))
+    }
+
     pub mod customer {
         use sea_orm::entity::prelude::*;
         use sea_orm::DeriveEntityModel;
 
         use common::entity::{AggregateRoot, AggregateRootBuilder, BaseEntityBuilder};
         use common::value_object::CustomerId;
+        use domain_core::dietary::{DietaryPreferences, DietaryPreferencesBuilder};
         use domain_core::entity::{Customer, CustomerBuilder};
 
+        use super::{format_tags, parse_tags};
+
         #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
         #[sea_orm(table_name = "order_customer_m_view", schema_name = "customer")]
         pub struct Model {
             #[sea_orm(primary_key)]
             pub id: uuid::Uuid,
+            pub dietary_allergies: Option<String>,
+            pub dietary_requirements: Option<String>,
+            pub dietary_conflict_policy: Option<String>,
         }
 
         #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -21,13 +44,24 @@ pub mod entity {
 
         impl From<Customer> for Model {
             fn from(c: Customer) -> Self {
-                Self { id: c.into() }
+                let dietary_preferences = c.dietary_preferences.clone();
+                Self {
+                    id: c.into(),
+                    dietary_allergies: dietary_preferences
+                        .as_ref()
+                        .and_then(|preferences| format_tags(&preferences.allergies)),
+                    dietary_requirements: dietary_preferences
+                        .as_ref()
+                        .and_then(|preferences| format_tags(&preferences.requirements)),
+                    dietary_conflict_policy: dietary_preferences
+                        .map(|preferences| preferences.conflict_policy.to_string()),
+                }
             }
         }
 
-        impl Into<Customer> for Model {
-            fn into(self) -> Customer {
-                let customer_id: CustomerId = self.id.into();
+        impl From<Model> for Customer {
+            fn from(model: Model) -> Self {
+                let customer_id: CustomerId = model.id.into();
                 let base_entity = BaseEntityBuilder::default()
                     .id(customer_id)
                     .build()
@@ -36,8 +70,23 @@ pub mod entity {
                     .base_entity(base_entity)
                     .build()
                     .unwrap();
+                let dietary_preferences: DietaryPreferences = DietaryPreferencesBuilder::default()
+                    .allergies(parse_tags(model.dietary_allergies.as_deref()))
+                    .requirements(parse_tags(model.dietary_requirements.as_deref()))
+                    .conflict_policy(
+                        model
+                            .dietary_conflict_policy
+                            .as_deref()
+                            .and_then(|policy| policy.parse().ok())
+                            .unwrap_or_default(),
+                    )
+                    .build()
+                    .unwrap();
                 CustomerBuilder::default()
                     .aggregate_root(aggregate_root)
+                    .dietary_preferences(
+                        Some(dietary_preferences).filter(|preferences| !preferences.is_empty()),
+                    )
                     .build()
                     .unwrap()
             }
@@ -52,7 +101,11 @@ pub mod entity {
         use sea_orm::entity::prelude::*;
         use sea_orm::DeriveEntityModel;
 
-        use domain_core::entity::{Product, ProductBuilder, Restaurant, RestaurantBuilder};
+        use domain_core::entity::{
+            Product, ProductAvailability, ProductBuilder, Restaurant, RestaurantBuilder,
+        };
+
+        use super::parse_tags;
 
         #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
         #[sea_orm(table_name = "order_restaurant_m_view", schema_name = "restaurant")]
@@ -65,6 +118,14 @@ pub mod entity {
             pub product_name: String,
             pub restaurant_active: bool,
             pub product_price: i64,
+            pub product_tax_category: String,
+            pub product_availability: String,
+            pub product_stock_quantity: Option<i64>,
+            pub restaurant_time_zone: Option<String>,
+            pub restaurant_minimum_order_amount: i64,
+            pub product_allergens: Option<String>,
+            pub product_dietary_tags: Option<String>,
+            pub product_prep_time_minutes: Option<i64>,
         }
 
         #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -72,30 +133,294 @@ pub mod entity {
 
         impl ActiveModelBehavior for ActiveModel {}
 
-        impl Into<Product> for Model {
-            fn into(self) -> Product {
-                let product_id: ProductId = self.product_id.into();
+        impl Model {
+            pub fn availability(&self) -> ProductAvailability {
+                match (
+                    self.product_availability.as_str(),
+                    self.product_stock_quantity,
+                ) {
+                    ("SoldOut", _) => ProductAvailability::SoldOut,
+                    ("Limited", Some(stock_quantity)) => {
+                        ProductAvailability::Limited(stock_quantity.max(0) as u64)
+                    }
+                    ("Limited", None) => ProductAvailability::SoldOut,
+                    _ => ProductAvailability::InStock,
+                }
+            }
+        }
+
+        impl From<Model> for Product {
+            fn from(model: Model) -> Self {
+                let availability = model.availability();
+                let product_id: ProductId = model.product_id.into();
                 let base_entity = BaseEntityBuilder::default().id(product_id).build().unwrap();
 
                 ProductBuilder::default()
                     .base_entity(base_entity)
-                    .name(self.product_name)
-                    .price(self.product_price.into())
+                    .name(model.product_name)
+                    .price(model.product_price.into())
+                    .tax_category(model.product_tax_category.parse().unwrap_or_default())
+                    .availability(availability)
+                    .allergens(parse_tags(model.product_allergens.as_deref()))
+                    .dietary_tags(parse_tags(model.product_dietary_tags.as_deref()))
+                    .prep_time(
+                        model
+                            .product_prep_time_minutes
+                            .map(chrono::Duration::minutes),
+                    )
                     .build()
                     .unwrap()
             }
         }
 
-        impl Into<Restaurant> for Model {
-            fn into(self) -> Restaurant {
-                let restaurant_id: RestaurantId = self.restaurant_id.into();
+        impl From<Model> for Restaurant {
+            fn from(model: Model) -> Self {
+                let restaurant_id: RestaurantId = model.restaurant_id.into();
                 let base_entity = BaseEntityBuilder::default()
                     .id(restaurant_id)
                     .build()
                     .unwrap();
                 RestaurantBuilder::default()
                     .base_entity(base_entity)
-                    .active(self.restaurant_active)
+                    .active(model.restaurant_active)
+                    .minimum_order_amount(model.restaurant_minimum_order_amount.into())
+                    .build()
+                    .unwrap()
+            }
+        }
+    }
+
+    pub mod restaurant_product_option {
+        use std::collections::HashMap;
+
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+
+        use domain_core::entity::{
+            ProductOption, ProductOptionBuilder, ProductOptionGroup, ProductOptionGroupBuilder,
+        };
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(
+            table_name = "order_restaurant_product_option_m_view",
+            schema_name = "restaurant"
+        )]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub option_id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub product_id: uuid::Uuid,
+            pub group_id: uuid::Uuid,
+            pub group_name: String,
+            pub group_required: bool,
+            pub group_min_selections: i32,
+            pub group_max_selections: i32,
+            pub option_name: String,
+            pub option_price_delta: i64,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        impl From<Model> for ProductOption {
+            fn from(model: Model) -> Self {
+                ProductOptionBuilder::default()
+                    .id(model.option_id)
+                    .name(model.option_name)
+                    .price_delta(model.option_price_delta.into())
+                    .build()
+                    .unwrap()
+            }
+        }
+
+        pub fn into_option_groups(
+            models: Vec<Model>,
+        ) -> HashMap<uuid::Uuid, Vec<ProductOptionGroup>> {
+            let mut option_groups: HashMap<uuid::Uuid, Vec<ProductOptionGroup>> = HashMap::new();
+            for model in models {
+                let product_option_groups = option_groups.entry(model.product_id).or_default();
+                let group_index = match product_option_groups
+                    .iter()
+                    .position(|group| group.id == model.group_id)
+                {
+                    Some(group_index) => group_index,
+                    None => {
+                        product_option_groups.push(
+                            ProductOptionGroupBuilder::default()
+                                .id(model.group_id)
+                                .name(model.group_name.clone())
+                                .required(model.group_required)
+                                .min_selections(model.group_min_selections.max(0) as u32)
+                                .max_selections(model.group_max_selections.max(0) as u32)
+                                .options(vec![])
+                                .build()
+                                .unwrap(),
+                        );
+                        product_option_groups.len() - 1
+                    }
+                };
+                product_option_groups[group_index]
+                    .options
+                    .push(model.into());
+            }
+            option_groups
+        }
+    }
+
+    pub mod restaurant_delivery_zone {
+        use std::collections::HashSet;
+
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+
+        use common::value_object::money::Money;
+        use domain_core::delivery::{
+            DeliveryArea, DeliveryFeeSchedule, DeliveryFeeScheduleBuilder, DeliveryZone,
+            DeliveryZoneBuilder,
+        };
+        use domain_core::value_object::Coordinates;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(
+            table_name = "order_restaurant_delivery_zone_m_view",
+            schema_name = "restaurant"
+        )]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub postal_codes: Option<String>,
+            pub center_latitude: Option<f64>,
+            pub center_longitude: Option<f64>,
+            pub radius_km: Option<f64>,
+            pub base_fee: i64,
+            pub fee_per_km: i64,
+            pub free_delivery_threshold: Option<i64>,
+            pub minimum_order_amount: i64,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        impl From<Model> for DeliveryZone {
+            fn from(model: Model) -> Self {
+                let area: DeliveryArea = match (
+                    model.center_latitude,
+                    model.center_longitude,
+                    model.radius_km,
+                ) {
+                    (Some(latitude), Some(longitude), Some(radius_km)) => DeliveryArea::Radius {
+                        center: Coordinates {
+                            latitude,
+                            longitude,
+                        },
+                        radius_km,
+                    },
+                    _ => {
+                        let postal_codes: HashSet<String> = model
+                            .postal_codes
+                            .unwrap_or_default()
+                            .split("#")
+                            .filter(|s| !s.is_empty())
+                            .map(|s| s.trim().to_string())
+                            .collect();
+                        DeliveryArea::PostalCodes(postal_codes)
+                    }
+                };
+                let free_delivery_threshold: Option<Money> =
+                    model.free_delivery_threshold.map(|v| v.into());
+                let fee_schedule: DeliveryFeeSchedule = DeliveryFeeScheduleBuilder::default()
+                    .base_fee(model.base_fee.into())
+                    .fee_per_km(model.fee_per_km.into())
+                    .free_delivery_threshold(free_delivery_threshold)
+                    .build()
+                    .unwrap();
+                DeliveryZoneBuilder::default()
+                    .id(model.id)
+                    .area(area)
+                    .fee_schedule(fee_schedule)
+                    .minimum_order_amount(model.minimum_order_amount.into())
+                    .build()
+                    .unwrap()
+            }
+        }
+    }
+
+    pub mod restaurant_opening_hours {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+
+        use domain_core::opening_hours::{OpeningPeriod, OpeningPeriodBuilder};
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(
+            table_name = "order_restaurant_opening_hours_m_view",
+            schema_name = "restaurant"
+        )]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub weekday: Option<String>,
+            pub exception_date: Option<chrono::NaiveDate>,
+            pub opens_at: Option<chrono::NaiveTime>,
+            pub closes_at: Option<chrono::NaiveTime>,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        impl Model {
+            pub fn period(&self) -> Option<OpeningPeriod> {
+                let (opens_at, closes_at) = self.opens_at.zip(self.closes_at)?;
+                Some(
+                    OpeningPeriodBuilder::default()
+                        .opens_at(opens_at)
+                        .closes_at(closes_at)
+                        .build()
+                        .unwrap(),
+                )
+            }
+        }
+    }
+
+    pub mod restaurant_pause {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+
+        use domain_core::opening_hours::{Pause, PauseBuilder};
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(
+            table_name = "order_restaurant_pause_m_view",
+            schema_name = "restaurant"
+        )]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub starts_at: chrono::DateTime<chrono::Utc>,
+            pub ends_at: chrono::DateTime<chrono::Utc>,
+            pub reason: String,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        impl From<Model> for Pause {
+            fn from(model: Model) -> Self {
+                PauseBuilder::default()
+                    .starts_at(model.starts_at)
+                    .ends_at(model.ends_at)
+                    .reason(model.reason)
                     .build()
                     .unwrap()
             }
@@ -105,14 +430,19 @@ pub mod entity {
     pub mod order {
         use sea_orm::entity::prelude::*;
         use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
 
         use common::entity::{AggregateRoot, AggregateRootBuilder, BaseEntityBuilder};
         use common::value_object::money::Money;
         use common::value_object::{CustomerId, OrderId, OrderStatus, RestaurantId};
-        use domain_core::entity::{Order, OrderBuilder};
-        use domain_core::value_object::TrackingId;
+        use domain_core::checkout::{OrderCheckout, OrderCheckoutBuilder};
+        use domain_core::dietary::{DietaryPreferences, DietaryPreferencesBuilder};
+        use domain_core::entity::{Order, OrderBuilder, OrderItem};
+        use domain_core::value_object::{StreetAddress, Tip, TipAmount, TipBuilder, TrackingId};
 
-        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        use super::{format_tags, parse_tags};
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
         #[sea_orm(table_name = "orders")]
         pub struct Model {
             #[sea_orm(primary_key)]
@@ -123,28 +453,71 @@ pub mod entity {
             pub price: i64,
             pub order_status: String,
             pub failure_messages: String,
+            pub tax: i64,
+            pub delivery_fee: i64,
+            pub scheduled_for: Option<chrono::DateTime<chrono::Utc>>,
+            pub tip: i64,
+            pub tip_recipient: Option<String>,
+            pub version: i64,
+            pub status_changed_at: Option<chrono::DateTime<chrono::Utc>>,
+            pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+            pub checkout_id: Option<uuid::Uuid>,
+            pub checkout_cancellation_policy: Option<String>,
+            pub declared_allergies: Option<String>,
+            pub dietary_requirements: Option<String>,
+            pub dietary_conflict_policy: Option<String>,
+            pub estimated_delivery_at: Option<chrono::DateTime<chrono::Utc>>,
+            pub risk_decision: Option<String>,
+            pub risk_reasons: Option<Json>,
         }
 
         impl From<Order> for Model {
             fn from(o: Order) -> Self {
                 Self {
                     id: o.clone().into(),
+                    tip: o.tip_amount().into(),
                     customer_id: o.customer_id.into(),
                     restaurant_id: o.restaurant_id.into(),
                     tracking_id: o.tracking_id.into(),
                     price: o.price.amount as i64,
                     order_status: o.order_status.to_string(),
                     failure_messages: o.failure_messages.join("#"),
+                    tax: o.tax.into(),
+                    delivery_fee: o.delivery_fee.into(),
+                    scheduled_for: o.scheduled_for,
+                    tip_recipient: o.tip.map(|tip| tip.recipient.to_string()),
+                    version: o.aggregate_root.version,
+                    status_changed_at: o.status_changed_at,
+                    created_at: o.created_at,
+                    checkout_id: o.checkout.as_ref().map(|checkout| checkout.id),
+                    checkout_cancellation_policy: o
+                        .checkout
+                        .map(|checkout| checkout.cancellation_policy.to_string()),
+                    declared_allergies: o
+                        .dietary_preferences
+                        .as_ref()
+                        .and_then(|preferences| format_tags(&preferences.allergies)),
+                    dietary_requirements: o
+                        .dietary_preferences
+                        .as_ref()
+                        .and_then(|preferences| format_tags(&preferences.requirements)),
+                    dietary_conflict_policy: o
+                        .dietary_preferences
+                        .map(|preferences| preferences.conflict_policy.to_string()),
+                    estimated_delivery_at: o.estimated_delivery_at,
+                    risk_decision: Some(o.risk_decision.to_string()),
+                    risk_reasons: Some(serde_json::to_value(o.risk_reasons).unwrap_or_default()),
                 }
             }
         }
 
-        impl Into<Order> for Model {
-            fn into(self) -> Order {
+        impl Model {
+            pub fn into_order(self, street_address: StreetAddress, items: Vec<OrderItem>) -> Order {
                 let order_id: OrderId = self.id.into();
                 let base_entity = BaseEntityBuilder::default().id(order_id).build().unwrap();
                 let aggregate_root: AggregateRoot<OrderId> = AggregateRootBuilder::default()
                     .base_entity(base_entity)
+                    .version(self.version)
                     .build()
                     .unwrap();
                 let customer_id: CustomerId = self.customer_id.into();
@@ -152,19 +525,72 @@ pub mod entity {
                 let tracking_id: TrackingId = self.tracking_id.into();
                 let order_status: OrderStatus = self.order_status.parse().unwrap();
                 let price: Money = self.price.into();
+                let tax: Money = self.tax.into();
+                let delivery_fee: Money = self.delivery_fee.into();
+                let tip: Option<Tip> = self.tip_recipient.map(|tip_recipient| {
+                    TipBuilder::default()
+                        .amount(TipAmount::Fixed(self.tip.into()))
+                        .recipient(tip_recipient.parse().unwrap_or_default())
+                        .build()
+                        .unwrap()
+                });
                 let failure_messages: Vec<String> = self
                     .failure_messages
                     .split("#")
+                    .filter(|s| !s.is_empty())
                     .map(|s| s.to_string())
                     .collect();
+                let checkout: Option<OrderCheckout> = self.checkout_id.map(|checkout_id| {
+                    OrderCheckoutBuilder::default()
+                        .id(checkout_id)
+                        .cancellation_policy(
+                            self.checkout_cancellation_policy
+                                .as_deref()
+                                .and_then(|policy| policy.parse().ok())
+                                .unwrap_or_default(),
+                        )
+                        .build()
+                        .unwrap()
+                });
+                let dietary_preferences: Option<DietaryPreferences> =
+                    self.dietary_conflict_policy.map(|dietary_conflict_policy| {
+                        DietaryPreferencesBuilder::default()
+                            .allergies(parse_tags(self.declared_allergies.as_deref()))
+                            .requirements(parse_tags(self.dietary_requirements.as_deref()))
+                            .conflict_policy(dietary_conflict_policy.parse().unwrap_or_default())
+                            .build()
+                            .unwrap()
+                    });
                 OrderBuilder::default()
                     .aggregate_root(aggregate_root)
                     .customer_id(customer_id)
                     .restaurant_id(restaurant_id)
+                    .street_address(street_address)
+                    .items(items)
                     .tracking_id(tracking_id)
                     .price(price)
                     .order_status(order_status)
                     .failure_messages(failure_messages)
+                    .tax(tax)
+                    .delivery_fee(delivery_fee)
+                    .scheduled_for(self.scheduled_for)
+                    .tip(tip)
+                    .status_changed_at(self.status_changed_at)
+                    .created_at(self.created_at)
+                    .checkout(checkout)
+                    .dietary_preferences(dietary_preferences)
+                    .estimated_delivery_at(self.estimated_delivery_at)
+                    .risk_decision(
+                        self.risk_decision
+                            .as_deref()
+                            .and_then(|risk_decision| risk_decision.parse().ok())
+                            .unwrap_or_default(),
+                    )
+                    .risk_reasons(
+                        self.risk_reasons
+                            .and_then(|risk_reasons| serde_json::from_value(risk_reasons).ok())
+                            .unwrap_or_default(),
+                    )
                     .build()
                     .unwrap()
             }
@@ -196,8 +622,12 @@ pub mod entity {
     pub mod order_address {
         use sea_orm::entity::prelude::*;
         use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
 
-        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        use domain_core::entity::Order;
+        use domain_core::value_object::{Coordinates, StreetAddress, StreetAddressBuilder};
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
         #[sea_orm(table_name = "order_addresses")]
         pub struct Model {
             #[sea_orm(primary_key)]
@@ -206,6 +636,44 @@ pub mod entity {
             pub street: String,
             pub postal_code: String,
             pub city: String,
+            pub latitude: Option<f64>,
+            pub longitude: Option<f64>,
+        }
+
+        impl From<Order> for Model {
+            fn from(o: Order) -> Self {
+                let coordinates = o.street_address.coordinates.clone();
+                Self {
+                    id: o.street_address.id,
+                    order_id: o.clone().into(),
+                    street: o.street_address.street,
+                    postal_code: o.street_address.postal_code,
+                    city: o.street_address.city,
+                    latitude: coordinates.as_ref().map(|c| c.latitude),
+                    longitude: coordinates.as_ref().map(|c| c.longitude),
+                }
+            }
+        }
+
+        impl From<Model> for StreetAddress {
+            fn from(model: Model) -> Self {
+                let coordinates: Option<Coordinates> =
+                    model
+                        .latitude
+                        .zip(model.longitude)
+                        .map(|(latitude, longitude)| Coordinates {
+                            latitude,
+                            longitude,
+                        });
+                StreetAddressBuilder::default()
+                    .city(model.city)
+                    .postal_code(model.postal_code)
+                    .street(model.street)
+                    .id(model.id)
+                    .coordinates(coordinates)
+                    .build()
+                    .unwrap()
+            }
         }
 
         #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -230,13 +698,13 @@ pub mod entity {
     pub mod order_item {
         use sea_orm::entity::prelude::*;
         use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
 
         use common::entity::{BaseEntity, BaseEntityBuilder};
-        use common::value_object;
-        use domain_core::entity::{OrderItem, OrderItemBuilder, Product, ProductBuilder};
+        use domain_core::entity::{OrderItem, OrderItemBuilder, Product};
         use domain_core::value_object::OrderItemId;
 
-        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
         #[sea_orm(table_name = "order_items")]
         pub struct Model {
             #[sea_orm(primary_key)]
@@ -247,35 +715,62 @@ pub mod entity {
             pub quantity: u64,
             pub price: i64,
             pub sub_total: i64,
+            pub tax: i64,
+        }
+
+        impl From<OrderItem> for Model {
+            fn from(i: OrderItem) -> Self {
+                Self {
+                    id: i.base_entity.id.into(),
+                    order_id: i.order_id.into(),
+                    product_id: i.product.into(),
+                    quantity: i.quantity,
+                    price: i.price.into(),
+                    sub_total: i.sub_total.into(),
+                    tax: i.tax.into(),
+                }
+            }
+        }
+
+        impl Model {
+            pub fn into_order_item(
+                self,
+                option_models: &[super::order_item_option::Model],
+            ) -> OrderItem {
+                let order_item_id = self.id;
+                let mut order_item: OrderItem = self.into();
+                order_item.restore_selected_options(
+                    option_models
+                        .iter()
+                        .filter(|option_model| option_model.order_item_id == order_item_id)
+                        .map(|option_model| option_model.clone().into())
+                        .collect(),
+                );
+                order_item
+            }
         }
 
         impl From<Model> for OrderItem {
-            fn from(val: Model) -> Self {
-                let order_item_id = val.id;
-                let order_id: uuid::Uuid = val.order_id;
-                let product_id: uuid::Uuid = val.product_id;
-                let quantity: u64 = val.quantity;
-                let price: i64 = val.price;
-                let sub_total: i64 = val.sub_total;
+            fn from(model: Model) -> Self {
+                let order_item_id = model.id;
+                let order_id: uuid::Uuid = model.order_id;
+                let product_id: uuid::Uuid = model.product_id;
+                let quantity: u64 = model.quantity;
+                let price: i64 = model.price;
+                let sub_total: i64 = model.sub_total;
+                let tax: i64 = model.tax;
                 let order_item_base_entity: BaseEntity<OrderItemId> = BaseEntityBuilder::default()
                     .id(order_item_id.into())
                     .build()
                     .unwrap();
-                let product_base_entity: BaseEntity<value_object::ProductId> =
-                    BaseEntityBuilder::default()
-                        .id(product_id.into())
-                        .build()
-                        .unwrap();
-                let product: Product = ProductBuilder::default()
-                    .base_entity(product_base_entity)
-                    .build()
-                    .unwrap();
+                let product: Product = Product::new(product_id, String::new(), price.into());
                 OrderItemBuilder::default()
                     .base_entity(order_item_base_entity)
                     .order_id(order_id.into())
                     .quantity(quantity)
                     .price(price.into())
                     .sub_total(sub_total.into())
+                    .tax(tax.into())
                     .product(product)
                     .build()
                     .unwrap()
@@ -299,124 +794,3495 @@ pub mod entity {
         }
 
         impl ActiveModelBehavior for ActiveModel {}
-    }
-}
 
-pub mod repository {
-    use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+        #[cfg(test)]
+        mod tests {
+            use super::Model;
+            use crate::entity::order_item_option;
 
-    use common::error::OrderDomainError;
-    use domain_core::{
-        entity::{Customer, Order, OrderItem, Restaurant},
-        value_object::{StreetAddress, StreetAddressBuilder, TrackingId},
-    };
-    use service::ports::output::repository::{
-        CustomerRepository, OrderRepository, RestaurantRepository,
-    };
+            #[test]
+            fn into_order_item_restores_the_base_product_price() {
+                let order_id = uuid::Uuid::from_u128(1);
+                let model = Model {
+                    id: 1,
+                    order_id,
+                    product_id: uuid::Uuid::from_u128(10),
+                    quantity: 2,
+                    price: 1300,
+                    sub_total: 2600,
+                    tax: 0,
+                };
+                let option_model = order_item_option::Model {
+                    order_id,
+                    order_item_id: 1,
+                    option_id: uuid::Uuid::from_u128(62),
+                    group_id: uuid::Uuid::from_u128(60),
+                    name: String::from("large"),
+                    price_delta: 300,
+                };
+                let order_item = model.into_order_item(&[option_model]);
+                assert_eq!(order_item.product.price.amount, 1000.0);
+                assert_eq!(order_item.unit_price().amount, 1300.0);
+                assert!(order_item.is_price_valid());
+            }
+        }
+    }
 
-    use crate::entity::{customer, order, order_address, order_item, restaurant};
+    pub mod order_adjustment {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
 
-    pub struct OrderRepositoryImpl {
-        db: sea_orm::DatabaseConnection,
-    }
+        use common::error::OrderDomainError;
+        use domain_core::entity::{OrderAdjustment, OrderAdjustmentBuilder};
 
-    #[async_trait::async_trait]
-    impl OrderRepository for OrderRepositoryImpl {
-        async fn save(&self, order: Order) -> Result<Order, OrderDomainError> {
-            let order_model: order::Model = order::Model::from(order.clone());
-            let order_active_model: order::ActiveModel = order::ActiveModel::from(order_model);
-            let _save_result = order_active_model
-                .insert(&self.db)
-                .await
-                .map_err(|_| OrderDomainError::SaveOrderError)?;
-            Ok(order)
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+        #[sea_orm(table_name = "order_adjustments")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub order_id: uuid::Uuid,
+            pub order_item_id: i64,
+            pub quantity: u64,
+            pub reason: String,
+            pub refund_amount: i64,
+            pub created_at: chrono::DateTime<chrono::Utc>,
         }
-        async fn find_by_tracking_id(&self, id: TrackingId) -> Result<Order, OrderDomainError> {
-            let tracking_uuid: uuid::Uuid = id.into();
-            let (order_model, street_model_optional) = order::Entity::find()
-                .filter(order::Column::TrackingId.eq(tracking_uuid))
-                .find_also_related(order_address::Entity)
-                .one(&self.db)
-                .await
-                .map_err(|_| OrderDomainError::OrderNotFound)?
-                .ok_or(OrderDomainError::OrderNotFound)?;
-            let order_item_models = order_item::Entity::find()
-                .filter(order_item::Column::OrderId.eq(order_model.id))
-                .all(&self.db)
-                .await
-                .map_err(|_| OrderDomainError::OrderItemNotFound)?;
-            let street_address_model: order_address::Model = street_model_optional.unwrap();
-            let street_address: StreetAddress = StreetAddressBuilder::default()
-                .city(street_address_model.city)
-                .postal_code(street_address_model.postal_code)
-                .street(street_address_model.street)
-                .id(street_address_model.id.into())
-                .build()
-                .unwrap();
-            let order_items: Vec<OrderItem> = order_item_models
-                .into_iter()
-                .map(|order_item_model| order_item_model.into())
-                .collect();
-            let mut order: Order = order_model.into();
-            order.street_address = street_address;
-            order.items = order_items;
-            Ok(order)
+
+        impl Model {
+            pub fn from_adjustment(order_id: uuid::Uuid, adjustment: OrderAdjustment) -> Self {
+                Self {
+                    id: adjustment.id,
+                    order_id,
+                    order_item_id: adjustment.order_item_id.into(),
+                    quantity: adjustment.quantity,
+                    reason: adjustment.reason.to_string(),
+                    refund_amount: adjustment.refund_amount.into(),
+                    created_at: adjustment.created_at,
+                }
+            }
         }
-    }
 
-    pub struct CustomerRepositoryImpl {
-        db: sea_orm::DatabaseConnection,
-    }
+        impl TryFrom<Model> for OrderAdjustment {
+            type Error = OrderDomainError;
 
-    #[async_trait::async_trait]
-    impl CustomerRepository for CustomerRepositoryImpl {
-        async fn find_customer(
-            &self,
-            customer_id: uuid::Uuid,
-        ) -> Result<Customer, OrderDomainError> {
-            let customer_model = customer::Entity::find_by_id(customer_id)
-                .one(&self.db)
-                .await
-                .map_err(|_| OrderDomainError::CustomerNotFound)?
-                .ok_or(OrderDomainError::CustomerNotFound)?;
-            Ok(customer_model.into())
+            fn try_from(model: Model) -> Result<Self, Self::Error> {
+                let reason = model.reason.parse().map_err(|_| {
+                    OrderDomainError::PersistenceError(format!(
+                        "unknown adjustment reason {}",
+                        model.reason
+                    ))
+                })?;
+                Ok(OrderAdjustmentBuilder::default()
+                    .id(model.id)
+                    .order_item_id(model.order_item_id.into())
+                    .quantity(model.quantity)
+                    .reason(reason)
+                    .refund_amount(model.refund_amount.into())
+                    .created_at(model.created_at)
+                    .build()
+                    .unwrap())
+            }
         }
-    }
 
-    pub struct RestaurantRepositoryImpl {
-        db: sea_orm::DatabaseConnection,
-    }
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {
+            #[sea_orm(
+                belongs_to = "super::order::Entity",
+                from = "Column::OrderId",
+                to = "super::order::Column::Id"
+            )]
+            Order,
+        }
 
-    #[async_trait::async_trait]
-    impl RestaurantRepository for RestaurantRepositoryImpl {
-        async fn find_restaurant_info(
-            &self,
-            restaurant: Restaurant,
-        ) -> Result<Restaurant, OrderDomainError> {
-            let product_uuids: Vec<uuid::Uuid> = restaurant
-                .clone()
-                .products
-                .into_iter()
-                .map(|p| p.into())
-                .collect();
-            let restaurant_id: uuid::Uuid = restaurant.into();
-            let restaurant_with_all_products = restaurant::Entity::find()
-                .filter(restaurant::Column::RestaurantId.eq(restaurant_id))
-                .filter(restaurant::Column::ProductId.is_in(product_uuids))
-                .all(&self.db)
-                .await
-                .map_err(|_| OrderDomainError::RestaurantNotFound)?;
-            if let Some(restaurant_model) = restaurant_with_all_products.first() {
-                let products = restaurant_with_all_products
-                    .iter()
-                    .map(|restaurant_model| (*restaurant_model).clone().into())
-                    .collect();
-                let mut restaurant: Restaurant = (*restaurant_model).clone().into();
-                restaurant.products = products;
-                Ok(restaurant)
-            } else {
-                return Err(OrderDomainError::RestaurantNotFound);
+        impl Related<super::order::Entity> for Entity {
+            fn to() -> RelationDef {
+                Relation::Order.def()
             }
         }
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        #[cfg(test)]
+        mod tests {
+            use chrono::TimeZone;
+            use common::error::OrderDomainError;
+            use domain_core::entity::OrderAdjustment;
+
+            use super::Model;
+
+            fn model(reason: &str) -> Model {
+                Model {
+                    id: uuid::Uuid::from_u128(70),
+                    order_id: uuid::Uuid::from_u128(1),
+                    order_item_id: 1,
+                    quantity: 1,
+                    reason: String::from(reason),
+                    refund_amount: 1090,
+                    created_at: chrono::Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap(),
+                }
+            }
+
+            #[test]
+            fn unknown_reasons_are_reported_instead_of_panicking() {
+                assert!(OrderAdjustment::try_from(model("Damaged")).is_ok());
+                assert!(matches!(
+                    OrderAdjustment::try_from(model("Lost")),
+                    Err(OrderDomainError::PersistenceError(_))
+                ));
+            }
+        }
+    }
+
+    pub mod order_payment_share {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
+
+        use domain_core::entity::{PaymentShare, PaymentShareBuilder};
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+        #[sea_orm(table_name = "order_payment_shares")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub order_id: uuid::Uuid,
+            #[sea_orm(primary_key)]
+            pub customer_id: uuid::Uuid,
+            pub items_price: i64,
+            pub total_price: i64,
+            pub tax: i64,
+            pub tip: i64,
+            pub status: String,
+        }
+
+        impl Model {
+            pub fn from_share(order_id: uuid::Uuid, share: PaymentShare) -> Self {
+                Self {
+                    order_id,
+                    customer_id: share.customer_uuid(),
+                    items_price: share.items_price.into(),
+                    total_price: share.total_price.into(),
+                    tax: share.tax.into(),
+                    tip: share.tip.into(),
+                    status: share.status.to_string(),
+                }
+            }
+        }
+
+        impl From<Model> for PaymentShare {
+            fn from(model: Model) -> Self {
+                PaymentShareBuilder::default()
+                    .customer_id(model.customer_id.into())
+                    .items_price(model.items_price.into())
+                    .total_price(model.total_price.into())
+                    .tax(model.tax.into())
+                    .tip(model.tip.into())
+                    .status(model.status.parse().unwrap_or_default())
+                    .build()
+                    .unwrap()
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {
+            #[sea_orm(
+                belongs_to = "super::order::Entity",
+                from = "Column::OrderId",
+                to = "super::order::Column::Id"
+            )]
+            Order,
+        }
+
+        impl Related<super::order::Entity> for Entity {
+            fn to() -> RelationDef {
+                Relation::Order.def()
+            }
+        }
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod order_dietary_conflict {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
+
+        use domain_core::dietary::{
+            DietaryConflict, DietaryConflictBuilder, DietaryConflictReason,
+        };
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+        #[sea_orm(table_name = "order_dietary_conflicts")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub order_id: uuid::Uuid,
+            #[sea_orm(primary_key)]
+            pub product_id: uuid::Uuid,
+            #[sea_orm(primary_key)]
+            pub conflict_kind: String,
+            #[sea_orm(primary_key)]
+            pub conflict_value: String,
+            pub product_name: String,
+        }
+
+        impl Model {
+            pub fn from_conflict(order_id: uuid::Uuid, conflict: DietaryConflict) -> Self {
+                Self {
+                    order_id,
+                    product_id: conflict.product_id,
+                    conflict_kind: conflict.reason.kind(),
+                    conflict_value: conflict.reason.value(),
+                    product_name: conflict.product_name,
+                }
+            }
+
+            pub fn into_conflict(self) -> Option<DietaryConflict> {
+                let reason =
+                    DietaryConflictReason::parse(&self.conflict_kind, &self.conflict_value)?;
+                Some(
+                    DietaryConflictBuilder::default()
+                        .product_id(self.product_id)
+                        .product_name(self.product_name)
+                        .reason(reason)
+                        .build()
+                        .unwrap(),
+                )
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {
+            #[sea_orm(
+                belongs_to = "super::order::Entity",
+                from = "Column::OrderId",
+                to = "super::order::Column::Id"
+            )]
+            Order,
+        }
+
+        impl Related<super::order::Entity> for Entity {
+            fn to() -> RelationDef {
+                Relation::Order.def()
+            }
+        }
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod order_risk_check {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+
+        use domain_core::risk::RiskCheck;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "order_risk_checks")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub order_id: uuid::Uuid,
+            pub customer_id: uuid::Uuid,
+            pub address_key: String,
+            pub decision: String,
+            pub reasons: Json,
+            pub checked_at: chrono::DateTime<chrono::Utc>,
+        }
+
+        impl From<RiskCheck> for Model {
+            fn from(r: RiskCheck) -> Self {
+                Self {
+                    id: r.id,
+                    order_id: r.order_id,
+                    customer_id: r.customer_id,
+                    address_key: r.address_key,
+                    decision: r.assessment.decision.to_string(),
+                    reasons: serde_json::to_value(r.assessment.reasons()).unwrap_or_default(),
+                    checked_at: r.checked_at,
+                }
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod group_order {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
+
+        use domain_core::{
+            entity::OrderItem,
+            group_order::{
+                GroupOrder, GroupOrderBuilder, GroupParticipant, GroupParticipantBuilder,
+            },
+            value_object::StreetAddress,
+        };
+
+        use super::{order_address, order_item, order_item_option};
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "group_orders")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub host_id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub status: String,
+            pub address_id: uuid::Uuid,
+            pub street: String,
+            pub postal_code: String,
+            pub city: String,
+            pub latitude: Option<f64>,
+            pub longitude: Option<f64>,
+            pub participants: Json,
+            pub order_id: Option<uuid::Uuid>,
+            pub version: i64,
+        }
+
+        #[derive(Clone, Serialize, Deserialize)]
+        pub struct GroupParticipantState {
+            pub customer_id: uuid::Uuid,
+            pub items: Vec<order_item::Model>,
+            pub item_options: Vec<order_item_option::Model>,
+        }
+
+        impl From<GroupParticipant> for GroupParticipantState {
+            fn from(participant: GroupParticipant) -> Self {
+                Self {
+                    customer_id: participant.customer_uuid(),
+                    item_options: participant
+                        .items
+                        .iter()
+                        .flat_map(|item| {
+                            item.selected_options
+                                .clone()
+                                .into_iter()
+                                .map(|selected_option| {
+                                    order_item_option::Model::from_selected_option(
+                                        item,
+                                        selected_option,
+                                    )
+                                })
+                        })
+                        .collect(),
+                    items: participant
+                        .items
+                        .into_iter()
+                        .map(order_item::Model::from)
+                        .collect(),
+                }
+            }
+        }
+
+        impl From<GroupParticipantState> for GroupParticipant {
+            fn from(group_participant_state: GroupParticipantState) -> Self {
+                let items: Vec<OrderItem> = group_participant_state
+                    .items
+                    .into_iter()
+                    .map(|item_model| {
+                        item_model.into_order_item(&group_participant_state.item_options)
+                    })
+                    .collect();
+                GroupParticipantBuilder::default()
+                    .customer_id(group_participant_state.customer_id.into())
+                    .items(items)
+                    .build()
+                    .unwrap()
+            }
+        }
+
+        impl From<GroupOrder> for Model {
+            fn from(g: GroupOrder) -> Self {
+                let coordinates = g.street_address.coordinates.clone();
+                let participants: Vec<GroupParticipantState> = g
+                    .participants
+                    .into_iter()
+                    .map(GroupParticipantState::from)
+                    .collect();
+                Self {
+                    id: g.id,
+                    host_id: g.host_id.into(),
+                    restaurant_id: g.restaurant_id.into(),
+                    status: g.status.to_string(),
+                    address_id: g.street_address.id,
+                    street: g.street_address.street,
+                    postal_code: g.street_address.postal_code,
+                    city: g.street_address.city,
+                    latitude: coordinates.as_ref().map(|c| c.latitude),
+                    longitude: coordinates.as_ref().map(|c| c.longitude),
+                    participants: serde_json::to_value(participants).unwrap_or_default(),
+                    order_id: g.order_id,
+                    version: g.version,
+                }
+            }
+        }
+
+        impl From<Model> for GroupOrder {
+            fn from(model: Model) -> Self {
+                let street_address: StreetAddress = order_address::Model {
+                    id: model.address_id,
+                    order_id: model.id,
+                    street: model.street,
+                    postal_code: model.postal_code,
+                    city: model.city,
+                    latitude: model.latitude,
+                    longitude: model.longitude,
+                }
+                .into();
+                let participants: Vec<GroupParticipantState> =
+                    serde_json::from_value(model.participants).unwrap_or_default();
+                GroupOrderBuilder::default()
+                    .id(model.id)
+                    .host_id(model.host_id.into())
+                    .restaurant_id(model.restaurant_id.into())
+                    .street_address(street_address)
+                    .status(model.status.parse().unwrap_or_default())
+                    .participants(
+                        participants
+                            .into_iter()
+                            .map(|participant| participant.into())
+                            .collect(),
+                    )
+                    .order_id(model.order_id)
+                    .version(model.version)
+                    .build()
+                    .unwrap()
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod order_subscription {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
+
+        use common::error::OrderDomainError;
+        use domain_core::{
+            entity::OrderItem,
+            subscription::{
+                OrderSubscription, OrderSubscriptionBuilder, SubscriptionScheduleBuilder,
+            },
+            value_object::{StreetAddress, Tip, TipAmount, TipBuilder},
+        };
+
+        use super::{order_address, order_item, order_item_option};
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "order_subscriptions")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub customer_id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub address_id: uuid::Uuid,
+            pub street: String,
+            pub postal_code: String,
+            pub city: String,
+            pub latitude: Option<f64>,
+            pub longitude: Option<f64>,
+            pub items: Json,
+            pub tip_fixed: Option<i64>,
+            pub tip_percentage: Option<f64>,
+            pub tip_recipient: Option<String>,
+            pub weekdays: String,
+            pub delivery_time: chrono::NaiveTime,
+            pub time_zone: String,
+            pub ends_on: Option<chrono::NaiveDate>,
+            pub status: String,
+            pub last_occurrence_on: Option<chrono::NaiveDate>,
+        }
+
+        #[derive(Clone, Default, Serialize, Deserialize)]
+        pub struct SubscriptionItemsState {
+            pub items: Vec<order_item::Model>,
+            pub item_options: Vec<order_item_option::Model>,
+        }
+
+        impl From<OrderSubscription> for Model {
+            fn from(s: OrderSubscription) -> Self {
+                let coordinates = s.street_address.coordinates.clone();
+                let items_state = SubscriptionItemsState {
+                    item_options: s
+                        .items
+                        .iter()
+                        .flat_map(|item| {
+                            item.selected_options
+                                .clone()
+                                .into_iter()
+                                .map(|selected_option| {
+                                    order_item_option::Model::from_selected_option(
+                                        item,
+                                        selected_option,
+                                    )
+                                })
+                        })
+                        .collect(),
+                    items: s.items.into_iter().map(order_item::Model::from).collect(),
+                };
+                let (tip_fixed, tip_percentage) = match s.tip.as_ref().map(|tip| &tip.amount) {
+                    Some(TipAmount::Fixed(amount)) => (Some(amount.clone().into()), None),
+                    Some(TipAmount::Percentage(percentage)) => (None, Some(*percentage)),
+                    None => (None, None),
+                };
+                let weekdays: Vec<String> = s
+                    .schedule
+                    .weekdays
+                    .iter()
+                    .map(|weekday| weekday.to_string())
+                    .collect();
+                Self {
+                    id: s.id,
+                    customer_id: s.customer_id.into(),
+                    restaurant_id: s.restaurant_id.into(),
+                    address_id: s.street_address.id,
+                    street: s.street_address.street,
+                    postal_code: s.street_address.postal_code,
+                    city: s.street_address.city,
+                    latitude: coordinates.as_ref().map(|c| c.latitude),
+                    longitude: coordinates.as_ref().map(|c| c.longitude),
+                    items: serde_json::to_value(items_state).unwrap_or_default(),
+                    tip_fixed,
+                    tip_percentage,
+                    tip_recipient: s.tip.map(|tip| tip.recipient.to_string()),
+                    weekdays: weekdays.join(","),
+                    delivery_time: s.schedule.delivery_time,
+                    time_zone: s.schedule.time_zone.name().to_string(),
+                    ends_on: s.ends_on,
+                    status: s.status.to_string(),
+                    last_occurrence_on: s.last_occurrence_on,
+                }
+            }
+        }
+
+        impl TryFrom<Model> for OrderSubscription {
+            type Error = OrderDomainError;
+
+            fn try_from(model: Model) -> Result<Self, Self::Error> {
+                let time_zone = model.time_zone.parse().map_err(|_| {
+                    OrderDomainError::PersistenceError(format!(
+                        "unknown subscription time zone {}",
+                        model.time_zone
+                    ))
+                })?;
+                let street_address: StreetAddress = order_address::Model {
+                    id: model.address_id,
+                    order_id: model.id,
+                    street: model.street,
+                    postal_code: model.postal_code,
+                    city: model.city,
+                    latitude: model.latitude,
+                    longitude: model.longitude,
+                }
+                .into();
+                let items_state: SubscriptionItemsState =
+                    serde_json::from_value(model.items).unwrap_or_default();
+                let items: Vec<OrderItem> = items_state
+                    .items
+                    .into_iter()
+                    .map(|item_model| item_model.into_order_item(&items_state.item_options))
+                    .collect();
+                let tip_amount: Option<TipAmount> = match (model.tip_fixed, model.tip_percentage) {
+                    (Some(tip_fixed), _) => Some(TipAmount::Fixed(tip_fixed.into())),
+                    (None, Some(tip_percentage)) => Some(TipAmount::Percentage(tip_percentage)),
+                    (None, None) => None,
+                };
+                let tip: Option<Tip> = tip_amount.map(|amount| {
+                    TipBuilder::default()
+                        .amount(amount)
+                        .recipient(
+                            model
+                                .tip_recipient
+                                .as_deref()
+                                .and_then(|recipient| recipient.parse().ok())
+                                .unwrap_or_default(),
+                        )
+                        .build()
+                        .unwrap()
+                });
+                let schedule = SubscriptionScheduleBuilder::default()
+                    .weekdays(
+                        model
+                            .weekdays
+                            .split(",")
+                            .filter_map(|weekday| weekday.parse().ok())
+                            .collect(),
+                    )
+                    .delivery_time(model.delivery_time)
+                    .time_zone(time_zone)
+                    .build()
+                    .unwrap();
+                Ok(OrderSubscriptionBuilder::default()
+                    .id(model.id)
+                    .customer_id(model.customer_id.into())
+                    .restaurant_id(model.restaurant_id.into())
+                    .street_address(street_address)
+                    .items(items)
+                    .tip(tip)
+                    .schedule(schedule)
+                    .ends_on(model.ends_on)
+                    .status(model.status.parse().unwrap_or_default())
+                    .last_occurrence_on(model.last_occurrence_on)
+                    .build()
+                    .unwrap())
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod subscription_occurrence {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "subscription_occurrences")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub subscription_id: uuid::Uuid,
+            #[sea_orm(primary_key)]
+            pub occurrence_on: chrono::NaiveDate,
+            pub order_id: uuid::Uuid,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod order_item_option {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
+
+        use domain_core::entity::{OrderItem, SelectedOption, SelectedOptionBuilder};
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+        #[sea_orm(table_name = "order_item_options")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub order_id: uuid::Uuid,
+            #[sea_orm(primary_key)]
+            pub order_item_id: i64,
+            #[sea_orm(primary_key)]
+            pub option_id: uuid::Uuid,
+            pub group_id: uuid::Uuid,
+            pub name: String,
+            pub price_delta: i64,
+        }
+
+        impl Model {
+            pub fn from_selected_option(
+                order_item: &OrderItem,
+                selected_option: SelectedOption,
+            ) -> Self {
+                Self {
+                    order_id: order_item.order_id.clone().into(),
+                    order_item_id: order_item.base_entity.id.clone().into(),
+                    option_id: selected_option.option_id,
+                    group_id: selected_option.group_id,
+                    name: selected_option.name,
+                    price_delta: selected_option.price_delta.into(),
+                }
+            }
+        }
+
+        impl From<Model> for SelectedOption {
+            fn from(model: Model) -> Self {
+                SelectedOptionBuilder::default()
+                    .group_id(model.group_id)
+                    .option_id(model.option_id)
+                    .name(model.name)
+                    .price_delta(model.price_delta.into())
+                    .build()
+                    .unwrap()
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {
+            #[sea_orm(
+                belongs_to = "super::order::Entity",
+                from = "Column::OrderId",
+                to = "super::order::Column::Id"
+            )]
+            Order,
+        }
+
+        impl Related<super::order::Entity> for Entity {
+            fn to() -> RelationDef {
+                Relation::Order.def()
+            }
+        }
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod order_stream {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+
+        use domain_core::entity::Order;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "order_streams")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub order_id: uuid::Uuid,
+            pub tracking_id: uuid::Uuid,
+            pub customer_id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub order_status: String,
+            pub scheduled_for: Option<chrono::DateTime<chrono::Utc>>,
+            pub version: i64,
+            pub status_changed_at: Option<chrono::DateTime<chrono::Utc>>,
+            pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+            pub checkout_id: Option<uuid::Uuid>,
+        }
+
+        impl Model {
+            pub fn from_order(order: &Order, version: i64) -> Self {
+                Self {
+                    order_id: order.clone().into(),
+                    tracking_id: order.tracking_id.clone().into(),
+                    customer_id: order.customer_id.clone().into(),
+                    restaurant_id: order.restaurant_id.clone().into(),
+                    order_status: order.order_status.to_string(),
+                    scheduled_for: order.scheduled_for,
+                    version,
+                    status_changed_at: order.status_changed_at,
+                    created_at: order.created_at,
+                    checkout_id: order.checkout.as_ref().map(|checkout| checkout.id),
+                }
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod order_event {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
+
+        use common::error::OrderDomainError;
+        use domain_core::event::OrderEvent;
+
+        use super::{order_adjustment, order_snapshot::OrderState};
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "order_events")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub order_id: uuid::Uuid,
+            pub version: i64,
+            pub event_type: String,
+            pub payload: Json,
+            pub recorded_at: chrono::DateTime<chrono::Utc>,
+        }
+
+        #[derive(Clone, Serialize, Deserialize)]
+        pub enum OrderEventPayload {
+            Created(Box<OrderState>),
+            Scheduled {
+                scheduled_for: chrono::DateTime<chrono::Utc>,
+            },
+            Released,
+            Paid,
+            Approved,
+            ItemAdjusted(order_adjustment::Model),
+            CancelInitiated {
+                failure_messages: Vec<String>,
+            },
+            Cancelled {
+                failure_messages: Vec<String>,
+            },
+            SharePaid {
+                customer_id: uuid::Uuid,
+            },
+            ShareFailed {
+                customer_id: uuid::Uuid,
+            },
+            DeliveryEstimated {
+                estimated_delivery_at: Option<chrono::DateTime<chrono::Utc>>,
+            },
+        }
+
+        impl OrderEventPayload {
+            pub fn from_event(order_id: uuid::Uuid, event: OrderEvent) -> Self {
+                match event {
+                    OrderEvent::Scheduled(scheduled_for) => {
+                        OrderEventPayload::Scheduled { scheduled_for }
+                    }
+                    OrderEvent::Released => OrderEventPayload::Released,
+                    OrderEvent::Paid => OrderEventPayload::Paid,
+                    OrderEvent::Approved => OrderEventPayload::Approved,
+                    OrderEvent::ItemAdjusted(adjustment) => OrderEventPayload::ItemAdjusted(
+                        order_adjustment::Model::from_adjustment(order_id, adjustment),
+                    ),
+                    OrderEvent::CancelInitiated(failure_messages) => {
+                        OrderEventPayload::CancelInitiated { failure_messages }
+                    }
+                    OrderEvent::Cancelled(failure_messages) => {
+                        OrderEventPayload::Cancelled { failure_messages }
+                    }
+                    OrderEvent::SharePaid(customer_id) => {
+                        OrderEventPayload::SharePaid { customer_id }
+                    }
+                    OrderEvent::ShareFailed(customer_id) => {
+                        OrderEventPayload::ShareFailed { customer_id }
+                    }
+                    OrderEvent::DeliveryEstimated(estimated_delivery_at) => {
+                        OrderEventPayload::DeliveryEstimated {
+                            estimated_delivery_at,
+                        }
+                    }
+                }
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    OrderEventPayload::Created(_) => "Created",
+                    OrderEventPayload::Scheduled { .. } => "Scheduled",
+                    OrderEventPayload::Released => "Released",
+                    OrderEventPayload::Paid => "Paid",
+                    OrderEventPayload::Approved => "Approved",
+                    OrderEventPayload::ItemAdjusted(_) => "ItemAdjusted",
+                    OrderEventPayload::CancelInitiated { .. } => "CancelInitiated",
+                    OrderEventPayload::Cancelled { .. } => "Cancelled",
+                    OrderEventPayload::SharePaid { .. } => "SharePaid",
+                    OrderEventPayload::ShareFailed { .. } => "ShareFailed",
+                    OrderEventPayload::DeliveryEstimated { .. } => "DeliveryEstimated",
+                }
+            }
+
+            pub fn into_event(self) -> Result<Option<OrderEvent>, OrderDomainError> {
+                Ok(match self {
+                    OrderEventPayload::Created(_) => None,
+                    OrderEventPayload::Scheduled { scheduled_for } => {
+                        Some(OrderEvent::Scheduled(scheduled_for))
+                    }
+                    OrderEventPayload::Released => Some(OrderEvent::Released),
+                    OrderEventPayload::Paid => Some(OrderEvent::Paid),
+                    OrderEventPayload::Approved => Some(OrderEvent::Approved),
+                    OrderEventPayload::ItemAdjusted(adjustment_model) => {
+                        Some(OrderEvent::ItemAdjusted(adjustment_model.try_into()?))
+                    }
+                    OrderEventPayload::CancelInitiated { failure_messages } => {
+                        Some(OrderEvent::CancelInitiated(failure_messages))
+                    }
+                    OrderEventPayload::Cancelled { failure_messages } => {
+                        Some(OrderEvent::Cancelled(failure_messages))
+                    }
+                    OrderEventPayload::SharePaid { customer_id } => {
+                        Some(OrderEvent::SharePaid(customer_id))
+                    }
+                    OrderEventPayload::ShareFailed { customer_id } => {
+                        Some(OrderEvent::ShareFailed(customer_id))
+                    }
+                    OrderEventPayload::DeliveryEstimated {
+                        estimated_delivery_at,
+                    } => Some(OrderEvent::DeliveryEstimated(estimated_delivery_at)),
+                })
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod order_snapshot {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
+
+        use common::error::OrderDomainError;
+        use domain_core::entity::{Order, OrderAdjustment, OrderItem};
+
+        use super::{
+            order, order_address, order_adjustment, order_dietary_conflict, order_item,
+            order_item_option, order_payment_share,
+        };
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "order_snapshots")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub order_id: uuid::Uuid,
+            #[sea_orm(primary_key)]
+            pub version: i64,
+            pub state: Json,
+            pub taken_at: chrono::DateTime<chrono::Utc>,
+        }
+
+        #[derive(Clone, Serialize, Deserialize)]
+        pub struct OrderState {
+            pub order: order::Model,
+            pub address: order_address::Model,
+            pub items: Vec<order_item::Model>,
+            pub item_options: Vec<order_item_option::Model>,
+            pub adjustments: Vec<order_adjustment::Model>,
+            #[serde(default)]
+            pub payment_shares: Vec<order_payment_share::Model>,
+            #[serde(default)]
+            pub dietary_conflicts: Vec<order_dietary_conflict::Model>,
+        }
+
+        impl From<Order> for OrderState {
+            fn from(o: Order) -> Self {
+                let order_id: uuid::Uuid = o.clone().into();
+                Self {
+                    order: order::Model::from(o.clone()),
+                    address: order_address::Model::from(o.clone()),
+                    items: o
+                        .items
+                        .clone()
+                        .into_iter()
+                        .map(order_item::Model::from)
+                        .collect(),
+                    item_options: o
+                        .items
+                        .iter()
+                        .flat_map(|item| {
+                            item.selected_options
+                                .clone()
+                                .into_iter()
+                                .map(|selected_option| {
+                                    order_item_option::Model::from_selected_option(
+                                        item,
+                                        selected_option,
+                                    )
+                                })
+                        })
+                        .collect(),
+                    adjustments: o
+                        .adjustments
+                        .into_iter()
+                        .map(|adjustment| {
+                            order_adjustment::Model::from_adjustment(order_id, adjustment)
+                        })
+                        .collect(),
+                    payment_shares: o
+                        .payment_shares
+                        .into_iter()
+                        .map(|share| order_payment_share::Model::from_share(order_id, share))
+                        .collect(),
+                    dietary_conflicts: o
+                        .dietary_conflicts
+                        .into_iter()
+                        .map(|conflict| {
+                            order_dietary_conflict::Model::from_conflict(order_id, conflict)
+                        })
+                        .collect(),
+                }
+            }
+        }
+
+        impl TryFrom<OrderState> for Order {
+            type Error = OrderDomainError;
+
+            fn try_from(order_state: OrderState) -> Result<Self, Self::Error> {
+                let items: Vec<OrderItem> = order_state
+                    .items
+                    .into_iter()
+                    .map(|item_model| item_model.into_order_item(&order_state.item_options))
+                    .collect();
+                let adjustments: Vec<OrderAdjustment> = order_state
+                    .adjustments
+                    .into_iter()
+                    .map(OrderAdjustment::try_from)
+                    .collect::<Result<_, _>>()?;
+                let mut order = order_state
+                    .order
+                    .into_order(order_state.address.into(), items);
+                order.adjustments = adjustments;
+                order.payment_shares = order_state
+                    .payment_shares
+                    .into_iter()
+                    .map(|share_model| share_model.into())
+                    .collect();
+                order.dietary_conflicts = order_state
+                    .dietary_conflicts
+                    .into_iter()
+                    .filter_map(|conflict_model| conflict_model.into_conflict())
+                    .collect();
+                Ok(order)
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod order_tracking_view {
+        use sea_orm::entity::prelude::*;
+        use sea_orm::DeriveEntityModel;
+        use serde::{Deserialize, Serialize};
+
+        use common::value_object::OrderStatus;
+        use domain_core::event::OrderEvent;
+        use service::dto::track::{OrderStatusChange, TrackOrderResponse};
+
+        use super::order;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "order_tracking_view")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub order_id: uuid::Uuid,
+            pub tracking_id: uuid::Uuid,
+            pub order_status: String,
+            pub failure_messages: String,
+            pub tip: i64,
+            pub tip_recipient: Option<String>,
+            pub status_history: Json,
+            pub estimated_delivery_at: Option<chrono::DateTime<chrono::Utc>>,
+        }
+
+        #[derive(Clone, Serialize, Deserialize)]
+        pub struct StatusChange {
+            pub order_status: String,
+            pub changed_at: chrono::DateTime<chrono::Utc>,
+        }
+
+        impl Model {
+            pub fn from_order_model(order_model: &order::Model) -> Self {
+                let status_history: Vec<StatusChange> = order_model
+                    .status_changed_at
+                    .map(|changed_at| StatusChange {
+                        order_status: order_model.order_status.clone(),
+                        changed_at,
+                    })
+                    .into_iter()
+                    .collect();
+                Self {
+                    order_id: order_model.id,
+                    tracking_id: order_model.tracking_id,
+                    order_status: order_model.order_status.clone(),
+                    failure_messages: order_model.failure_messages.clone(),
+                    tip: order_model.tip,
+                    tip_recipient: order_model.tip_recipient.clone(),
+                    status_history: serde_json::to_value(status_history).unwrap_or_default(),
+                    estimated_delivery_at: order_model
+                        .estimated_delivery_at
+                        .or(order_model.scheduled_for),
+                }
+            }
+
+            pub fn apply(&mut self, event: &OrderEvent, changed_at: chrono::DateTime<chrono::Utc>) {
+                match event {
+                    OrderEvent::Scheduled(scheduled_for) => {
+                        self.estimated_delivery_at = Some(*scheduled_for);
+                        self.change_status(OrderStatus::Scheduled, changed_at);
+                    }
+                    OrderEvent::Released => self.change_status(OrderStatus::Pending, changed_at),
+                    OrderEvent::Paid => self.change_status(OrderStatus::Paid, changed_at),
+                    OrderEvent::Approved => self.change_status(OrderStatus::Approved, changed_at),
+                    OrderEvent::ItemAdjusted(_)
+                    | OrderEvent::SharePaid(_)
+                    | OrderEvent::ShareFailed(_) => {}
+                    OrderEvent::DeliveryEstimated(estimated_delivery_at) => {
+                        self.estimated_delivery_at = *estimated_delivery_at
+                    }
+                    OrderEvent::CancelInitiated(failure_messages) => {
+                        self.add_failure_messages(failure_messages);
+                        self.change_status(OrderStatus::Cancelling, changed_at);
+                    }
+                    OrderEvent::Cancelled(failure_messages) => {
+                        self.add_failure_messages(failure_messages);
+                        self.change_status(OrderStatus::Cancelled, changed_at);
+                    }
+                }
+            }
+
+            fn status_history(&self) -> Vec<StatusChange> {
+                serde_json::from_value(self.status_history.clone()).unwrap_or_default()
+            }
+
+            fn change_status(
+                &mut self,
+                order_status: OrderStatus,
+                changed_at: chrono::DateTime<chrono::Utc>,
+            ) {
+                let mut status_history = self.status_history();
+                status_history.push(StatusChange {
+                    order_status: order_status.to_string(),
+                    changed_at,
+                });
+                self.order_status = order_status.to_string();
+                self.status_history = serde_json::to_value(status_history).unwrap_or_default();
+            }
+
+            fn add_failure_messages(&mut self, failure_messages: &[String]) {
+                let mut all_failure_messages: Vec<String> = self
+                    .failure_messages
+                    .split("#")
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string())
+                    .collect();
+                all_failure_messages.extend(failure_messages.iter().cloned());
+                self.failure_messages = all_failure_messages.join("#");
+            }
+
+            pub fn into_response(self) -> TrackOrderResponse {
+                let status_history: Vec<OrderStatusChange> = self
+                    .status_history()
+                    .into_iter()
+                    .filter_map(|status_change| {
+                        Some(OrderStatusChange {
+                            order_status: status_change.order_status.parse().ok()?,
+                            changed_at: status_change.changed_at,
+                        })
+                    })
+                    .collect();
+                TrackOrderResponse {
+                    order_tracking_id: self.tracking_id,
+                    order_status: self.order_status.parse().unwrap(),
+                    failure_messages: self
+                        .failure_messages
+                        .split("#")
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.to_string())
+                        .collect(),
+                    tip: self.tip.into(),
+                    tip_recipient: self
+                        .tip_recipient
+                        .map(|tip_recipient| tip_recipient.parse().unwrap_or_default()),
+                    status_history,
+                    estimated_delivery_at: self.estimated_delivery_at,
+                }
+            }
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+}
+
+pub mod projection {
+    use sea_orm::{
+        ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+        TransactionTrait,
+    };
+
+    use common::error::OrderDomainError;
+    use domain_core::{entity::Order, event::OrderEvent, value_object::TrackingId};
+    use service::{
+        dto::track::TrackOrderResponse, ports::output::repository::OrderTrackingViewRepository,
+    };
+
+    use crate::entity::{order, order_tracking_view};
+
+    #[cfg(feature = "event-store")]
+    use {
+        crate::entity::order_event::{self, OrderEventPayload},
+        sea_orm::QueryOrder,
+    };
+
+    #[derive(Default)]
+    pub struct OrderTrackingProjector;
+
+    impl OrderTrackingProjector {
+        pub async fn project<D: ConnectionTrait>(
+            &self,
+            db: &D,
+            order: &Order,
+            events: &[OrderEvent],
+            changed_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), OrderDomainError> {
+            let order_id: uuid::Uuid = order.clone().into();
+            let order_tracking_view_model = order_tracking_view::Entity::find_by_id(order_id)
+                .one(db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            match order_tracking_view_model {
+                Some(mut order_tracking_view_model) => {
+                    for event in events {
+                        order_tracking_view_model.apply(event, changed_at);
+                    }
+                    let _update_result =
+                        order_tracking_view::ActiveModel::from(order_tracking_view_model)
+                            .reset_all()
+                            .update(db)
+                            .await
+                            .map_err(|_| OrderDomainError::SaveOrderError)?;
+                }
+                None => {
+                    let _save_result = order_tracking_view::ActiveModel::from(
+                        order_tracking_view::Model::from_order_model(&order::Model::from(
+                            order.clone(),
+                        )),
+                    )
+                    .insert(db)
+                    .await
+                    .map_err(|_| OrderDomainError::SaveOrderError)?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    pub struct OrderTrackingViewRepositoryImpl {
+        db: sea_orm::DatabaseConnection,
+    }
+
+    impl OrderTrackingViewRepositoryImpl {
+        pub fn new(db: sea_orm::DatabaseConnection) -> Self {
+            Self { db }
+        }
+
+        async fn insert_view<D: ConnectionTrait>(
+            &self,
+            db: &D,
+            order_tracking_view_model: order_tracking_view::Model,
+        ) -> Result<(), OrderDomainError> {
+            let _save_result = order_tracking_view::ActiveModel::from(order_tracking_view_model)
+                .insert(db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(())
+        }
+
+        #[cfg(not(feature = "event-store"))]
+        async fn rebuild_from_orders<D: ConnectionTrait>(
+            &self,
+            db: &D,
+        ) -> Result<u64, OrderDomainError> {
+            let mut order_pages = order::Entity::find().paginate(db, REBUILD_PAGE_SIZE);
+            let mut rebuilt = 0;
+            while let Some(order_models) = order_pages
+                .fetch_and_next()
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?
+            {
+                for order_model in order_models {
+                    self.insert_view(
+                        db,
+                        order_tracking_view::Model::from_order_model(&order_model),
+                    )
+                    .await?;
+                    rebuilt += 1;
+                }
+            }
+            Ok(rebuilt)
+        }
+
+        #[cfg(feature = "event-store")]
+        async fn rebuild_from_event_log<D: ConnectionTrait>(
+            &self,
+            db: &D,
+        ) -> Result<u64, OrderDomainError> {
+            let mut order_event_pages = order_event::Entity::find()
+                .order_by_asc(order_event::Column::OrderId)
+                .order_by_asc(order_event::Column::Version)
+                .paginate(db, REBUILD_PAGE_SIZE);
+            let mut current: Option<order_tracking_view::Model> = None;
+            let mut rebuilt = 0;
+            while let Some(order_event_models) = order_event_pages
+                .fetch_and_next()
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?
+            {
+                for order_event_model in order_event_models {
+                    let payload: OrderEventPayload =
+                        serde_json::from_value(order_event_model.payload)
+                            .map_err(|_| OrderDomainError::OrderNotFound)?;
+                    match payload {
+                        OrderEventPayload::Created(order_state) => {
+                            if let Some(order_tracking_view_model) = current.take() {
+                                self.insert_view(db, order_tracking_view_model).await?;
+                                rebuilt += 1;
+                            }
+                            current = Some(order_tracking_view::Model::from_order_model(
+                                &order_state.order,
+                            ));
+                        }
+                        payload => {
+                            if let (Some(order_tracking_view_model), Some(event)) =
+                                (current.as_mut(), payload.into_event()?)
+                            {
+                                order_tracking_view_model
+                                    .apply(&event, order_event_model.recorded_at);
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(order_tracking_view_model) = current {
+                self.insert_view(db, order_tracking_view_model).await?;
+                rebuilt += 1;
+            }
+            Ok(rebuilt)
+        }
+    }
+
+    const REBUILD_PAGE_SIZE: u64 = 500;
+
+    #[async_trait::async_trait]
+    impl OrderTrackingViewRepository for OrderTrackingViewRepositoryImpl {
+        async fn find_by_tracking_id(
+            &self,
+            id: TrackingId,
+        ) -> Result<TrackOrderResponse, OrderDomainError> {
+            let tracking_uuid: uuid::Uuid = id.into();
+            let order_tracking_view_model = order_tracking_view::Entity::find()
+                .filter(order_tracking_view::Column::TrackingId.eq(tracking_uuid))
+                .one(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?
+                .ok_or(OrderDomainError::OrderNotFound)?;
+            Ok(order_tracking_view_model.into_response())
+        }
+
+        async fn rebuild(&self) -> Result<u64, OrderDomainError> {
+            let txn = self
+                .db
+                .begin()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let _delete_result = order_tracking_view::Entity::delete_many()
+                .exec(&txn)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            #[cfg(feature = "event-store")]
+            let rebuilt = self.rebuild_from_event_log(&txn).await?;
+            #[cfg(not(feature = "event-store"))]
+            let rebuilt = self.rebuild_from_orders(&txn).await?;
+            txn.commit()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(rebuilt)
+        }
+    }
+}
+
+pub mod query {
+    use std::collections::HashMap;
+
+    use sea_orm::{
+        sea_query::NullOrdering, ColumnTrait, Condition, EntityTrait, QueryFilter, QuerySelect,
+        QueryTrait, Select,
+    };
+
+    use service::dto::list::{OrderCursor, OrderSearchCriteria, OrderSort};
+
+    pub fn group_by_order<M>(
+        models: Vec<M>,
+        order_id: impl Fn(&M) -> uuid::Uuid,
+    ) -> HashMap<uuid::Uuid, Vec<M>> {
+        let mut grouped_models: HashMap<uuid::Uuid, Vec<M>> = HashMap::new();
+        for model in models {
+            grouped_models
+                .entry(order_id(&model))
+                .or_default()
+                .push(model);
+        }
+        grouped_models
+    }
+
+    pub struct OrderSearchColumns<C: ColumnTrait> {
+        pub customer_id: C,
+        pub restaurant_id: C,
+        pub order_status: C,
+        pub created_at: C,
+        pub order_id: C,
+    }
+
+    impl<C: ColumnTrait> OrderSearchColumns<C> {
+        pub fn select<E: EntityTrait<Column = C>>(
+            &self,
+            criteria: &OrderSearchCriteria,
+        ) -> Select<E> {
+            let mut condition = Condition::all();
+            if let Some(customer_id) = criteria.customer_id {
+                condition = condition.add(self.customer_id.eq(customer_id));
+            }
+            if let Some(restaurant_id) = criteria.restaurant_id {
+                condition = condition.add(self.restaurant_id.eq(restaurant_id));
+            }
+            if let Some(order_status) = criteria.order_status.clone() {
+                condition = condition.add(self.order_status.eq(order_status.to_string()));
+            }
+            if let Some(created_from) = criteria.created_from {
+                condition = condition.add(self.created_at.gte(created_from));
+            }
+            if let Some(created_to) = criteria.created_to {
+                condition = condition.add(self.created_at.lt(created_to));
+            }
+            if let Some(after) = criteria.after {
+                condition = condition.add(self.after(criteria.sort, after));
+            }
+            let (direction, nulls) = match criteria.sort {
+                OrderSort::NewestFirst => (sea_orm::Order::Desc, NullOrdering::Last),
+                OrderSort::OldestFirst => (sea_orm::Order::Asc, NullOrdering::First),
+            };
+            let mut select = E::find().filter(condition).limit(criteria.limit);
+            QueryTrait::query(&mut select)
+                .order_by_with_nulls((E::default(), self.created_at), direction.clone(), nulls)
+                .order_by((E::default(), self.order_id), direction);
+            select
+        }
+
+        fn after(&self, sort: OrderSort, after: OrderCursor) -> Condition {
+            match (sort, after.created_at) {
+                (OrderSort::NewestFirst, Some(created_at)) => Condition::any()
+                    .add(self.created_at.lt(created_at))
+                    .add(
+                        Condition::all()
+                            .add(self.created_at.eq(created_at))
+                            .add(self.order_id.lt(after.order_id)),
+                    )
+                    .add(self.created_at.is_null()),
+                (OrderSort::NewestFirst, None) => Condition::all()
+                    .add(self.created_at.is_null())
+                    .add(self.order_id.lt(after.order_id)),
+                (OrderSort::OldestFirst, Some(created_at)) => {
+                    Condition::any().add(self.created_at.gt(created_at)).add(
+                        Condition::all()
+                            .add(self.created_at.eq(created_at))
+                            .add(self.order_id.gt(after.order_id)),
+                    )
+                }
+                (OrderSort::OldestFirst, None) => {
+                    Condition::any().add(self.created_at.is_not_null()).add(
+                        Condition::all()
+                            .add(self.created_at.is_null())
+                            .add(self.order_id.gt(after.order_id)),
+                    )
+                }
+            }
+        }
+    }
+}
+
+pub mod repository {
+    use std::collections::BTreeMap;
+
+    use chrono::NaiveDate;
+    use chrono_tz::Tz;
+    use sea_orm::{
+        ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, TransactionTrait,
+    };
+
+    use common::{clock::Clock, error::OrderDomainError};
+    use domain_core::{
+        delivery::DeliveryZone,
+        entity::{Customer, Product, Restaurant},
+        group_order::GroupOrder,
+        opening_hours::{
+            OpeningException, OpeningExceptionBuilder, OpeningHours, OpeningHoursBuilder,
+            OpeningPeriod, Pause, WeeklyOpeningPeriod, WeeklyOpeningPeriodBuilder,
+        },
+        risk::RiskCheck,
+        subscription::{OrderSubscription, SubscriptionStatus},
+    };
+    use service::ports::output::repository::{
+        CustomerRepository, GroupOrderRepository, OrderSubscriptionRepository,
+        RestaurantRepository, RiskCheckRepository,
+    };
+
+    use crate::entity::{
+        customer, group_order, order_risk_check, order_subscription, restaurant,
+        restaurant_delivery_zone, restaurant_opening_hours, restaurant_pause,
+        restaurant_product_option, subscription_occurrence,
+    };
+
+    use {
+        crate::entity::{
+            order, order_address, order_adjustment, order_dietary_conflict, order_item,
+            order_item_option, order_payment_share,
+        },
+        crate::projection::OrderTrackingProjector,
+        crate::query::{group_by_order, OrderSearchColumns},
+        common::value_object::OrderStatus,
+        domain_core::{
+            dietary::DietaryConflict,
+            entity::{Order, OrderAdjustment, OrderItem, PaymentShare},
+            value_object::{StreetAddress, TrackingId},
+        },
+        sea_orm::{Condition, PaginatorTrait},
+        service::{
+            dto::list::OrderSearchCriteria,
+            ports::output::repository::{OrderQueryRepository, OrderRepository},
+        },
+    };
+
+    pub struct OrderRepositoryImpl<C: Clock> {
+        db: sea_orm::DatabaseConnection,
+        tracking_projector: OrderTrackingProjector,
+        clock: C,
+    }
+
+    impl<C: Clock> OrderRepositoryImpl<C> {
+        pub fn new(db: sea_orm::DatabaseConnection, clock: C) -> Self {
+            Self {
+                tracking_projector: OrderTrackingProjector,
+                db,
+                clock,
+            }
+        }
+
+        async fn load_order(
+            &self,
+            order_model: order::Model,
+            street_address_model: Option<order_address::Model>,
+        ) -> Result<Order, OrderDomainError> {
+            self.load_orders(vec![(order_model, street_address_model)])
+                .await?
+                .pop()
+                .ok_or(OrderDomainError::OrderNotFound)
+        }
+
+        async fn load_orders(
+            &self,
+            order_models: Vec<(order::Model, Option<order_address::Model>)>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_ids: Vec<uuid::Uuid> = order_models
+                .iter()
+                .map(|(order_model, _)| order_model.id)
+                .collect();
+            let mut order_item_models = group_by_order(
+                order_item::Entity::find()
+                    .filter(order_item::Column::OrderId.is_in(order_ids.clone()))
+                    .all(&self.db)
+                    .await
+                    .map_err(|_| OrderDomainError::OrderItemNotFound)?,
+                |order_item_model| order_item_model.order_id,
+            );
+            let mut order_item_option_models = group_by_order(
+                order_item_option::Entity::find()
+                    .filter(order_item_option::Column::OrderId.is_in(order_ids.clone()))
+                    .all(&self.db)
+                    .await
+                    .map_err(|_| OrderDomainError::OrderItemNotFound)?,
+                |order_item_option_model| order_item_option_model.order_id,
+            );
+            let mut order_adjustment_models = group_by_order(
+                order_adjustment::Entity::find()
+                    .filter(order_adjustment::Column::OrderId.is_in(order_ids.clone()))
+                    .all(&self.db)
+                    .await
+                    .map_err(|_| OrderDomainError::OrderNotFound)?,
+                |order_adjustment_model| order_adjustment_model.order_id,
+            );
+            let mut order_payment_share_models = group_by_order(
+                order_payment_share::Entity::find()
+                    .filter(order_payment_share::Column::OrderId.is_in(order_ids.clone()))
+                    .all(&self.db)
+                    .await
+                    .map_err(|_| OrderDomainError::OrderNotFound)?,
+                |order_payment_share_model| order_payment_share_model.order_id,
+            );
+            let mut order_dietary_conflict_models = group_by_order(
+                order_dietary_conflict::Entity::find()
+                    .filter(order_dietary_conflict::Column::OrderId.is_in(order_ids))
+                    .all(&self.db)
+                    .await
+                    .map_err(|_| OrderDomainError::OrderNotFound)?,
+                |order_dietary_conflict_model| order_dietary_conflict_model.order_id,
+            );
+            let mut orders: Vec<Order> = vec![];
+            for (order_model, street_address_model) in order_models {
+                let order_id = order_model.id;
+                let street_address: StreetAddress = street_address_model
+                    .ok_or(OrderDomainError::OrderNotFound)?
+                    .into();
+                let order_item_option_models = order_item_option_models
+                    .remove(&order_id)
+                    .unwrap_or_default();
+                let order_items: Vec<OrderItem> = order_item_models
+                    .remove(&order_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|order_item_model| {
+                        order_item_model.into_order_item(&order_item_option_models)
+                    })
+                    .collect();
+                let adjustments: Vec<OrderAdjustment> = order_adjustment_models
+                    .remove(&order_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(OrderAdjustment::try_from)
+                    .collect::<Result<_, _>>()?;
+                let payment_shares: Vec<PaymentShare> = order_payment_share_models
+                    .remove(&order_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|share_model| share_model.into())
+                    .collect();
+                let dietary_conflicts: Vec<DietaryConflict> = order_dietary_conflict_models
+                    .remove(&order_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|conflict_model| conflict_model.into_conflict())
+                    .collect();
+                let mut order = order_model.into_order(street_address, order_items);
+                order.adjustments = adjustments;
+                order.payment_shares = payment_shares;
+                order.dietary_conflicts = dietary_conflicts;
+                orders.push(order);
+            }
+            Ok(orders)
+        }
+
+        async fn insert_order<D: ConnectionTrait>(
+            &self,
+            db: &D,
+            order: &Order,
+        ) -> Result<(), OrderDomainError> {
+            let order_model: order::Model = order::Model::from(order.clone());
+            let order_active_model: order::ActiveModel = order::ActiveModel::from(order_model);
+            let _save_result = order_active_model
+                .insert(db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let order_address_model: order_address::Model =
+                order_address::Model::from(order.clone());
+            let _save_result = order_address::ActiveModel::from(order_address_model)
+                .insert(db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let order_item_active_models: Vec<order_item::ActiveModel> = order
+                .items
+                .clone()
+                .into_iter()
+                .map(|item| order_item::ActiveModel::from(order_item::Model::from(item)))
+                .collect();
+            if !order_item_active_models.is_empty() {
+                let _save_result = order_item::Entity::insert_many(order_item_active_models)
+                    .exec(db)
+                    .await
+                    .map_err(|_| OrderDomainError::SaveOrderError)?;
+            }
+            let order_item_option_active_models: Vec<order_item_option::ActiveModel> = order
+                .items
+                .iter()
+                .flat_map(|item| {
+                    item.selected_options
+                        .clone()
+                        .into_iter()
+                        .map(|selected_option| {
+                            order_item_option::ActiveModel::from(
+                                order_item_option::Model::from_selected_option(
+                                    item,
+                                    selected_option,
+                                ),
+                            )
+                        })
+                })
+                .collect();
+            if !order_item_option_active_models.is_empty() {
+                let _save_result =
+                    order_item_option::Entity::insert_many(order_item_option_active_models)
+                        .exec(db)
+                        .await
+                        .map_err(|_| OrderDomainError::SaveOrderError)?;
+            }
+            self.replace_payment_shares(db, order).await?;
+            self.save_dietary_conflicts(db, order).await?;
+            self.tracking_projector
+                .project(db, order, &[], self.clock.now())
+                .await?;
+            Ok(())
+        }
+
+        async fn save_dietary_conflicts<D: ConnectionTrait>(
+            &self,
+            db: &D,
+            order: &Order,
+        ) -> Result<(), OrderDomainError> {
+            let order_id: uuid::Uuid = order.clone().into();
+            let order_dietary_conflict_active_models: Vec<order_dietary_conflict::ActiveModel> =
+                order
+                    .dietary_conflicts
+                    .clone()
+                    .into_iter()
+                    .map(|conflict| {
+                        order_dietary_conflict::ActiveModel::from(
+                            order_dietary_conflict::Model::from_conflict(order_id, conflict),
+                        )
+                    })
+                    .collect();
+            if !order_dietary_conflict_active_models.is_empty() {
+                let _save_result = order_dietary_conflict::Entity::insert_many(
+                    order_dietary_conflict_active_models,
+                )
+                .exec(db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            }
+            Ok(())
+        }
+
+        async fn replace_payment_shares<D: ConnectionTrait>(
+            &self,
+            db: &D,
+            order: &Order,
+        ) -> Result<(), OrderDomainError> {
+            let order_id: uuid::Uuid = order.clone().into();
+            let _delete_result = order_payment_share::Entity::delete_many()
+                .filter(order_payment_share::Column::OrderId.eq(order_id))
+                .exec(db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let order_payment_share_active_models: Vec<order_payment_share::ActiveModel> = order
+                .payment_shares
+                .clone()
+                .into_iter()
+                .map(|share| {
+                    order_payment_share::ActiveModel::from(order_payment_share::Model::from_share(
+                        order_id, share,
+                    ))
+                })
+                .collect();
+            if !order_payment_share_active_models.is_empty() {
+                let _save_result =
+                    order_payment_share::Entity::insert_many(order_payment_share_active_models)
+                        .exec(db)
+                        .await
+                        .map_err(|_| OrderDomainError::SaveOrderError)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<C: Clock> OrderRepository for OrderRepositoryImpl<C> {
+        async fn save(&self, mut order: Order) -> Result<Order, OrderDomainError> {
+            let _pending_events = order.take_pending_events();
+            let txn = self
+                .db
+                .begin()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            self.insert_order(&txn, &order).await?;
+            txn.commit()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(order)
+        }
+        async fn save_all(&self, mut orders: Vec<Order>) -> Result<Vec<Order>, OrderDomainError> {
+            let txn = self
+                .db
+                .begin()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            for order in orders.iter_mut() {
+                let _pending_events = order.take_pending_events();
+                self.insert_order(&txn, order).await?;
+            }
+            txn.commit()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(orders)
+        }
+        async fn update(&self, mut order: Order) -> Result<Order, OrderDomainError> {
+            let pending_events = order.take_pending_events();
+            let expected_version = order.aggregate_root.version - pending_events.len() as i64;
+            let txn = self
+                .db
+                .begin()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let order_model: order::Model = order::Model::from(order.clone());
+            let order_active_model: order::ActiveModel =
+                order::ActiveModel::from(order_model.clone()).reset_all();
+            let update_result = order::Entity::update_many()
+                .set(order_active_model)
+                .filter(order::Column::Id.eq(order_model.id))
+                .filter(order::Column::Version.eq(expected_version))
+                .exec(&txn)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            if update_result.rows_affected == 0 {
+                return Err(OrderDomainError::ConcurrentModification);
+            }
+            for item in order.items.clone() {
+                let _update_result = order_item::ActiveModel::from(order_item::Model::from(item))
+                    .reset_all()
+                    .update(&txn)
+                    .await
+                    .map_err(|_| OrderDomainError::SaveOrderError)?;
+            }
+            let order_id: uuid::Uuid = order.clone().into();
+            let _delete_result = order_adjustment::Entity::delete_many()
+                .filter(order_adjustment::Column::OrderId.eq(order_id))
+                .exec(&txn)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let order_adjustment_active_models: Vec<order_adjustment::ActiveModel> = order
+                .adjustments
+                .clone()
+                .into_iter()
+                .map(|adjustment| {
+                    order_adjustment::ActiveModel::from(order_adjustment::Model::from_adjustment(
+                        order_id, adjustment,
+                    ))
+                })
+                .collect();
+            if !order_adjustment_active_models.is_empty() {
+                let _save_result =
+                    order_adjustment::Entity::insert_many(order_adjustment_active_models)
+                        .exec(&txn)
+                        .await
+                        .map_err(|_| OrderDomainError::SaveOrderError)?;
+            }
+            self.replace_payment_shares(&txn, &order).await?;
+            self.tracking_projector
+                .project(&txn, &order, &pending_events, self.clock.now())
+                .await?;
+            txn.commit()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(order)
+        }
+        async fn find_by_id(&self, order_id: uuid::Uuid) -> Result<Order, OrderDomainError> {
+            let (order_model, street_model_optional) = order::Entity::find_by_id(order_id)
+                .find_also_related(order_address::Entity)
+                .one(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?
+                .ok_or(OrderDomainError::OrderNotFound)?;
+            self.load_order(order_model, street_model_optional).await
+        }
+        async fn find_by_tracking_id(&self, id: TrackingId) -> Result<Order, OrderDomainError> {
+            let tracking_uuid: uuid::Uuid = id.into();
+            let (order_model, street_model_optional) = order::Entity::find()
+                .filter(order::Column::TrackingId.eq(tracking_uuid))
+                .find_also_related(order_address::Entity)
+                .one(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?
+                .ok_or(OrderDomainError::OrderNotFound)?;
+            self.load_order(order_model, street_model_optional).await
+        }
+        async fn find_scheduled_due(
+            &self,
+            release_cutoff: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_models = order::Entity::find()
+                .filter(order::Column::OrderStatus.eq(OrderStatus::Scheduled.to_string()))
+                .filter(order::Column::ScheduledFor.lte(release_cutoff))
+                .find_also_related(order_address::Entity)
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?;
+            self.load_orders(order_models).await
+        }
+        async fn find_stuck(
+            &self,
+            order_status: OrderStatus,
+            status_changed_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_models = order::Entity::find()
+                .filter(order::Column::OrderStatus.eq(order_status.to_string()))
+                .filter(
+                    Condition::any()
+                        .add(order::Column::StatusChangedAt.lte(status_changed_before))
+                        .add(order::Column::StatusChangedAt.is_null()),
+                )
+                .find_also_related(order_address::Entity)
+                .all(&self.db)
+                .await
+                .map_err(|error| OrderDomainError::PersistenceError(error.to_string()))?;
+            self.load_orders(order_models).await
+        }
+        async fn find_by_checkout_id(
+            &self,
+            checkout_id: uuid::Uuid,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_models = order::Entity::find()
+                .filter(order::Column::CheckoutId.eq(checkout_id))
+                .find_also_related(order_address::Entity)
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?;
+            self.load_orders(order_models).await
+        }
+        async fn count_open_orders_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<u64, OrderDomainError> {
+            let open_statuses: Vec<String> = OrderStatus::open_statuses()
+                .iter()
+                .map(|order_status| order_status.to_string())
+                .collect();
+            order::Entity::find()
+                .filter(order::Column::CustomerId.eq(customer_id))
+                .filter(order::Column::OrderStatus.is_in(open_statuses))
+                .count(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)
+        }
+        async fn count_kitchen_orders_by_restaurant(
+            &self,
+            restaurant_id: uuid::Uuid,
+            created_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, OrderDomainError> {
+            let kitchen_statuses: Vec<String> = OrderStatus::kitchen_statuses()
+                .iter()
+                .map(|order_status| order_status.to_string())
+                .collect();
+            order::Entity::find()
+                .filter(order::Column::RestaurantId.eq(restaurant_id))
+                .filter(order::Column::OrderStatus.is_in(kitchen_statuses))
+                .filter(order::Column::CreatedAt.lt(created_before))
+                .count(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)
+        }
+        async fn count_orders_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<u64, OrderDomainError> {
+            order::Entity::find()
+                .filter(order::Column::CustomerId.eq(customer_id))
+                .count(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<C: Clock> OrderQueryRepository for OrderRepositoryImpl<C> {
+        async fn search(
+            &self,
+            criteria: OrderSearchCriteria,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_models = OrderSearchColumns {
+                customer_id: order::Column::CustomerId,
+                restaurant_id: order::Column::RestaurantId,
+                order_status: order::Column::OrderStatus,
+                created_at: order::Column::CreatedAt,
+                order_id: order::Column::Id,
+            }
+            .select::<order::Entity>(&criteria)
+            .find_also_related(order_address::Entity)
+            .all(&self.db)
+            .await
+            .map_err(|error| OrderDomainError::PersistenceError(error.to_string()))?;
+            self.load_orders(order_models).await
+        }
+    }
+
+    pub struct CustomerRepositoryImpl {
+        db: sea_orm::DatabaseConnection,
+    }
+
+    impl CustomerRepositoryImpl {
+        pub fn new(db: sea_orm::DatabaseConnection) -> Self {
+            Self { db }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl CustomerRepository for CustomerRepositoryImpl {
+        async fn find_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<Customer, OrderDomainError> {
+            let customer_model = customer::Entity::find_by_id(customer_id)
+                .one(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::CustomerNotFound)?
+                .ok_or(OrderDomainError::CustomerNotFound)?;
+            Ok(customer_model.into())
+        }
+    }
+
+    pub struct GroupOrderRepositoryImpl {
+        db: sea_orm::DatabaseConnection,
+    }
+
+    impl GroupOrderRepositoryImpl {
+        pub fn new(db: sea_orm::DatabaseConnection) -> Self {
+            Self { db }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl GroupOrderRepository for GroupOrderRepositoryImpl {
+        async fn save(&self, group_order: GroupOrder) -> Result<GroupOrder, OrderDomainError> {
+            let group_order_model: group_order::Model = group_order.clone().into();
+            let _save_result = group_order::ActiveModel::from(group_order_model)
+                .insert(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(group_order)
+        }
+        async fn update(
+            &self,
+            mut group_order: GroupOrder,
+        ) -> Result<GroupOrder, OrderDomainError> {
+            let expected_version = group_order.version;
+            group_order.version += 1;
+            let group_order_model: group_order::Model = group_order.clone().into();
+            let update_result = group_order::Entity::update_many()
+                .set(group_order::ActiveModel::from(group_order_model).reset_all())
+                .filter(group_order::Column::Id.eq(group_order.id))
+                .filter(group_order::Column::Version.eq(expected_version))
+                .exec(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            if update_result.rows_affected == 0 {
+                return Err(OrderDomainError::ConcurrentModification);
+            }
+            Ok(group_order)
+        }
+        async fn find_by_id(
+            &self,
+            group_order_id: uuid::Uuid,
+        ) -> Result<GroupOrder, OrderDomainError> {
+            let group_order_model = group_order::Entity::find_by_id(group_order_id)
+                .one(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?
+                .ok_or(OrderDomainError::OrderNotFound)?;
+            Ok(group_order_model.into())
+        }
+    }
+
+    pub struct OrderSubscriptionRepositoryImpl {
+        db: sea_orm::DatabaseConnection,
+    }
+
+    impl OrderSubscriptionRepositoryImpl {
+        pub fn new(db: sea_orm::DatabaseConnection) -> Self {
+            Self { db }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl OrderSubscriptionRepository for OrderSubscriptionRepositoryImpl {
+        async fn save(
+            &self,
+            subscription: OrderSubscription,
+        ) -> Result<OrderSubscription, OrderDomainError> {
+            let subscription_model: order_subscription::Model = subscription.clone().into();
+            let _save_result = order_subscription::ActiveModel::from(subscription_model)
+                .insert(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(subscription)
+        }
+        async fn update(
+            &self,
+            subscription: OrderSubscription,
+        ) -> Result<OrderSubscription, OrderDomainError> {
+            let subscription_model: order_subscription::Model = subscription.clone().into();
+            let _update_result = order_subscription::ActiveModel::from(subscription_model)
+                .reset_all()
+                .update(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(subscription)
+        }
+        async fn find_by_id(
+            &self,
+            subscription_id: uuid::Uuid,
+        ) -> Result<OrderSubscription, OrderDomainError> {
+            let subscription_model = order_subscription::Entity::find_by_id(subscription_id)
+                .one(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::SubscriptionNotFound)?
+                .ok_or(OrderDomainError::SubscriptionNotFound)?;
+            subscription_model.try_into()
+        }
+        async fn find_active(&self) -> Result<Vec<OrderSubscription>, OrderDomainError> {
+            let subscription_models = order_subscription::Entity::find()
+                .filter(
+                    order_subscription::Column::Status.eq(SubscriptionStatus::Active.to_string()),
+                )
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::SubscriptionNotFound)?;
+            subscription_models
+                .into_iter()
+                .map(OrderSubscription::try_from)
+                .collect()
+        }
+        async fn claim_occurrence(
+            &self,
+            subscription: OrderSubscription,
+            occurrence_on: NaiveDate,
+            order_id: uuid::Uuid,
+        ) -> Result<OrderSubscription, OrderDomainError> {
+            let txn = self
+                .db
+                .begin()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let claimed =
+                subscription_occurrence::Entity::find_by_id((subscription.id, occurrence_on))
+                    .one(&txn)
+                    .await
+                    .map_err(|error| OrderDomainError::PersistenceError(error.to_string()))?;
+            if claimed.is_some() {
+                return Err(OrderDomainError::SubscriptionOccurrenceClaimed(
+                    occurrence_on.to_string(),
+                ));
+            }
+            let _save_result =
+                subscription_occurrence::ActiveModel::from(subscription_occurrence::Model {
+                    subscription_id: subscription.id,
+                    occurrence_on,
+                    order_id,
+                })
+                .insert(&txn)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let subscription_model: order_subscription::Model = subscription.clone().into();
+            let _update_result = order_subscription::ActiveModel::from(subscription_model)
+                .reset_all()
+                .update(&txn)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            txn.commit()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(subscription)
+        }
+    }
+
+    pub struct RiskCheckRepositoryImpl {
+        db: sea_orm::DatabaseConnection,
+    }
+
+    impl RiskCheckRepositoryImpl {
+        pub fn new(db: sea_orm::DatabaseConnection) -> Self {
+            Self { db }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl RiskCheckRepository for RiskCheckRepositoryImpl {
+        async fn save(&self, risk_check: RiskCheck) -> Result<RiskCheck, OrderDomainError> {
+            let risk_check_model: order_risk_check::Model = risk_check.clone().into();
+            let _save_result = order_risk_check::ActiveModel::from(risk_check_model)
+                .insert(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(risk_check)
+        }
+        async fn find_check_times_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+            since: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, OrderDomainError> {
+            let risk_check_models = order_risk_check::Entity::find()
+                .filter(order_risk_check::Column::CustomerId.eq(customer_id))
+                .filter(order_risk_check::Column::CheckedAt.gt(since))
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?;
+            Ok(risk_check_models
+                .into_iter()
+                .map(|risk_check_model| risk_check_model.checked_at)
+                .collect())
+        }
+        async fn find_check_times_by_address(
+            &self,
+            address_key: String,
+            since: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, OrderDomainError> {
+            let risk_check_models = order_risk_check::Entity::find()
+                .filter(order_risk_check::Column::AddressKey.eq(address_key))
+                .filter(order_risk_check::Column::CheckedAt.gt(since))
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?;
+            Ok(risk_check_models
+                .into_iter()
+                .map(|risk_check_model| risk_check_model.checked_at)
+                .collect())
+        }
+    }
+
+    pub struct RestaurantRepositoryImpl {
+        db: sea_orm::DatabaseConnection,
+    }
+
+    impl RestaurantRepositoryImpl {
+        pub fn new(db: sea_orm::DatabaseConnection) -> Self {
+            Self { db }
+        }
+
+        async fn find_opening_hours(
+            &self,
+            restaurant_id: uuid::Uuid,
+            time_zone: &str,
+        ) -> Result<OpeningHours, OrderDomainError> {
+            let time_zone: Tz = time_zone.parse().map_err(|_| {
+                OrderDomainError::InvalidRestaurantConfiguration(format!(
+                    "unknown time zone {}",
+                    time_zone
+                ))
+            })?;
+            let opening_hours_models = restaurant_opening_hours::Entity::find()
+                .filter(restaurant_opening_hours::Column::RestaurantId.eq(restaurant_id))
+                .all(&self.db)
+                .await
+                .map_err(|error| OrderDomainError::PersistenceError(error.to_string()))?;
+            let weekly_periods: Vec<WeeklyOpeningPeriod> = opening_hours_models
+                .iter()
+                .filter_map(|model| {
+                    let weekday = model.weekday.as_ref()?.parse().ok()?;
+                    Some(
+                        WeeklyOpeningPeriodBuilder::default()
+                            .weekday(weekday)
+                            .period(model.period()?)
+                            .build()
+                            .unwrap(),
+                    )
+                })
+                .collect();
+            let mut exception_periods: BTreeMap<chrono::NaiveDate, Vec<OpeningPeriod>> =
+                BTreeMap::new();
+            for model in opening_hours_models.iter() {
+                if let Some(exception_date) = model.exception_date {
+                    let periods = exception_periods.entry(exception_date).or_default();
+                    if let Some(period) = model.period() {
+                        periods.push(period);
+                    }
+                }
+            }
+            let exceptions: Vec<OpeningException> = exception_periods
+                .into_iter()
+                .map(|(date, periods)| {
+                    OpeningExceptionBuilder::default()
+                        .date(date)
+                        .periods(periods)
+                        .build()
+                        .unwrap()
+                })
+                .collect();
+            let pauses: Vec<Pause> = restaurant_pause::Entity::find()
+                .filter(restaurant_pause::Column::RestaurantId.eq(restaurant_id))
+                .all(&self.db)
+                .await
+                .map_err(|error| OrderDomainError::PersistenceError(error.to_string()))?
+                .into_iter()
+                .map(|pause_model| pause_model.into())
+                .collect();
+            Ok(OpeningHoursBuilder::default()
+                .time_zone(time_zone)
+                .weekly_periods(weekly_periods)
+                .exceptions(exceptions)
+                .pauses(pauses)
+                .build()
+                .unwrap())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl RestaurantRepository for RestaurantRepositoryImpl {
+        async fn find_restaurant_info(
+            &self,
+            restaurant: Restaurant,
+        ) -> Result<Restaurant, OrderDomainError> {
+            let product_uuids: Vec<uuid::Uuid> = restaurant
+                .clone()
+                .products
+                .into_iter()
+                .map(|p| p.into())
+                .collect();
+            let restaurant_id: uuid::Uuid = restaurant.into();
+            let restaurant_with_all_products = restaurant::Entity::find()
+                .filter(restaurant::Column::RestaurantId.eq(restaurant_id))
+                .filter(restaurant::Column::ProductId.is_in(product_uuids.clone()))
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::RestaurantNotFound)?;
+            if let Some(restaurant_model) = restaurant_with_all_products.first() {
+                let product_option_models = restaurant_product_option::Entity::find()
+                    .filter(restaurant_product_option::Column::RestaurantId.eq(restaurant_id))
+                    .filter(restaurant_product_option::Column::ProductId.is_in(product_uuids))
+                    .all(&self.db)
+                    .await
+                    .map_err(|_| OrderDomainError::RestaurantNotFound)?;
+                let mut option_groups =
+                    restaurant_product_option::into_option_groups(product_option_models);
+                let products = restaurant_with_all_products
+                    .iter()
+                    .map(|restaurant_model| {
+                        let mut product: Product = (*restaurant_model).clone().into();
+                        product.option_groups = option_groups
+                            .remove(&restaurant_model.product_id)
+                            .unwrap_or_default();
+                        product
+                    })
+                    .collect();
+                let delivery_zones: Vec<DeliveryZone> = restaurant_delivery_zone::Entity::find()
+                    .filter(restaurant_delivery_zone::Column::RestaurantId.eq(restaurant_id))
+                    .all(&self.db)
+                    .await
+                    .map_err(|error| OrderDomainError::PersistenceError(error.to_string()))?
+                    .into_iter()
+                    .map(|delivery_zone_model| delivery_zone_model.into())
+                    .collect();
+                let opening_hours: Option<OpeningHours> =
+                    match &restaurant_model.restaurant_time_zone {
+                        Some(time_zone) => {
+                            Some(self.find_opening_hours(restaurant_id, time_zone).await?)
+                        }
+                        None => None,
+                    };
+                let mut restaurant: Restaurant = (*restaurant_model).clone().into();
+                restaurant.products = products;
+                restaurant.delivery_zones = delivery_zones;
+                restaurant.opening_hours = opening_hours;
+                Ok(restaurant)
+            } else {
+                return Err(OrderDomainError::RestaurantNotFound);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use common::{
+            clock::FixedClock,
+            error::OrderDomainError,
+            value_object::{money::Money, OrderStatus},
+        };
+        use sea_orm::{ActiveModelTrait, ConnectionTrait};
+        use service::{
+            dto::list::{OrderCursor, OrderSearchCriteriaBuilder, OrderSort},
+            ports::output::repository::{
+                GroupOrderRepository, OrderQueryRepository, OrderRepository,
+                OrderSubscriptionRepository, OrderTrackingViewRepository,
+            },
+        };
+
+        use super::{
+            GroupOrderRepositoryImpl, OrderRepositoryImpl, OrderSubscriptionRepositoryImpl,
+        };
+        use crate::entity::order_subscription;
+        use crate::{fixtures, projection::OrderTrackingViewRepositoryImpl};
+
+        async fn repository() -> OrderRepositoryImpl<FixedClock> {
+            OrderRepositoryImpl::new(fixtures::database().await, FixedClock::new(fixtures::now()))
+        }
+
+        #[tokio::test]
+        async fn save_all_writes_no_order_when_one_order_fails() {
+            let order_repository = repository().await;
+            let result = order_repository
+                .save_all(vec![
+                    fixtures::numbered_order(1, None),
+                    fixtures::numbered_order(2, None),
+                    fixtures::numbered_order(1, None),
+                ])
+                .await;
+            assert!(matches!(result, Err(OrderDomainError::SaveOrderError)));
+            assert!(matches!(
+                order_repository.find_by_id(fixtures::id(2)).await,
+                Err(OrderDomainError::OrderNotFound)
+            ));
+        }
+
+        #[tokio::test]
+        async fn kitchen_orders_are_paid_or_approved_orders_created_earlier() {
+            let order_repository = repository().await;
+            for (n, order_status, created_at) in [
+                (
+                    11,
+                    OrderStatus::Paid,
+                    fixtures::now() - chrono::Duration::hours(2),
+                ),
+                (
+                    12,
+                    OrderStatus::Approved,
+                    fixtures::now() - chrono::Duration::hours(1),
+                ),
+                (
+                    13,
+                    OrderStatus::Pending,
+                    fixtures::now() - chrono::Duration::hours(1),
+                ),
+                (
+                    14,
+                    OrderStatus::Cancelled,
+                    fixtures::now() - chrono::Duration::hours(1),
+                ),
+                (
+                    15,
+                    OrderStatus::Paid,
+                    fixtures::now() + chrono::Duration::hours(1),
+                ),
+            ] {
+                let mut order = fixtures::numbered_order(n, Some(created_at));
+                order.order_status = order_status;
+                order_repository.save(order).await.unwrap();
+            }
+            assert_eq!(
+                order_repository
+                    .count_kitchen_orders_by_restaurant(fixtures::id(3), fixtures::now())
+                    .await
+                    .unwrap(),
+                2
+            );
+        }
+
+        #[tokio::test]
+        async fn subscription_occurrences_can_only_be_claimed_once() {
+            let db = fixtures::database().await;
+            order_subscription::ActiveModel::from(fixtures::subscription_model("Europe/Amsterdam"))
+                .insert(&db)
+                .await
+                .unwrap();
+            let order_subscription_repository = OrderSubscriptionRepositoryImpl::new(db);
+            let subscription = order_subscription_repository
+                .find_by_id(fixtures::id(70))
+                .await
+                .unwrap();
+            let occurrence_on = fixtures::now().date_naive();
+            order_subscription_repository
+                .claim_occurrence(subscription.clone(), occurrence_on, fixtures::id(71))
+                .await
+                .unwrap();
+            assert!(matches!(
+                order_subscription_repository
+                    .claim_occurrence(subscription, occurrence_on, fixtures::id(72))
+                    .await,
+                Err(OrderDomainError::SubscriptionOccurrenceClaimed(_))
+            ));
+        }
+
+        #[tokio::test]
+        async fn subscriptions_with_an_unknown_time_zone_are_rejected() {
+            let db = fixtures::database().await;
+            order_subscription::ActiveModel::from(fixtures::subscription_model("Mars/Olympus"))
+                .insert(&db)
+                .await
+                .unwrap();
+            let order_subscription_repository = OrderSubscriptionRepositoryImpl::new(db);
+            assert!(matches!(
+                order_subscription_repository
+                    .find_by_id(fixtures::id(70))
+                    .await,
+                Err(OrderDomainError::PersistenceError(_))
+            ));
+        }
+
+        #[tokio::test]
+        async fn group_order_update_rejects_a_stale_group_order() {
+            let group_order_repository = GroupOrderRepositoryImpl::new(fixtures::database().await);
+            let group_order = group_order_repository
+                .save(fixtures::group_order())
+                .await
+                .unwrap();
+            let mut claimed_group_order = group_order.clone();
+            claimed_group_order.order_id = Some(fixtures::id(41));
+            let claimed_group_order = group_order_repository
+                .update(claimed_group_order)
+                .await
+                .unwrap();
+            assert_eq!(claimed_group_order.version, 1);
+            let mut stale_group_order = group_order;
+            stale_group_order.order_id = Some(fixtures::id(42));
+            assert!(matches!(
+                group_order_repository.update(stale_group_order).await,
+                Err(OrderDomainError::ConcurrentModification)
+            ));
+            let stored_group_order = group_order_repository
+                .find_by_id(fixtures::id(40))
+                .await
+                .unwrap();
+            assert_eq!(stored_group_order.version, 1);
+            assert_eq!(stored_group_order.order_id, Some(fixtures::id(41)));
+            assert_eq!(stored_group_order.participants.len(), 2);
+        }
+
+        #[tokio::test]
+        async fn update_rejects_a_stale_order_without_writing_it() {
+            let order_repository = repository().await;
+            let order = order_repository
+                .save(fixtures::order(OrderStatus::Pending))
+                .await
+                .unwrap();
+            let mut paid_order = order.clone();
+            paid_order.pay().unwrap();
+            order_repository.update(paid_order).await.unwrap();
+            let mut stale_order = order;
+            stale_order.pay().unwrap();
+            stale_order.price = Money::new(1.0);
+            assert!(matches!(
+                order_repository.update(stale_order).await,
+                Err(OrderDomainError::ConcurrentModification)
+            ));
+            let stored_order = order_repository.find_by_id(fixtures::id(1)).await.unwrap();
+            assert_eq!(stored_order.aggregate_root.version, 2);
+            assert_eq!(stored_order.price.amount, 1000.0);
+        }
+
+        #[tokio::test]
+        async fn update_projects_status_changes_at_the_repository_clock() {
+            let changed_at = fixtures::now() + chrono::Duration::hours(1);
+            let db = fixtures::database().await;
+            let order_repository =
+                OrderRepositoryImpl::new(db.clone(), FixedClock::new(changed_at));
+            let mut order = order_repository
+                .save(fixtures::order(OrderStatus::Pending))
+                .await
+                .unwrap();
+            order.pay().unwrap();
+            order_repository.update(order).await.unwrap();
+            let order_tracking_view = OrderTrackingViewRepositoryImpl::new(db)
+                .find_by_tracking_id(fixtures::id(4).into())
+                .await
+                .unwrap();
+            assert!(order_tracking_view.order_status == OrderStatus::Paid);
+            assert_eq!(
+                order_tracking_view
+                    .status_history
+                    .last()
+                    .unwrap()
+                    .changed_at,
+                changed_at
+            );
+        }
+
+        #[tokio::test]
+        async fn save_writes_nothing_when_the_projection_fails() {
+            let db = fixtures::database().await;
+            db.execute_unprepared("DROP TABLE order_tracking_view")
+                .await
+                .unwrap();
+            let order_repository = OrderRepositoryImpl::new(db, FixedClock::new(fixtures::now()));
+            assert!(order_repository
+                .save(fixtures::order(OrderStatus::Pending))
+                .await
+                .is_err());
+            assert!(matches!(
+                order_repository.find_by_id(fixtures::id(1)).await,
+                Err(OrderDomainError::OrderNotFound)
+            ));
+        }
+
+        #[cfg(not(feature = "event-store"))]
+        #[tokio::test]
+        async fn rebuild_replaces_the_tracking_views() {
+            let db = fixtures::database().await;
+            let order_repository =
+                OrderRepositoryImpl::new(db.clone(), FixedClock::new(fixtures::now()));
+            order_repository
+                .save(fixtures::order(OrderStatus::Pending))
+                .await
+                .unwrap();
+            let order_tracking_view_repository = OrderTrackingViewRepositoryImpl::new(db);
+            assert_eq!(order_tracking_view_repository.rebuild().await.unwrap(), 1);
+            assert!(order_tracking_view_repository
+                .find_by_tracking_id(fixtures::id(4).into())
+                .await
+                .is_ok());
+        }
+
+        #[tokio::test]
+        async fn find_stuck_includes_orders_without_a_status_change_time() {
+            let order_repository = repository().await;
+            let mut order = fixtures::order(OrderStatus::Pending);
+            order.status_changed_at = None;
+            order_repository.save(order).await.unwrap();
+            let stuck_orders = order_repository
+                .find_stuck(
+                    OrderStatus::Pending,
+                    fixtures::now() - chrono::Duration::minutes(5),
+                )
+                .await
+                .unwrap();
+            assert_eq!(stuck_orders.len(), 1);
+            assert!(stuck_orders[0].status_changed_at.is_none());
+        }
+
+        #[tokio::test]
+        async fn search_pages_through_orders_with_unknown_creation_times_last() {
+            let order_repository = repository().await;
+            for (n, created_at) in [
+                (11, Some(fixtures::now() - chrono::Duration::hours(2))),
+                (12, Some(fixtures::now() - chrono::Duration::hours(1))),
+                (13, None),
+            ] {
+                order_repository
+                    .save(fixtures::numbered_order(n, created_at))
+                    .await
+                    .unwrap();
+            }
+            let first_page = order_repository
+                .search(
+                    OrderSearchCriteriaBuilder::default()
+                        .limit(2)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                fixtures::order_ids(&first_page),
+                vec![fixtures::id(12), fixtures::id(11)]
+            );
+            let second_page = order_repository
+                .search(
+                    OrderSearchCriteriaBuilder::default()
+                        .after(Some(OrderCursor {
+                            created_at: first_page[1].created_at,
+                            order_id: fixtures::id(11),
+                        }))
+                        .limit(2)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(fixtures::order_ids(&second_page), vec![fixtures::id(13)]);
+            let oldest_first = order_repository
+                .search(
+                    OrderSearchCriteriaBuilder::default()
+                        .sort(OrderSort::OldestFirst)
+                        .after(Some(OrderCursor {
+                            created_at: None,
+                            order_id: fixtures::id(13),
+                        }))
+                        .limit(5)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                fixtures::order_ids(&oldest_first),
+                vec![fixtures::id(11), fixtures::id(12)]
+            );
+        }
+    }
+}
+
+#[cfg(feature = "event-store")]
+pub mod event_store {
+    use std::collections::HashMap;
+
+    use sea_orm::{
+        ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, PaginatorTrait,
+        QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+    };
+
+    use common::{
+        clock::Clock, error::OrderDomainError, id::IdGenerator, value_object::OrderStatus,
+    };
+    use domain_core::{entity::Order, event::OrderEvent, value_object::TrackingId};
+    use service::{
+        dto::list::OrderSearchCriteria,
+        ports::output::repository::{OrderQueryRepository, OrderRepository},
+    };
+
+    use crate::{
+        entity::{
+            order_event::{self, OrderEventPayload},
+            order_snapshot::{self, OrderState},
+            order_stream,
+        },
+        projection::OrderTrackingProjector,
+        query::{group_by_order, OrderSearchColumns},
+    };
+
+    pub struct EventSourcedOrderRepositoryImpl<C: Clock, G: IdGenerator> {
+        db: sea_orm::DatabaseConnection,
+        tracking_projector: OrderTrackingProjector,
+        clock: C,
+        id_generator: G,
+        snapshot_frequency: i64,
+    }
+
+    impl<C: Clock, G: IdGenerator> EventSourcedOrderRepositoryImpl<C, G> {
+        pub fn new(
+            db: sea_orm::DatabaseConnection,
+            clock: C,
+            id_generator: G,
+            snapshot_frequency: i64,
+        ) -> Self {
+            Self {
+                tracking_projector: OrderTrackingProjector,
+                db,
+                clock,
+                id_generator,
+                snapshot_frequency: snapshot_frequency.max(1),
+            }
+        }
+
+        async fn append_events<D: ConnectionTrait>(
+            &self,
+            db: &D,
+            order_id: uuid::Uuid,
+            stream_version: i64,
+            payloads: Vec<OrderEventPayload>,
+        ) -> Result<i64, OrderDomainError> {
+            let mut version = stream_version;
+            let mut order_event_active_models: Vec<order_event::ActiveModel> = vec![];
+            for payload in payloads {
+                version += 1;
+                let order_event_model = order_event::Model {
+                    id: self.id_generator.generate(),
+                    order_id,
+                    version,
+                    event_type: payload.name().to_string(),
+                    payload: serde_json::to_value(payload)
+                        .map_err(|_| OrderDomainError::SaveOrderError)?,
+                    recorded_at: self.clock.now(),
+                };
+                order_event_active_models.push(order_event::ActiveModel::from(order_event_model));
+            }
+            if !order_event_active_models.is_empty() {
+                let _save_result = order_event::Entity::insert_many(order_event_active_models)
+                    .exec(db)
+                    .await
+                    .map_err(|_| OrderDomainError::SaveOrderError)?;
+            }
+            Ok(version)
+        }
+
+        async fn take_snapshot_if_due<D: ConnectionTrait>(
+            &self,
+            db: &D,
+            order: &Order,
+            stream_version: i64,
+            version: i64,
+        ) -> Result<(), OrderDomainError> {
+            if version / self.snapshot_frequency == stream_version / self.snapshot_frequency {
+                return Ok(());
+            }
+            let order_snapshot_model = order_snapshot::Model {
+                order_id: order.clone().into(),
+                version,
+                state: serde_json::to_value(OrderState::from(order.clone()))
+                    .map_err(|_| OrderDomainError::SaveOrderError)?,
+                taken_at: self.clock.now(),
+            };
+            let _save_result = order_snapshot::ActiveModel::from(order_snapshot_model)
+                .insert(db)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(())
+        }
+
+        async fn load_order(&self, order_id: uuid::Uuid) -> Result<Order, OrderDomainError> {
+            self.load_orders(vec![order_id])
+                .await?
+                .pop()
+                .ok_or(OrderDomainError::OrderNotFound)
+        }
+
+        async fn load_orders(
+            &self,
+            order_ids: Vec<uuid::Uuid>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            if order_ids.is_empty() {
+                return Ok(vec![]);
+            }
+            let snapshot_versions: Vec<(uuid::Uuid, i64)> = order_snapshot::Entity::find()
+                .select_only()
+                .column(order_snapshot::Column::OrderId)
+                .column_as(order_snapshot::Column::Version.max(), "version")
+                .filter(order_snapshot::Column::OrderId.is_in(order_ids.clone()))
+                .group_by(order_snapshot::Column::OrderId)
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?;
+            let mut order_snapshot_models: HashMap<uuid::Uuid, order_snapshot::Model> =
+                HashMap::new();
+            if !snapshot_versions.is_empty() {
+                let snapshot_condition = snapshot_versions.iter().fold(
+                    Condition::any(),
+                    |condition, (order_id, version)| {
+                        condition.add(
+                            Condition::all()
+                                .add(order_snapshot::Column::OrderId.eq(*order_id))
+                                .add(order_snapshot::Column::Version.eq(*version)),
+                        )
+                    },
+                );
+                for order_snapshot_model in order_snapshot::Entity::find()
+                    .filter(snapshot_condition)
+                    .all(&self.db)
+                    .await
+                    .map_err(|_| OrderDomainError::OrderNotFound)?
+                {
+                    order_snapshot_models
+                        .insert(order_snapshot_model.order_id, order_snapshot_model);
+                }
+            }
+            let event_condition = order_ids
+                .iter()
+                .fold(Condition::any(), |condition, order_id| {
+                    let snapshot_version = order_snapshot_models
+                        .get(order_id)
+                        .map_or(0, |order_snapshot_model| order_snapshot_model.version);
+                    condition.add(
+                        Condition::all()
+                            .add(order_event::Column::OrderId.eq(*order_id))
+                            .add(order_event::Column::Version.gt(snapshot_version)),
+                    )
+                });
+            let mut order_event_models = group_by_order(
+                order_event::Entity::find()
+                    .filter(event_condition)
+                    .order_by_asc(order_event::Column::Version)
+                    .all(&self.db)
+                    .await
+                    .map_err(|_| OrderDomainError::OrderNotFound)?,
+                |order_event_model| order_event_model.order_id,
+            );
+            order_ids
+                .into_iter()
+                .map(|order_id| {
+                    Self::replay_order(
+                        order_snapshot_models.remove(&order_id),
+                        order_event_models.remove(&order_id).unwrap_or_default(),
+                    )
+                })
+                .collect()
+        }
+
+        async fn create_stream<D: ConnectionTrait>(
+            &self,
+            db: &D,
+            order: &Order,
+        ) -> Result<(), OrderDomainError> {
+            let order_id: uuid::Uuid = order.clone().into();
+            let version = self
+                .append_events(
+                    db,
+                    order_id,
+                    0,
+                    vec![OrderEventPayload::Created(Box::new(OrderState::from(
+                        order.clone(),
+                    )))],
+                )
+                .await?;
+            let _save_result =
+                order_stream::ActiveModel::from(order_stream::Model::from_order(order, version))
+                    .insert(db)
+                    .await
+                    .map_err(|_| OrderDomainError::SaveOrderError)?;
+            self.take_snapshot_if_due(db, order, 0, version).await?;
+            self.tracking_projector
+                .project(db, order, &[], self.clock.now())
+                .await?;
+            Ok(())
+        }
+
+        fn replay_order(
+            order_snapshot_model: Option<order_snapshot::Model>,
+            order_event_models: Vec<order_event::Model>,
+        ) -> Result<Order, OrderDomainError> {
+            let (mut order, snapshot_version): (Option<Order>, i64) = match order_snapshot_model {
+                Some(order_snapshot_model) => {
+                    let order_state: OrderState =
+                        serde_json::from_value(order_snapshot_model.state)
+                            .map_err(|_| OrderDomainError::OrderNotFound)?;
+                    (Some(order_state.try_into()?), order_snapshot_model.version)
+                }
+                None => (None, 0),
+            };
+            let mut events: Vec<OrderEvent> = vec![];
+            let mut version = snapshot_version;
+            let mut status_changed_at = None;
+            for order_event_model in order_event_models {
+                version = order_event_model.version;
+                let payload: OrderEventPayload = serde_json::from_value(order_event_model.payload)
+                    .map_err(|_| OrderDomainError::OrderNotFound)?;
+                if payload
+                    .clone()
+                    .into_event()?
+                    .is_none_or(|event| event.changes_status())
+                {
+                    status_changed_at = Some(order_event_model.recorded_at);
+                }
+                match payload {
+                    OrderEventPayload::Created(order_state) => {
+                        order = Some((*order_state).try_into()?)
+                    }
+                    payload => events.extend(payload.into_event()?),
+                }
+            }
+            let mut order = order
+                .map(|order| order.replay(&events))
+                .ok_or(OrderDomainError::OrderNotFound)?;
+            order.aggregate_root.version = version;
+            if status_changed_at.is_some() {
+                order.status_changed_at = status_changed_at;
+            }
+            Ok(order)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<C: Clock, G: IdGenerator> OrderRepository for EventSourcedOrderRepositoryImpl<C, G> {
+        async fn save(&self, mut order: Order) -> Result<Order, OrderDomainError> {
+            let _pending_events = order.take_pending_events();
+            let txn = self
+                .db
+                .begin()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            self.create_stream(&txn, &order).await?;
+            txn.commit()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(order)
+        }
+        async fn save_all(&self, mut orders: Vec<Order>) -> Result<Vec<Order>, OrderDomainError> {
+            let txn = self
+                .db
+                .begin()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            for order in orders.iter_mut() {
+                let _pending_events = order.take_pending_events();
+                self.create_stream(&txn, order).await?;
+            }
+            txn.commit()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(orders)
+        }
+        async fn update(&self, mut order: Order) -> Result<Order, OrderDomainError> {
+            let order_id: uuid::Uuid = order.clone().into();
+            let pending_events = order.take_pending_events();
+            if pending_events.is_empty() {
+                return Ok(order);
+            }
+            let stream_version = order.aggregate_root.version - pending_events.len() as i64;
+            let txn = self
+                .db
+                .begin()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            let update_result = order_stream::Entity::update_many()
+                .set(
+                    order_stream::ActiveModel::from(order_stream::Model::from_order(
+                        &order,
+                        order.aggregate_root.version,
+                    ))
+                    .reset_all(),
+                )
+                .filter(order_stream::Column::OrderId.eq(order_id))
+                .filter(order_stream::Column::Version.eq(stream_version))
+                .exec(&txn)
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            if update_result.rows_affected == 0 {
+                return Err(OrderDomainError::ConcurrentModification);
+            }
+            let version = self
+                .append_events(
+                    &txn,
+                    order_id,
+                    stream_version,
+                    pending_events
+                        .iter()
+                        .cloned()
+                        .map(|event| OrderEventPayload::from_event(order_id, event))
+                        .collect(),
+                )
+                .await?;
+            self.take_snapshot_if_due(&txn, &order, stream_version, version)
+                .await?;
+            self.tracking_projector
+                .project(&txn, &order, &pending_events, self.clock.now())
+                .await?;
+            txn.commit()
+                .await
+                .map_err(|_| OrderDomainError::SaveOrderError)?;
+            Ok(order)
+        }
+        async fn find_by_id(&self, order_id: uuid::Uuid) -> Result<Order, OrderDomainError> {
+            self.load_order(order_id).await
+        }
+        async fn find_by_tracking_id(&self, id: TrackingId) -> Result<Order, OrderDomainError> {
+            let tracking_uuid: uuid::Uuid = id.into();
+            let order_stream_model = order_stream::Entity::find()
+                .filter(order_stream::Column::TrackingId.eq(tracking_uuid))
+                .one(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?
+                .ok_or(OrderDomainError::OrderNotFound)?;
+            self.load_order(order_stream_model.order_id).await
+        }
+        async fn find_scheduled_due(
+            &self,
+            release_cutoff: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_stream_models = order_stream::Entity::find()
+                .filter(order_stream::Column::OrderStatus.eq(OrderStatus::Scheduled.to_string()))
+                .filter(order_stream::Column::ScheduledFor.lte(release_cutoff))
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?;
+            self.load_orders(
+                order_stream_models
+                    .into_iter()
+                    .map(|order_stream_model| order_stream_model.order_id)
+                    .collect(),
+            )
+            .await
+        }
+        async fn find_stuck(
+            &self,
+            order_status: OrderStatus,
+            status_changed_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_stream_models = order_stream::Entity::find()
+                .filter(order_stream::Column::OrderStatus.eq(order_status.to_string()))
+                .filter(
+                    Condition::any()
+                        .add(order_stream::Column::StatusChangedAt.lte(status_changed_before))
+                        .add(order_stream::Column::StatusChangedAt.is_null()),
+                )
+                .all(&self.db)
+                .await
+                .map_err(|error| OrderDomainError::PersistenceError(error.to_string()))?;
+            self.load_orders(
+                order_stream_models
+                    .into_iter()
+                    .map(|order_stream_model| order_stream_model.order_id)
+                    .collect(),
+            )
+            .await
+        }
+        async fn find_by_checkout_id(
+            &self,
+            checkout_id: uuid::Uuid,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_stream_models = order_stream::Entity::find()
+                .filter(order_stream::Column::CheckoutId.eq(checkout_id))
+                .all(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)?;
+            self.load_orders(
+                order_stream_models
+                    .into_iter()
+                    .map(|order_stream_model| order_stream_model.order_id)
+                    .collect(),
+            )
+            .await
+        }
+        async fn count_open_orders_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<u64, OrderDomainError> {
+            let open_statuses: Vec<String> = OrderStatus::open_statuses()
+                .iter()
+                .map(|order_status| order_status.to_string())
+                .collect();
+            order_stream::Entity::find()
+                .filter(order_stream::Column::CustomerId.eq(customer_id))
+                .filter(order_stream::Column::OrderStatus.is_in(open_statuses))
+                .count(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)
+        }
+        async fn count_kitchen_orders_by_restaurant(
+            &self,
+            restaurant_id: uuid::Uuid,
+            created_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, OrderDomainError> {
+            let kitchen_statuses: Vec<String> = OrderStatus::kitchen_statuses()
+                .iter()
+                .map(|order_status| order_status.to_string())
+                .collect();
+            order_stream::Entity::find()
+                .filter(order_stream::Column::RestaurantId.eq(restaurant_id))
+                .filter(order_stream::Column::OrderStatus.is_in(kitchen_statuses))
+                .filter(order_stream::Column::CreatedAt.lt(created_before))
+                .count(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)
+        }
+        async fn count_orders_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<u64, OrderDomainError> {
+            order_stream::Entity::find()
+                .filter(order_stream::Column::CustomerId.eq(customer_id))
+                .count(&self.db)
+                .await
+                .map_err(|_| OrderDomainError::OrderNotFound)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<C: Clock, G: IdGenerator> OrderQueryRepository for EventSourcedOrderRepositoryImpl<C, G> {
+        async fn search(
+            &self,
+            criteria: OrderSearchCriteria,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            let order_stream_models = OrderSearchColumns {
+                customer_id: order_stream::Column::CustomerId,
+                restaurant_id: order_stream::Column::RestaurantId,
+                order_status: order_stream::Column::OrderStatus,
+                created_at: order_stream::Column::CreatedAt,
+                order_id: order_stream::Column::OrderId,
+            }
+            .select::<order_stream::Entity>(&criteria)
+            .all(&self.db)
+            .await
+            .map_err(|error| OrderDomainError::PersistenceError(error.to_string()))?;
+            self.load_orders(
+                order_stream_models
+                    .into_iter()
+                    .map(|order_stream_model| order_stream_model.order_id)
+                    .collect(),
+            )
+            .await
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use common::{
+            clock::FixedClock, error::OrderDomainError, id::SequentialIdGenerator,
+            value_object::OrderStatus,
+        };
+        use service::{
+            dto::list::OrderSearchCriteriaBuilder,
+            ports::output::repository::{
+                OrderQueryRepository, OrderRepository, OrderTrackingViewRepository,
+            },
+        };
+
+        use super::EventSourcedOrderRepositoryImpl;
+        use crate::{fixtures, projection::OrderTrackingViewRepositoryImpl};
+
+        async fn repository() -> EventSourcedOrderRepositoryImpl<FixedClock, SequentialIdGenerator>
+        {
+            EventSourcedOrderRepositoryImpl::new(
+                fixtures::database().await,
+                FixedClock::new(fixtures::now()),
+                SequentialIdGenerator::new(100),
+                2,
+            )
+        }
+
+        #[tokio::test]
+        async fn save_all_writes_no_stream_when_one_order_fails() {
+            let order_repository = repository().await;
+            let result = order_repository
+                .save_all(vec![
+                    fixtures::numbered_order(1, None),
+                    fixtures::numbered_order(2, None),
+                    fixtures::numbered_order(1, None),
+                ])
+                .await;
+            assert!(matches!(result, Err(OrderDomainError::SaveOrderError)));
+            assert!(matches!(
+                order_repository.find_by_id(fixtures::id(2)).await,
+                Err(OrderDomainError::OrderNotFound)
+            ));
+        }
+
+        #[tokio::test]
+        async fn kitchen_orders_are_paid_or_approved_streams_created_earlier() {
+            let order_repository = repository().await;
+            for (n, order_status, created_at) in [
+                (
+                    11,
+                    OrderStatus::Paid,
+                    fixtures::now() - chrono::Duration::hours(2),
+                ),
+                (
+                    12,
+                    OrderStatus::Approved,
+                    fixtures::now() - chrono::Duration::hours(1),
+                ),
+                (
+                    13,
+                    OrderStatus::Pending,
+                    fixtures::now() - chrono::Duration::hours(1),
+                ),
+                (
+                    14,
+                    OrderStatus::Cancelled,
+                    fixtures::now() - chrono::Duration::hours(1),
+                ),
+                (
+                    15,
+                    OrderStatus::Paid,
+                    fixtures::now() + chrono::Duration::hours(1),
+                ),
+            ] {
+                let mut order = fixtures::numbered_order(n, Some(created_at));
+                order.order_status = order_status;
+                order_repository.save(order).await.unwrap();
+            }
+            assert_eq!(
+                order_repository
+                    .count_kitchen_orders_by_restaurant(fixtures::id(3), fixtures::now())
+                    .await
+                    .unwrap(),
+                2
+            );
+        }
+
+        #[tokio::test]
+        async fn save_and_update_number_the_stream_consistently() {
+            let order_repository = repository().await;
+            let order = order_repository
+                .save(fixtures::order(OrderStatus::Pending))
+                .await
+                .unwrap();
+            let mut paid_order = order;
+            paid_order.pay().unwrap();
+            order_repository.update(paid_order).await.unwrap();
+            let stored_order = order_repository.find_by_id(fixtures::id(1)).await.unwrap();
+            assert_eq!(stored_order.aggregate_root.version, 2);
+            assert!(stored_order.order_status == OrderStatus::Paid);
+        }
+
+        #[tokio::test]
+        async fn update_appends_no_events_when_the_stream_version_is_stale() {
+            let order_repository = repository().await;
+            let order = order_repository
+                .save(fixtures::order(OrderStatus::Pending))
+                .await
+                .unwrap();
+            let mut paid_order = order.clone();
+            paid_order.pay().unwrap();
+            order_repository.update(paid_order).await.unwrap();
+            let mut stale_order = order;
+            stale_order.pay().unwrap();
+            assert!(matches!(
+                order_repository.update(stale_order).await,
+                Err(OrderDomainError::ConcurrentModification)
+            ));
+            let stored_order = order_repository.find_by_id(fixtures::id(1)).await.unwrap();
+            assert_eq!(stored_order.aggregate_root.version, 2);
+        }
+
+        #[tokio::test]
+        async fn rebuild_replays_the_tracking_views_from_the_event_log() {
+            let db = fixtures::database().await;
+            let order_repository = EventSourcedOrderRepositoryImpl::new(
+                db.clone(),
+                FixedClock::new(fixtures::now()),
+                SequentialIdGenerator::new(100),
+                2,
+            );
+            let mut order = order_repository
+                .save(fixtures::order(OrderStatus::Pending))
+                .await
+                .unwrap();
+            order.pay().unwrap();
+            order_repository.update(order).await.unwrap();
+            let order_tracking_view_repository = OrderTrackingViewRepositoryImpl::new(db);
+            assert_eq!(order_tracking_view_repository.rebuild().await.unwrap(), 1);
+            let order_tracking_view = order_tracking_view_repository
+                .find_by_tracking_id(fixtures::id(4).into())
+                .await
+                .unwrap();
+            assert!(order_tracking_view.order_status == OrderStatus::Paid);
+            assert_eq!(
+                order_tracking_view
+                    .status_history
+                    .last()
+                    .unwrap()
+                    .changed_at,
+                fixtures::now()
+            );
+        }
+
+        #[tokio::test]
+        async fn search_replays_each_order_from_its_latest_snapshot() {
+            let order_repository = repository().await;
+            for (n, created_at) in [
+                (11, Some(fixtures::now() - chrono::Duration::hours(2))),
+                (12, None),
+                (13, Some(fixtures::now() - chrono::Duration::hours(1))),
+            ] {
+                order_repository
+                    .save(fixtures::numbered_order(n, created_at))
+                    .await
+                    .unwrap();
+            }
+            let mut paid_order = order_repository.find_by_id(fixtures::id(11)).await.unwrap();
+            paid_order.pay().unwrap();
+            order_repository.update(paid_order).await.unwrap();
+            let orders = order_repository
+                .search(
+                    OrderSearchCriteriaBuilder::default()
+                        .limit(5)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                fixtures::order_ids(&orders),
+                vec![fixtures::id(13), fixtures::id(11), fixtures::id(12)]
+            );
+            assert!(orders[1].order_status == OrderStatus::Paid);
+            assert_eq!(orders[1].aggregate_root.version, 2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod fixtures {
+    use chrono::{DateTime, TimeZone, Utc};
+    use common::value_object::OrderStatus;
+    use domain_core::{entity::Order, group_order::GroupOrder};
+    use sea_orm::{ConnectionTrait, Database, DatabaseConnection, EntityTrait, Schema};
+
+    use crate::entity::{
+        group_order, order, order_address, order_adjustment, order_dietary_conflict, order_event,
+        order_item, order_item_option, order_payment_share, order_snapshot, order_stream,
+        order_subscription, order_tracking_view, subscription_occurrence,
+    };
+
+    async fn create_table<E: EntityTrait>(db: &DatabaseConnection, entity: E) {
+        let backend = db.get_database_backend();
+        let statement = backend
+            .build(&Schema::new(backend).create_table_from_entity(entity))
+            .to_string()
+            .replace(" AUTOINCREMENT", "");
+        db.execute_unprepared(&statement).await.unwrap();
+    }
+
+    pub async fn database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        create_table(&db, order::Entity).await;
+        create_table(&db, order_address::Entity).await;
+        create_table(&db, order_item::Entity).await;
+        create_table(&db, order_item_option::Entity).await;
+        create_table(&db, order_adjustment::Entity).await;
+        create_table(&db, order_payment_share::Entity).await;
+        create_table(&db, order_dietary_conflict::Entity).await;
+        create_table(&db, order_tracking_view::Entity).await;
+        create_table(&db, order_stream::Entity).await;
+        create_table(&db, order_event::Entity).await;
+        create_table(&db, order_snapshot::Entity).await;
+        create_table(&db, group_order::Entity).await;
+        create_table(&db, order_subscription::Entity).await;
+        create_table(&db, subscription_occurrence::Entity).await;
+        db
+    }
+
+    pub fn id(n: u128) -> uuid::Uuid {
+        uuid::Uuid::from_u128(n)
+    }
+
+    pub fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap()
+    }
+
+    pub fn order_model(order_status: OrderStatus) -> order::Model {
+        order::Model {
+            id: id(1),
+            customer_id: id(2),
+            restaurant_id: id(3),
+            tracking_id: id(4),
+            price: 1000,
+            order_status: order_status.to_string(),
+            failure_messages: String::new(),
+            tax: 0,
+            delivery_fee: 0,
+            scheduled_for: None,
+            tip: 0,
+            tip_recipient: None,
+            version: 1,
+            status_changed_at: Some(now()),
+            created_at: Some(now()),
+            checkout_id: None,
+            checkout_cancellation_policy: None,
+            declared_allergies: None,
+            dietary_requirements: None,
+            dietary_conflict_policy: None,
+            estimated_delivery_at: None,
+            risk_decision: None,
+            risk_reasons: None,
+        }
+    }
+
+    fn order_address_model(address_id: uuid::Uuid, order_id: uuid::Uuid) -> order_address::Model {
+        order_address::Model {
+            id: address_id,
+            order_id,
+            street: String::from("Damrak 1"),
+            postal_code: String::from("1011AB"),
+            city: String::from("Amsterdam"),
+            latitude: None,
+            longitude: None,
+        }
+    }
+
+    pub fn order(order_status: OrderStatus) -> Order {
+        order_model(order_status).into_order(order_address_model(id(5), id(1)).into(), vec![])
+    }
+
+    pub fn numbered_order(n: u128, created_at: Option<DateTime<Utc>>) -> Order {
+        let mut order_model = order_model(OrderStatus::Pending);
+        order_model.id = id(n);
+        order_model.tracking_id = id(n + 100);
+        order_model.created_at = created_at;
+        order_model.into_order(order_address_model(id(n + 200), id(n)).into(), vec![])
+    }
+
+    pub fn group_order() -> GroupOrder {
+        GroupOrder::open(
+            id(40),
+            id(2),
+            id(3),
+            order_address_model(id(5), id(40)).into(),
+            vec![id(21)],
+        )
+    }
+
+    pub fn subscription_model(time_zone: &str) -> order_subscription::Model {
+        order_subscription::Model {
+            id: id(70),
+            customer_id: id(2),
+            restaurant_id: id(3),
+            address_id: id(5),
+            street: String::from("1 Main Street"),
+            postal_code: String::from("1011AB"),
+            city: String::from("Amsterdam"),
+            latitude: None,
+            longitude: None,
+            items: serde_json::json!({ "items": [], "item_options": [] }),
+            tip_fixed: None,
+            tip_percentage: None,
+            tip_recipient: None,
+            weekdays: String::from("Mon"),
+            delivery_time: chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            time_zone: String::from(time_zone),
+            ends_on: None,
+            status: String::from("Active"),
+            last_occurrence_on: None,
+        }
+    }
+
+    pub fn order_ids(orders: &[Order]) -> Vec<uuid::Uuid> {
+        orders.iter().map(|order| order.clone().into()).collect()
     }
 }
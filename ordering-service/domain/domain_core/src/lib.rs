@@ -1,27 +1,55 @@
+use checkout::{Checkout, CheckoutCancellationPolicy};
+use common::clock::Clock;
 use common::error::OrderDomainError;
+use common::event::{Causation, EventEnvelope, EventEnvelopeBuilder};
+use common::id::IdGenerator;
+use common::value_object::OrderStatus;
 use entity::{Order, Restaurant};
-use event::{OrderCancelledBuilder, OrderCreatedBuilder, OrderPaidBuilder};
+use eta::EtaEstimator;
+use event::{
+    CheckoutCreatedBuilder, OrderApprovedBuilder, OrderCancelledBuilder,
+    OrderCancelledFinalBuilder, OrderCreatedBuilder, OrderItemAdjustedBuilder, OrderPaidBuilder,
+    OrderPaymentSharePaidBuilder, OrderRiskReviewRequestedBuilder, OrderSubscriptionFailedBuilder,
+};
+use risk::{RiskCheck, RiskCheckBuilder, RiskRulesEngine, RiskSignals};
+use scheduling::SchedulingPolicy;
+use subscription::{OrderSubscription, SubscriptionOccurrence};
+use tax::TaxCalculator;
+use value_object::AdjustmentReason;
 
 pub mod entity {
     use common::entity::{AggregateRoot, BaseEntity, BaseEntityBuilder};
     use common::error::OrderDomainError;
+    use common::validation::ValidationReport;
     use common::value_object::money::Money;
     use common::value_object::{
         BaseId, BaseIdBuilder, CustomerId, OrderId, OrderStatus, ProductId, RestaurantId,
-        RestaurantIdBuilder,
+        RestaurantIdBuilder, TaxCategory,
     };
     use derive_builder::Builder;
 
-    use crate::value_object::{OrderItemId, StreetAddress, TrackingId};
+    use crate::checkout::{CheckoutCancellationPolicy, OrderCheckout};
+    use crate::delivery::DeliveryZone;
+    use crate::dietary::{Allergen, DietaryConflict, DietaryPreferences, DietaryTag};
+    use crate::event::OrderEvent;
+    use crate::opening_hours::{OpeningHours, RestaurantAvailability};
+    use crate::risk::{RiskAssessment, RiskDecision};
+    use crate::tax::TaxCalculator;
+    use crate::value_object::{
+        AdjustmentReason, OrderItemId, PaymentShareStatus, StreetAddress, Tip, TipAmount,
+        TrackingId,
+    };
 
     #[derive(Clone, Builder)]
     pub struct Customer {
         pub aggregate_root: AggregateRoot<CustomerId>,
+        #[builder(default)]
+        pub dietary_preferences: Option<DietaryPreferences>,
     }
 
-    impl Into<uuid::Uuid> for Customer {
-        fn into(self) -> uuid::Uuid {
-            return self.aggregate_root.base_entity.id.base_id.value;
+    impl From<Customer> for uuid::Uuid {
+        fn from(customer: Customer) -> Self {
+            customer.aggregate_root.base_entity.id.base_id.value
         }
     }
 
@@ -30,6 +58,18 @@ pub mod entity {
         pub base_entity: BaseEntity<ProductId>,
         pub name: String,
         pub price: Money,
+        #[builder(default)]
+        pub tax_category: TaxCategory,
+        #[builder(default)]
+        pub option_groups: Vec<ProductOptionGroup>,
+        #[builder(default)]
+        pub availability: ProductAvailability,
+        #[builder(default)]
+        pub allergens: Vec<Allergen>,
+        #[builder(default)]
+        pub dietary_tags: Vec<DietaryTag>,
+        #[builder(default)]
+        pub prep_time: Option<chrono::Duration>,
     }
 
     impl Product {
@@ -41,13 +81,36 @@ pub mod entity {
                 base_entity,
                 name,
                 price,
+                tax_category: TaxCategory::default(),
+                option_groups: vec![],
+                availability: ProductAvailability::default(),
+                allergens: vec![],
+                dietary_tags: vec![],
+                prep_time: None,
             }
         }
+
+        pub fn find_option(
+            &self,
+            group_id: uuid::Uuid,
+            option_id: uuid::Uuid,
+        ) -> Option<&ProductOption> {
+            self.option_groups
+                .iter()
+                .find(|group| group.id == group_id)?
+                .options
+                .iter()
+                .find(|option| option.id == option_id)
+        }
+
+        pub fn has_same_id(&self, other: &Product) -> bool {
+            self.base_entity.id.base_id.value == other.base_entity.id.base_id.value
+        }
     }
 
-    impl Into<uuid::Uuid> for Product {
-        fn into(self) -> uuid::Uuid {
-            return self.base_entity.id.base_id.value;
+    impl From<Product> for uuid::Uuid {
+        fn from(product: Product) -> Self {
+            product.base_entity.id.base_id.value
         }
     }
 
@@ -57,22 +120,125 @@ pub mod entity {
         }
     }
 
+    #[derive(Clone, Default, PartialEq)]
+    pub enum ProductAvailability {
+        #[default]
+        InStock,
+        SoldOut,
+        Limited(u64),
+    }
+
+    impl ProductAvailability {
+        pub fn can_fulfill(&self, quantity: u64) -> bool {
+            match self {
+                ProductAvailability::InStock => true,
+                ProductAvailability::SoldOut => false,
+                ProductAvailability::Limited(stock_quantity) => quantity <= *stock_quantity,
+            }
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct ProductOption {
+        pub id: uuid::Uuid,
+        pub name: String,
+        #[builder(default)]
+        pub price_delta: Money,
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct ProductOptionGroup {
+        pub id: uuid::Uuid,
+        pub name: String,
+        #[builder(default)]
+        pub required: bool,
+        #[builder(default)]
+        pub min_selections: u32,
+        pub max_selections: u32,
+        pub options: Vec<ProductOption>,
+    }
+
+    impl ProductOptionGroup {
+        pub fn validate_selection_count(&self, selections: u32) -> Result<(), OrderDomainError> {
+            let min_selections = if self.required {
+                self.min_selections.max(1)
+            } else {
+                self.min_selections
+            };
+            if selections < min_selections && (self.required || selections > 0) {
+                return Err(OrderDomainError::InvalidProductOptions(format!(
+                    "at least {} selections required for {}",
+                    min_selections, self.name
+                )));
+            }
+            if selections > self.max_selections {
+                return Err(OrderDomainError::InvalidProductOptions(format!(
+                    "at most {} selections allowed for {}",
+                    self.max_selections, self.name
+                )));
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct SelectedOption {
+        pub group_id: uuid::Uuid,
+        pub option_id: uuid::Uuid,
+        #[builder(default)]
+        pub name: String,
+        #[builder(default)]
+        pub price_delta: Money,
+    }
+
     #[derive(Clone, Builder)]
     pub struct Restaurant {
         pub base_entity: BaseEntity<RestaurantId>,
         pub products: Vec<Product>,
         pub active: bool,
+        #[builder(default)]
+        pub delivery_zones: Vec<DeliveryZone>,
+        #[builder(default)]
+        pub opening_hours: Option<OpeningHours>,
+        #[builder(default)]
+        pub minimum_order_amount: Money,
     }
 
-    impl Into<uuid::Uuid> for Restaurant {
-        fn into(self) -> uuid::Uuid {
-            return self.base_entity.id.base_id.value;
+    impl From<Restaurant> for uuid::Uuid {
+        fn from(restaurant: Restaurant) -> Self {
+            restaurant.base_entity.id.base_id.value
         }
     }
 
     impl Restaurant {
         pub fn is_active(&self) -> bool {
-            return self.active;
+            self.active
+        }
+
+        pub fn availability_at(&self, at: chrono::DateTime<chrono::Utc>) -> RestaurantAvailability {
+            match &self.opening_hours {
+                Some(opening_hours) => opening_hours.availability_at(at),
+                None => RestaurantAvailability::Open,
+            }
+        }
+
+        pub fn validate_open_at(
+            &self,
+            at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), OrderDomainError> {
+            match self.availability_at(at) {
+                RestaurantAvailability::Open => Ok(()),
+                RestaurantAvailability::Closed => {
+                    Err(OrderDomainError::RestaurantClosed(at.to_rfc3339()))
+                }
+                RestaurantAvailability::Paused(reason) => {
+                    Err(OrderDomainError::RestaurantPaused(reason))
+                }
+            }
+        }
+
+        pub fn find_delivery_zone(&self, address: &StreetAddress) -> Option<&DeliveryZone> {
+            self.delivery_zones.iter().find(|zone| zone.covers(address))
         }
 
         pub fn new(restaurant_id: uuid::Uuid, products: Vec<Product>, active: bool) -> Self {
@@ -92,6 +258,9 @@ pub mod entity {
                 base_entity,
                 products,
                 active,
+                delivery_zones: vec![],
+                opening_hours: None,
+                minimum_order_amount: Money::default(),
             }
         }
     }
@@ -104,13 +273,79 @@ pub mod entity {
         pub quantity: u64,
         pub price: Money,
         pub sub_total: Money,
+        #[builder(default)]
+        pub tax: Money,
+        #[builder(default)]
+        pub selected_options: Vec<SelectedOption>,
+        #[builder(default)]
+        pub dietary_preferences: Option<DietaryPreferences>,
     }
 
     impl OrderItem {
+        pub fn unit_price(&self) -> Money {
+            let mut unit_price = self.product.price.clone();
+            for selected_option in self.selected_options.iter() {
+                unit_price += selected_option.price_delta.clone();
+            }
+            unit_price
+        }
+
+        pub fn restore_selected_options(&mut self, selected_options: Vec<SelectedOption>) {
+            let mut options_price = Money::default();
+            for selected_option in selected_options.iter() {
+                options_price += selected_option.price_delta.clone();
+            }
+            self.product.price = Money::new(self.price.amount - options_price.amount);
+            self.selected_options = selected_options;
+        }
+
         pub fn is_price_valid(&self) -> bool {
-            return self.price.is_greater_than_zero()
-                && self.price == self.product.price
-                && self.price.clone() * self.quantity == self.sub_total;
+            self.price.is_greater_than_zero()
+                && self.price == self.unit_price()
+                && self.price.clone() * self.quantity == self.sub_total
+        }
+
+        pub fn apply_catalog_product(&mut self, product: &Product) {
+            self.product.name = product.name.clone();
+            self.product.price = product.price.clone();
+            self.product.tax_category = product.tax_category.clone();
+            self.product.option_groups = product.option_groups.clone();
+            self.product.availability = product.availability.clone();
+            self.product.allergens = product.allergens.clone();
+            self.product.dietary_tags = product.dietary_tags.clone();
+            self.product.prep_time = product.prep_time;
+            for selected_option in self.selected_options.iter_mut() {
+                if let Some(option) =
+                    product.find_option(selected_option.group_id, selected_option.option_id)
+                {
+                    selected_option.name = option.name.clone();
+                    selected_option.price_delta = option.price_delta.clone();
+                }
+            }
+        }
+
+        pub fn validate_options(&self) -> Result<(), OrderDomainError> {
+            for selected_option in self.selected_options.iter() {
+                if self
+                    .product
+                    .find_option(selected_option.group_id, selected_option.option_id)
+                    .is_none()
+                {
+                    return Err(OrderDomainError::InvalidProductOptions(format!(
+                        "unknown option {} for product {}",
+                        selected_option.option_id, self.product.name
+                    )));
+                }
+            }
+            for group in self.product.option_groups.iter() {
+                let selections = self
+                    .selected_options
+                    .iter()
+                    .filter(|selected_option| selected_option.group_id == group.id)
+                    .count() as u32;
+                group.validate_selection_count(selections)?;
+            }
+            Ok(())
         }
     }
 
@@ -125,48 +360,406 @@ pub mod entity {
         pub tracking_id: TrackingId,
         pub order_status: OrderStatus,
         pub failure_messages: Vec<String>,
+        #[builder(default)]
+        pub tax: Money,
+        #[builder(default)]
+        pub delivery_fee: Money,
+        #[builder(default)]
+        pub scheduled_for: Option<chrono::DateTime<chrono::Utc>>,
+        #[builder(default)]
+        pub tip: Option<Tip>,
+        #[builder(default)]
+        pub adjustments: Vec<OrderAdjustment>,
+        #[builder(default)]
+        pub pending_events: Vec<OrderEvent>,
+        #[builder(default)]
+        pub status_changed_at: Option<chrono::DateTime<chrono::Utc>>,
+        #[builder(default)]
+        pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+        #[builder(default)]
+        pub payment_shares: Vec<PaymentShare>,
+        #[builder(default)]
+        pub checkout: Option<OrderCheckout>,
+        #[builder(default)]
+        pub dietary_preferences: Option<DietaryPreferences>,
+        #[builder(default)]
+        pub dietary_conflicts: Vec<DietaryConflict>,
+        #[builder(default)]
+        pub estimated_delivery_at: Option<chrono::DateTime<chrono::Utc>>,
+        #[builder(default)]
+        pub risk_decision: RiskDecision,
+        #[builder(default)]
+        pub risk_reasons: Vec<String>,
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct PaymentShare {
+        pub customer_id: CustomerId,
+        pub items_price: Money,
+        #[builder(default)]
+        pub total_price: Money,
+        #[builder(default)]
+        pub tax: Money,
+        #[builder(default)]
+        pub tip: Money,
+        #[builder(default)]
+        pub status: PaymentShareStatus,
+    }
+
+    impl PaymentShare {
+        pub fn customer_uuid(&self) -> uuid::Uuid {
+            self.customer_id.clone().into()
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OrderAdjustment {
+        pub id: uuid::Uuid,
+        pub order_item_id: OrderItemId,
+        pub quantity: u64,
+        pub reason: AdjustmentReason,
+        pub refund_amount: Money,
+        pub created_at: chrono::DateTime<chrono::Utc>,
     }
 
-    impl Into<uuid::Uuid> for Order {
-        fn into(self) -> uuid::Uuid {
-            return self.aggregate_root.base_entity.id.base_id.value;
+    impl From<Order> for uuid::Uuid {
+        fn from(order: Order) -> Self {
+            order.aggregate_root.base_entity.id.base_id.value
         }
     }
 
     impl Order {
         pub fn validate_total_price(&self) -> Result<(), OrderDomainError> {
-            if self.price.is_greater_than_zero() {
+            if !self.price.is_greater_than_zero() {
                 return Err(OrderDomainError::TotalPriceZeroError);
             }
-            return Ok(());
+            Ok(())
+        }
+
+        pub fn validate_minimum_order_amount(
+            &self,
+            restaurant: &Restaurant,
+        ) -> Result<(), OrderDomainError> {
+            if self.price < restaurant.minimum_order_amount {
+                return Err(OrderDomainError::BelowMinimumOrderAmount(
+                    restaurant.minimum_order_amount.amount,
+                ));
+            }
+            Ok(())
         }
 
-        pub fn validate_items_price(&self) -> Result<(), OrderDomainError> {
+        pub fn collect_items_violations(&self, report: &mut ValidationReport) {
             let mut order_items_total_price = Money::new(0.0);
-            for item in self.items.iter() {
-                if item.is_price_valid() {
-                    order_items_total_price += item.sub_total.clone();
-                } else {
-                    return Err(OrderDomainError::OrderItemPriceInvalid);
+            for (index, item) in self.items.iter().enumerate() {
+                report.check(format!("items[{}].options", index), item.validate_options());
+                if !item.is_price_valid() {
+                    report.add(
+                        format!("items[{}].price", index),
+                        format!(
+                            "{} (expected price {} and sub total {}, given {} and {})",
+                            OrderDomainError::OrderItemPriceInvalid,
+                            item.unit_price().amount,
+                            (item.unit_price() * item.quantity).amount,
+                            item.price.amount,
+                            item.sub_total.amount
+                        ),
+                    );
                 }
+                order_items_total_price += item.sub_total.clone();
             }
             if order_items_total_price != self.price {
-                return Err(OrderDomainError::OrderTotalPriceMismatch);
+                report.add(
+                    "price",
+                    format!(
+                        "{} (expected {}, given {})",
+                        OrderDomainError::OrderTotalPriceMismatch,
+                        order_items_total_price.amount,
+                        self.price.amount
+                    ),
+                );
+            }
+        }
+
+        pub fn collect_restaurant_violations(
+            &self,
+            restaurant: &Restaurant,
+            report: &mut ValidationReport,
+        ) {
+            if !restaurant.is_active() {
+                report.add(
+                    "restaurant_id",
+                    OrderDomainError::InactiveRestaurant.to_string(),
+                );
+            }
+            let mut requested_quantities: Vec<(uuid::Uuid, u64)> = vec![];
+            for item in self.items.iter() {
+                let product_id: uuid::Uuid = item.product.clone().into();
+                match requested_quantities
+                    .iter_mut()
+                    .find(|(id, _)| *id == product_id)
+                {
+                    Some((_, quantity)) => *quantity += item.quantity,
+                    None => requested_quantities.push((product_id, item.quantity)),
+                }
+            }
+            for (index, item) in self.items.iter().enumerate() {
+                let product_id: uuid::Uuid = item.product.clone().into();
+                let requested_quantity = requested_quantities
+                    .iter()
+                    .find(|(id, _)| *id == product_id)
+                    .map(|(_, quantity)| *quantity)
+                    .unwrap_or(item.quantity);
+                match restaurant
+                    .products
+                    .iter()
+                    .find(|product| product.base_entity.id.base_id.value == product_id)
+                {
+                    None => report.add_unavailable_product(
+                        format!("items[{}].product_id", index),
+                        product_id,
+                        format!("unknown product {}", product_id),
+                    ),
+                    Some(product) if !product.availability.can_fulfill(requested_quantity) => {
+                        report.add_unavailable_product(
+                            format!("items[{}].quantity", index),
+                            product_id,
+                            format!(
+                                "product {} is unavailable for quantity {}",
+                                product_id, requested_quantity
+                            ),
+                        )
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        fn item_dietary_preferences<'a>(
+            &'a self,
+            item: &'a OrderItem,
+        ) -> Option<&'a DietaryPreferences> {
+            item.dietary_preferences
+                .as_ref()
+                .or(self.dietary_preferences.as_ref())
+        }
+
+        pub fn declared_dietary_preferences(&self) -> Option<DietaryPreferences> {
+            DietaryPreferences::combine(
+                self.dietary_preferences
+                    .iter()
+                    .chain(
+                        self.items
+                            .iter()
+                            .filter_map(|item| item.dietary_preferences.as_ref()),
+                    )
+                    .cloned()
+                    .collect(),
+            )
+        }
+
+        pub fn assess_dietary_constraints(&mut self) {
+            let mut dietary_conflicts: Vec<DietaryConflict> = vec![];
+            for item in self.items.iter() {
+                if let Some(preferences) = self.item_dietary_preferences(item) {
+                    for conflict in preferences.conflicts_for(&item.product) {
+                        if !dietary_conflicts.contains(&conflict) {
+                            dietary_conflicts.push(conflict);
+                        }
+                    }
+                }
+            }
+            self.dietary_conflicts = dietary_conflicts;
+        }
+
+        pub fn collect_dietary_violations(&self, report: &mut ValidationReport) {
+            for (index, item) in self.items.iter().enumerate() {
+                let preferences = match self.item_dietary_preferences(item) {
+                    Some(preferences) if preferences.rejects_conflicts() => preferences,
+                    _ => continue,
+                };
+                for conflict in preferences.conflicts_for(&item.product) {
+                    report.add(
+                        format!("items[{}].product_id", index),
+                        format!("{} ({})", OrderDomainError::DietaryConflict, conflict),
+                    );
+                }
+            }
+        }
+
+        pub fn validate_tip(&self) -> Result<(), OrderDomainError> {
+            match self.tip.as_ref().map(|tip| &tip.amount) {
+                Some(TipAmount::Fixed(amount)) if amount.amount < 0.0 => Err(
+                    OrderDomainError::InvalidTip(String::from("amount must not be negative")),
+                ),
+                Some(TipAmount::Percentage(percentage)) if !(0.0..=100.0).contains(percentage) => {
+                    Err(OrderDomainError::InvalidTip(String::from(
+                        "percentage must be between 0 and 100",
+                    )))
+                }
+                _ => Ok(()),
             }
-            return Ok(());
+        }
+
+        pub fn collect_violations(&self, report: &mut ValidationReport) {
+            report.check("price", self.validate_total_price());
+            self.collect_items_violations(report);
+            report.check("tip", self.validate_tip());
         }
 
         pub fn validate_order(&self) -> Result<(), OrderDomainError> {
-            self.validate_total_price()?;
-            self.validate_items_price()?;
+            let mut report = ValidationReport::new();
+            self.collect_violations(&mut report);
+            report.into_result()
+        }
+
+        pub fn validate_for_restaurant(
+            &self,
+            restaurant: &Restaurant,
+        ) -> Result<(), OrderDomainError> {
+            let mut report = ValidationReport::new();
+            self.collect_restaurant_violations(restaurant, &mut report);
+            self.collect_dietary_violations(&mut report);
+            self.collect_violations(&mut report);
+            report.into_result()
+        }
+
+        pub fn tip_amount(&self) -> Money {
+            match &self.tip {
+                Some(tip) => tip.amount_for(&self.price),
+                None => Money::default(),
+            }
+        }
+
+        pub fn apply_taxes<TC: TaxCalculator>(&mut self, tax_calculator: &TC) {
+            let mut order_tax = Money::default();
+            for item in self.items.iter_mut() {
+                item.tax = tax_calculator
+                    .calculate_item_tax(&self.street_address, item)
+                    .round();
+                order_tax += item.tax.clone();
+            }
+            self.tax = order_tax;
+        }
+
+        pub fn apply_delivery_zone(
+            &mut self,
+            restaurant: &Restaurant,
+        ) -> Result<(), OrderDomainError> {
+            let delivery_zone = restaurant
+                .find_delivery_zone(&self.street_address)
+                .ok_or(OrderDomainError::UndeliverableAddress)?;
+            if self.price < delivery_zone.minimum_order_amount {
+                return Err(OrderDomainError::DeliveryZoneMinimumNotMet);
+            }
+            self.delivery_fee = delivery_zone.delivery_fee(&self.street_address, &self.price);
             Ok(())
         }
 
-        pub fn pay(&mut self) -> Result<(), OrderDomainError> {
+        pub fn total_price(&self) -> Money {
+            self.price.clone() + self.tax.clone() + self.delivery_fee.clone() + self.tip_amount()
+        }
+
+        pub fn schedule(
+            &mut self,
+            scheduled_for: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), OrderDomainError> {
             if self.order_status != OrderStatus::Pending {
+                return Err(OrderDomainError::InvalidOrderStatus(String::from(
+                    "schedule",
+                )));
+            }
+            self.record(OrderEvent::Scheduled(scheduled_for));
+            Ok(())
+        }
+
+        pub fn release(&mut self) -> Result<(), OrderDomainError> {
+            if self.order_status != OrderStatus::Scheduled {
+                return Err(OrderDomainError::InvalidOrderStatus(String::from(
+                    "release",
+                )));
+            }
+            self.record(OrderEvent::Released);
+            Ok(())
+        }
+
+        pub fn pay(&mut self) -> Result<(), OrderDomainError> {
+            if self.order_status != OrderStatus::Pending
+                || (self.is_split_payment() && !self.all_shares_paid())
+            {
                 return Err(OrderDomainError::InvalidOrderStatus(String::from("pay")));
             }
-            self.order_status = OrderStatus::Paid;
+            self.record(OrderEvent::Paid);
+            Ok(())
+        }
+
+        pub fn is_split_payment(&self) -> bool {
+            !self.payment_shares.is_empty()
+        }
+
+        pub fn all_shares_paid(&self) -> bool {
+            self.payment_shares
+                .iter()
+                .all(|share| share.status == PaymentShareStatus::Paid)
+        }
+
+        pub fn allocate_payment_shares(&mut self) {
+            let items_price = self.price.amount;
+            if self.payment_shares.is_empty() || items_price <= 0.0 {
+                return;
+            }
+            let total_price = self.total_price().amount;
+            let tax = self.tax.amount;
+            let tip = self.tip_amount().amount;
+            let (mut allocated_total, mut allocated_tax, mut allocated_tip) = (0.0, 0.0, 0.0);
+            let last_index = self.payment_shares.len() - 1;
+            for (index, share) in self.payment_shares.iter_mut().enumerate() {
+                if index == last_index {
+                    share.total_price = Money::new(total_price - allocated_total);
+                    share.tax = Money::new(tax - allocated_tax);
+                    share.tip = Money::new(tip - allocated_tip);
+                } else {
+                    let ratio = share.items_price.amount / items_price;
+                    share.total_price = Money::new((total_price * ratio).round());
+                    share.tax = Money::new((tax * ratio).round());
+                    share.tip = Money::new((tip * ratio).round());
+                }
+                allocated_total += share.total_price.amount;
+                allocated_tax += share.tax.amount;
+                allocated_tip += share.tip.amount;
+            }
+        }
+
+        fn pending_share(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<&PaymentShare, OrderDomainError> {
+            if self.order_status != OrderStatus::Pending {
+                return Err(OrderDomainError::InvalidOrderStatus(String::from(
+                    "settle payment share",
+                )));
+            }
+            let share = self
+                .payment_shares
+                .iter()
+                .find(|share| share.customer_uuid() == customer_id)
+                .ok_or(OrderDomainError::PaymentShareNotFound(customer_id))?;
+            if share.status != PaymentShareStatus::Pending {
+                return Err(OrderDomainError::InvalidOrderStatus(String::from(
+                    "settle payment share",
+                )));
+            }
+            Ok(share)
+        }
+
+        pub fn pay_share(&mut self, customer_id: uuid::Uuid) -> Result<(), OrderDomainError> {
+            self.pending_share(customer_id)?;
+            self.record(OrderEvent::SharePaid(customer_id));
+            Ok(())
+        }
+
+        pub fn fail_share(&mut self, customer_id: uuid::Uuid) -> Result<(), OrderDomainError> {
+            self.pending_share(customer_id)?;
+            self.record(OrderEvent::ShareFailed(customer_id));
             Ok(())
         }
 
@@ -176,7 +769,107 @@ pub mod entity {
                     "approve",
                 )));
             }
-            self.order_status = OrderStatus::Approved;
+            self.record(OrderEvent::Approved);
+            Ok(())
+        }
+
+        pub fn adjust_item(
+            &mut self,
+            adjustment_id: uuid::Uuid,
+            order_item_id: i64,
+            quantity: u64,
+            reason: AdjustmentReason,
+            created_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<OrderAdjustment, OrderDomainError> {
+            if self.order_status != OrderStatus::Approved {
+                return Err(OrderDomainError::InvalidOrderStatus(String::from("adjust")));
+            }
+            if quantity == 0 {
+                return Err(OrderDomainError::InvalidAdjustment(String::from(
+                    "quantity must be greater than zero",
+                )));
+            }
+            let item = self
+                .items
+                .iter()
+                .find(|item| item.base_entity.id.base_id.value == order_item_id)
+                .ok_or(OrderDomainError::OrderItemNotFound)?;
+            if quantity > item.quantity {
+                return Err(OrderDomainError::InvalidAdjustment(format!(
+                    "only {} items left to adjust",
+                    item.quantity
+                )));
+            }
+            let adjusted_item_id = item.base_entity.id.clone();
+            let mut adjusted_order = self.clone();
+            adjusted_order.reduce_item_quantity(order_item_id, quantity);
+            let refund_amount =
+                Money::new(self.total_price().amount - adjusted_order.total_price().amount);
+            let adjustment = OrderAdjustmentBuilder::default()
+                .id(adjustment_id)
+                .order_item_id(adjusted_item_id)
+                .quantity(quantity)
+                .reason(reason)
+                .refund_amount(refund_amount)
+                .created_at(created_at)
+                .build()
+                .unwrap();
+            self.record(OrderEvent::ItemAdjusted(adjustment.clone()));
+            Ok(adjustment)
+        }
+
+        fn reduce_item_quantity(&mut self, order_item_id: i64, quantity: u64) {
+            let (sub_total_reduction, tax_reduction) = match self
+                .items
+                .iter_mut()
+                .find(|item| item.base_entity.id.base_id.value == order_item_id)
+            {
+                Some(item) if item.quantity > 0 => {
+                    let remaining_quantity = item.quantity.saturating_sub(quantity);
+                    let remaining_share = remaining_quantity as f64 / item.quantity as f64;
+                    let sub_total_before = item.sub_total.clone();
+                    let tax_before = item.tax.clone();
+                    item.quantity = remaining_quantity;
+                    item.sub_total = item.price.clone() * remaining_quantity;
+                    item.tax = tax_before.multiply(remaining_share).round();
+                    (
+                        sub_total_before.amount - item.sub_total.amount,
+                        tax_before.amount - item.tax.amount,
+                    )
+                }
+                _ => return,
+            };
+            self.price = Money::new(self.price.amount - sub_total_reduction);
+            self.tax = Money::new(self.tax.amount - tax_reduction);
+        }
+
+        pub fn payment_order_id(&self) -> uuid::Uuid {
+            match self.checkout.as_ref() {
+                Some(checkout) => checkout.id,
+                None => self.aggregate_root.base_entity.id.base_id.value,
+            }
+        }
+
+        pub fn cancels_whole_checkout(&self) -> bool {
+            self.checkout.as_ref().is_some_and(|checkout| {
+                checkout.cancellation_policy == CheckoutCancellationPolicy::CancelAll
+            })
+        }
+
+        pub fn init_checkout_cancel(
+            &mut self,
+            failure_messages: Vec<String>,
+        ) -> Result<(), OrderDomainError> {
+            if !self.cancels_whole_checkout()
+                || !(self.order_status == OrderStatus::Paid
+                    || self.order_status == OrderStatus::Approved)
+            {
+                return Err(OrderDomainError::InvalidOrderStatus(String::from(
+                    "checkout cancel",
+                )));
+            }
+            self.record(OrderEvent::CancelInitiated(failure_messages));
+            self.update_delivery_estimate(None);
             Ok(())
         }
 
@@ -189,171 +882,3737 @@ pub mod entity {
                     "init cancel",
                 )));
             }
-            self.order_status = OrderStatus::Cancelling;
-            self.failure_messages.append(&mut failure_messages.clone());
+            self.record(OrderEvent::CancelInitiated(failure_messages));
+            self.update_delivery_estimate(None);
             Ok(())
         }
 
         pub fn cancel(&mut self, failure_messages: Vec<String>) -> Result<(), OrderDomainError> {
             if !(self.order_status == OrderStatus::Cancelling
-                || self.order_status == OrderStatus::Pending)
+                || self.order_status == OrderStatus::Pending
+                || self.order_status == OrderStatus::Scheduled)
             {
                 return Err(OrderDomainError::InvalidOrderStatus(String::from("cancel")));
             }
-            self.order_status = OrderStatus::Cancelled;
-            self.failure_messages.append(&mut failure_messages.clone());
+            self.record(OrderEvent::Cancelled(failure_messages));
+            self.update_delivery_estimate(None);
             Ok(())
         }
-    }
-}
 
-pub mod value_object {
-    use common::value_object::{BaseId, BaseIdBuilder};
-    use derive_builder::Builder;
+        pub fn apply(&mut self, event: &OrderEvent) {
+            match event {
+                OrderEvent::Scheduled(scheduled_for) => {
+                    self.order_status = OrderStatus::Scheduled;
+                    self.scheduled_for = Some(*scheduled_for);
+                }
+                OrderEvent::Released => self.order_status = OrderStatus::Pending,
+                OrderEvent::Paid => self.order_status = OrderStatus::Paid,
+                OrderEvent::Approved => self.order_status = OrderStatus::Approved,
+                OrderEvent::ItemAdjusted(adjustment) => {
+                    self.reduce_item_quantity(
+                        adjustment.order_item_id.clone().into(),
+                        adjustment.quantity,
+                    );
+                    self.adjustments.push(adjustment.clone());
+                }
+                OrderEvent::CancelInitiated(failure_messages) => {
+                    self.order_status = OrderStatus::Cancelling;
+                    self.failure_messages
+                        .extend(failure_messages.iter().cloned());
+                }
+                OrderEvent::Cancelled(failure_messages) => {
+                    self.order_status = OrderStatus::Cancelled;
+                    self.failure_messages
+                        .extend(failure_messages.iter().cloned());
+                }
+                OrderEvent::SharePaid(customer_id) => {
+                    self.set_share_status(*customer_id, PaymentShareStatus::Paid)
+                }
+                OrderEvent::ShareFailed(customer_id) => {
+                    self.set_share_status(*customer_id, PaymentShareStatus::Failed)
+                }
+                OrderEvent::DeliveryEstimated(estimated_delivery_at) => {
+                    self.estimated_delivery_at = *estimated_delivery_at
+                }
+            }
+        }
 
-    #[derive(Clone, Builder)]
-    pub struct OrderItemId {
-        pub base_id: BaseId<i64>,
-    }
+        pub fn apply_risk_assessment(
+            &mut self,
+            assessment: &RiskAssessment,
+        ) -> Result<(), OrderDomainError> {
+            if assessment.decision == RiskDecision::Deny {
+                return Err(OrderDomainError::RiskCheckDenied(
+                    assessment.reasons().join("; "),
+                ));
+            }
+            self.risk_decision = assessment.decision.clone();
+            self.risk_reasons = assessment.reasons();
+            Ok(())
+        }
 
-    impl From<i64> for OrderItemId {
-        fn from(id: i64) -> Self {
-            return OrderItemIdBuilder::default()
-                .base_id(BaseIdBuilder::default().value(id).build().unwrap())
-                .build()
-                .unwrap();
+        pub fn update_delivery_estimate(
+            &mut self,
+            estimated_delivery_at: Option<chrono::DateTime<chrono::Utc>>,
+        ) {
+            if self.estimated_delivery_at != estimated_delivery_at {
+                self.record(OrderEvent::DeliveryEstimated(estimated_delivery_at));
+            }
         }
-    }
 
-    #[derive(Clone, Builder)]
-    pub struct StreetAddress {
-        pub id: uuid::Uuid,
-        pub street: String,
-        pub postal_code: String,
-        pub city: String,
-    }
+        fn set_share_status(&mut self, customer_id: uuid::Uuid, status: PaymentShareStatus) {
+            if let Some(share) = self
+                .payment_shares
+                .iter_mut()
+                .find(|share| share.customer_uuid() == customer_id)
+            {
+                share.status = status;
+            }
+        }
 
-    #[derive(Clone)]
-    pub struct TrackingId {
-        base_id: BaseId<uuid::Uuid>,
-    }
+        fn record(&mut self, event: OrderEvent) {
+            self.apply(&event);
+            self.aggregate_root.version += 1;
+            self.pending_events.push(event);
+        }
 
-    impl From<uuid::Uuid> for TrackingId {
-        fn from(id: uuid::Uuid) -> Self {
-            return Self {
-                base_id: BaseIdBuilder::default().value(id).build().unwrap(),
-            };
+        pub fn replay(mut self, events: &[OrderEvent]) -> Order {
+            for event in events.iter() {
+                self.apply(event);
+            }
+            self
         }
-    }
 
-    impl Into<uuid::Uuid> for TrackingId {
-        fn into(self) -> uuid::Uuid {
-            return self.base_id.value;
+        pub fn take_pending_events(&mut self) -> Vec<OrderEvent> {
+            std::mem::take(&mut self.pending_events)
         }
     }
-}
 
-pub mod event {
+    #[cfg(test)]
+    mod tests {
+        use common::value_object::money::Money;
 
-    use common::event::DomainEvent;
-    use derive_builder::Builder;
+        use super::{ProductOption, ProductOptionGroup, SelectedOption};
+        use crate::fixtures;
+        use crate::value_object::AdjustmentReason;
 
-    use crate::entity::Order;
+        fn size_group() -> ProductOptionGroup {
+            ProductOptionGroup {
+                id: fixtures::id(60),
+                name: String::from("size"),
+                required: true,
+                min_selections: 1,
+                max_selections: 1,
+                options: vec![
+                    ProductOption {
+                        id: fixtures::id(61),
+                        name: String::from("small"),
+                        price_delta: Money::default(),
+                    },
+                    ProductOption {
+                        id: fixtures::id(62),
+                        name: String::from("large"),
+                        price_delta: Money::new(300.0),
+                    },
+                ],
+            }
+        }
+
+        fn large() -> SelectedOption {
+            SelectedOption {
+                group_id: fixtures::id(60),
+                option_id: fixtures::id(62),
+                name: String::new(),
+                price_delta: Money::default(),
+            }
+        }
+
+        #[test]
+        fn unit_price_adds_catalog_option_deltas() {
+            let mut catalog_product = fixtures::product(10, 1000.0);
+            catalog_product.option_groups = vec![size_group()];
+            let mut item = fixtures::order_item(1, fixtures::product(10, 1000.0), 2);
+            item.selected_options = vec![large()];
+            item.price = Money::new(1300.0);
+            item.sub_total = Money::new(2600.0);
+            item.apply_catalog_product(&catalog_product);
+            assert_eq!(item.unit_price().amount, 1300.0);
+            assert!(item.is_price_valid());
+            assert!(item.validate_options().is_ok());
+        }
+
+        #[test]
+        fn validate_options_requires_a_selection_in_required_groups() {
+            let mut catalog_product = fixtures::product(10, 1000.0);
+            catalog_product.option_groups = vec![size_group()];
+            let mut item = fixtures::order_item(1, fixtures::product(10, 1000.0), 1);
+            item.apply_catalog_product(&catalog_product);
+            assert!(item.validate_options().is_err());
+        }
+
+        #[test]
+        fn restore_selected_options_keeps_the_unit_price_of_a_stored_item() {
+            let mut item = fixtures::order_item(1, fixtures::product(10, 1300.0), 2);
+            let mut stored_option = large();
+            stored_option.price_delta = Money::new(300.0);
+            item.restore_selected_options(vec![stored_option]);
+            assert_eq!(item.product.price.amount, 1000.0);
+            assert_eq!(item.unit_price().amount, 1300.0);
+            assert!(item.is_price_valid());
+        }
+
+        fn restaurant_violations(
+            order: &super::Order,
+            restaurant: &super::Restaurant,
+        ) -> Vec<String> {
+            let mut report = common::validation::ValidationReport::new();
+            order.collect_restaurant_violations(restaurant, &mut report);
+            report.errors.into_iter().map(|error| error.field).collect()
+        }
+
+        #[test]
+        fn sold_out_and_unknown_products_are_rejected() {
+            let mut sold_out = fixtures::product(10, 1000.0);
+            sold_out.availability = super::ProductAvailability::SoldOut;
+            let order = fixtures::order(vec![
+                fixtures::order_item(1, fixtures::product(10, 1000.0), 1),
+                fixtures::order_item(2, fixtures::product(11, 500.0), 1),
+            ]);
+            assert_eq!(
+                restaurant_violations(&order, &fixtures::restaurant(vec![sold_out])),
+                vec!["items[0].quantity", "items[1].product_id"]
+            );
+        }
+
+        #[test]
+        fn only_unavailable_products_are_reported_as_products_unavailable() {
+            let mut sold_out = fixtures::product(10, 1000.0);
+            sold_out.availability = super::ProductAvailability::SoldOut;
+            let order = fixtures::order(vec![
+                fixtures::order_item(1, fixtures::product(10, 1000.0), 1),
+                fixtures::order_item(2, fixtures::product(10, 1000.0), 1),
+            ]);
+            let restaurant = fixtures::restaurant(vec![sold_out]);
+            match order.validate_for_restaurant(&restaurant) {
+                Err(common::error::OrderDomainError::ProductsUnavailable(product_ids)) => {
+                    assert_eq!(product_ids, vec![fixtures::id(10)])
+                }
+                _ => panic!("expected the sold out product to be reported"),
+            }
+            let mut inactive_restaurant = restaurant;
+            inactive_restaurant.active = false;
+            match order.validate_for_restaurant(&inactive_restaurant) {
+                Err(common::error::OrderDomainError::ValidationFailed(report)) => {
+                    assert_eq!(report.unavailable_products(), vec![fixtures::id(10)])
+                }
+                _ => panic!("expected a validation report"),
+            }
+        }
+
+        #[test]
+        fn limited_stock_is_checked_against_the_total_requested_quantity() {
+            let mut limited = fixtures::product(10, 1000.0);
+            limited.availability = super::ProductAvailability::Limited(3);
+            let restaurant = fixtures::restaurant(vec![limited]);
+            let within_stock = fixtures::order(vec![
+                fixtures::order_item(1, fixtures::product(10, 1000.0), 1),
+                fixtures::order_item(2, fixtures::product(10, 1000.0), 2),
+            ]);
+            assert!(restaurant_violations(&within_stock, &restaurant).is_empty());
+            let above_stock = fixtures::order(vec![
+                fixtures::order_item(1, fixtures::product(10, 1000.0), 2),
+                fixtures::order_item(2, fixtures::product(10, 1000.0), 2),
+            ]);
+            assert_eq!(
+                restaurant_violations(&above_stock, &restaurant),
+                vec!["items[0].quantity", "items[1].quantity"]
+            );
+        }
+
+        #[test]
+        fn validate_total_price_rejects_only_non_positive_totals() {
+            let order = fixtures::order(vec![fixtures::order_item(
+                1,
+                fixtures::product(10, 1000.0),
+                1,
+            )]);
+            assert!(order.validate_total_price().is_ok());
+            let empty_order = fixtures::order(vec![]);
+            assert!(matches!(
+                empty_order.validate_total_price(),
+                Err(common::error::OrderDomainError::TotalPriceZeroError)
+            ));
+        }
+
+        #[test]
+        fn products_compare_by_name_and_price() {
+            let product = fixtures::product(10, 250.0);
+            let mut same_id = fixtures::product(10, 250.0);
+            same_id.price = common::value_object::money::Money::new(300.0);
+            assert!(product == fixtures::product(10, 250.0));
+            assert!(product != same_id);
+            assert!(product.has_same_id(&same_id));
+            assert!(!product.has_same_id(&fixtures::product(11, 250.0)));
+        }
+
+        fn approved_order() -> super::Order {
+            let mut item = fixtures::order_item(1, fixtures::product(10, 1000.0), 3);
+            item.tax = Money::new(270.0);
+            let mut order = fixtures::order(vec![item]);
+            order.tax = Money::new(270.0);
+            order.order_status = common::value_object::OrderStatus::Approved;
+            order
+        }
+
+        #[test]
+        fn adjust_item_refunds_the_line_share_including_tax() {
+            let mut order = approved_order();
+            let adjustment = order
+                .adjust_item(
+                    fixtures::id(70),
+                    1,
+                    1,
+                    AdjustmentReason::Damaged,
+                    fixtures::now(),
+                )
+                .unwrap();
+            assert_eq!(adjustment.refund_amount.amount, 1090.0);
+            assert_eq!(order.items[0].quantity, 2);
+            assert_eq!(order.price.amount, 2000.0);
+            assert_eq!(order.tax.amount, 180.0);
+        }
+
+        #[test]
+        fn adjust_item_rejects_more_than_the_remaining_quantity() {
+            let mut order = approved_order();
+            assert!(matches!(
+                order.adjust_item(
+                    fixtures::id(70),
+                    1,
+                    4,
+                    AdjustmentReason::Unavailable,
+                    fixtures::now()
+                ),
+                Err(common::error::OrderDomainError::InvalidAdjustment(_))
+            ));
+            order.order_status = common::value_object::OrderStatus::Paid;
+            assert!(matches!(
+                order.adjust_item(
+                    fixtures::id(70),
+                    1,
+                    1,
+                    AdjustmentReason::Unavailable,
+                    fixtures::now()
+                ),
+                Err(common::error::OrderDomainError::InvalidOrderStatus(_))
+            ));
+        }
+    }
+}
+
+pub mod value_object {
+    use std::{fmt::Display, str::FromStr};
+
+    use common::value_object::{money::Money, BaseId, BaseIdBuilder};
+    use derive_builder::Builder;
+
+    #[derive(Clone, Builder)]
+    pub struct OrderItemId {
+        pub base_id: BaseId<i64>,
+    }
+
+    impl From<i64> for OrderItemId {
+        fn from(id: i64) -> Self {
+            OrderItemIdBuilder::default()
+                .base_id(BaseIdBuilder::default().value(id).build().unwrap())
+                .build()
+                .unwrap()
+        }
+    }
+
+    impl From<OrderItemId> for i64 {
+        fn from(order_item_id: OrderItemId) -> Self {
+            order_item_id.base_id.value
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct Coordinates {
+        pub latitude: f64,
+        pub longitude: f64,
+    }
+
+    impl Coordinates {
+        const EARTH_RADIUS_KM: f64 = 6371.0;
+
+        pub fn distance_km(&self, other: &Coordinates) -> f64 {
+            let delta_latitude = (other.latitude - self.latitude).to_radians();
+            let delta_longitude = (other.longitude - self.longitude).to_radians();
+            let a = (delta_latitude / 2.0).sin().powi(2)
+                + self.latitude.to_radians().cos()
+                    * other.latitude.to_radians().cos()
+                    * (delta_longitude / 2.0).sin().powi(2);
+            2.0 * Self::EARTH_RADIUS_KM * a.sqrt().asin()
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct StreetAddress {
+        pub id: uuid::Uuid,
+        pub street: String,
+        pub postal_code: String,
+        pub city: String,
+        #[builder(default)]
+        pub coordinates: Option<Coordinates>,
+    }
+
+    #[derive(Clone, PartialEq)]
+    pub enum AdjustmentReason {
+        Unavailable,
+        Damaged,
+    }
+
+    impl FromStr for AdjustmentReason {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Unavailable" => Ok(AdjustmentReason::Unavailable),
+                "Damaged" => Ok(AdjustmentReason::Damaged),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for AdjustmentReason {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                AdjustmentReason::Unavailable => write!(f, "Unavailable"),
+                AdjustmentReason::Damaged => write!(f, "Damaged"),
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub enum TipAmount {
+        Fixed(Money),
+        Percentage(f64),
+    }
+
+    #[derive(Clone, Default, PartialEq)]
+    pub enum TipRecipient {
+        #[default]
+        Courier,
+        Restaurant,
+    }
+
+    impl FromStr for TipRecipient {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Courier" => Ok(TipRecipient::Courier),
+                "Restaurant" => Ok(TipRecipient::Restaurant),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for TipRecipient {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                TipRecipient::Courier => write!(f, "Courier"),
+                TipRecipient::Restaurant => write!(f, "Restaurant"),
+            }
+        }
+    }
+
+    #[derive(Clone, Default, PartialEq)]
+    pub enum PaymentShareStatus {
+        #[default]
+        Pending,
+        Paid,
+        Failed,
+    }
+
+    impl FromStr for PaymentShareStatus {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Pending" => Ok(PaymentShareStatus::Pending),
+                "Paid" => Ok(PaymentShareStatus::Paid),
+                "Failed" => Ok(PaymentShareStatus::Failed),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for PaymentShareStatus {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                PaymentShareStatus::Pending => write!(f, "Pending"),
+                PaymentShareStatus::Paid => write!(f, "Paid"),
+                PaymentShareStatus::Failed => write!(f, "Failed"),
+            }
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct Tip {
+        pub amount: TipAmount,
+        #[builder(default)]
+        pub recipient: TipRecipient,
+    }
+
+    impl Tip {
+        pub fn amount_for(&self, order_price: &Money) -> Money {
+            match &self.amount {
+                TipAmount::Fixed(amount) => amount.clone(),
+                TipAmount::Percentage(percentage) => {
+                    order_price.multiply(percentage / 100.0).round()
+                }
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct TrackingId {
+        base_id: BaseId<uuid::Uuid>,
+    }
+
+    impl From<uuid::Uuid> for TrackingId {
+        fn from(id: uuid::Uuid) -> Self {
+            Self {
+                base_id: BaseIdBuilder::default().value(id).build().unwrap(),
+            }
+        }
+    }
+
+    impl From<TrackingId> for uuid::Uuid {
+        fn from(tracking_id: TrackingId) -> Self {
+            tracking_id.base_id.value
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use common::error::OrderDomainError;
+        use common::value_object::money::Money;
+
+        use super::{Tip, TipAmount, TipRecipient};
+        use crate::fixtures;
+
+        fn tip(amount: TipAmount) -> Tip {
+            Tip {
+                amount,
+                recipient: TipRecipient::default(),
+            }
+        }
+
+        #[test]
+        fn percentage_tips_are_rounded_to_whole_units() {
+            let tip = tip(TipAmount::Percentage(12.5));
+            assert_eq!(tip.amount_for(&Money::new(333.0)).amount, 42.0);
+        }
+
+        #[test]
+        fn total_price_includes_the_tip() {
+            let mut order = fixtures::order(vec![fixtures::order_item(
+                1,
+                fixtures::product(10, 1000.0),
+                1,
+            )]);
+            order.tax = Money::new(90.0);
+            order.delivery_fee = Money::new(250.0);
+            order.tip = Some(tip(TipAmount::Percentage(10.0)));
+            assert_eq!(order.tip_amount().amount, 100.0);
+            assert_eq!(order.total_price().amount, 1440.0);
+        }
+
+        #[test]
+        fn validate_tip_rejects_negative_amounts_and_out_of_range_percentages() {
+            let mut order = fixtures::order(vec![fixtures::order_item(
+                1,
+                fixtures::product(10, 1000.0),
+                1,
+            )]);
+            order.tip = Some(tip(TipAmount::Fixed(Money::new(-1.0))));
+            assert!(matches!(
+                order.validate_tip(),
+                Err(OrderDomainError::InvalidTip(_))
+            ));
+            order.tip = Some(tip(TipAmount::Percentage(120.0)));
+            assert!(matches!(
+                order.validate_tip(),
+                Err(OrderDomainError::InvalidTip(_))
+            ));
+            order.tip = Some(tip(TipAmount::Percentage(100.0)));
+            assert!(order.validate_tip().is_ok());
+        }
+    }
+}
+
+pub mod delivery {
+    use std::collections::HashSet;
+
+    use common::value_object::money::Money;
+    use derive_builder::Builder;
+
+    use crate::value_object::{Coordinates, StreetAddress};
+
+    #[derive(Clone)]
+    pub enum DeliveryArea {
+        PostalCodes(HashSet<String>),
+        Radius { center: Coordinates, radius_km: f64 },
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct DeliveryFeeSchedule {
+        pub base_fee: Money,
+        #[builder(default)]
+        pub fee_per_km: Money,
+        #[builder(default)]
+        pub free_delivery_threshold: Option<Money>,
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct DeliveryZone {
+        pub id: uuid::Uuid,
+        pub area: DeliveryArea,
+        pub fee_schedule: DeliveryFeeSchedule,
+        #[builder(default)]
+        pub minimum_order_amount: Money,
+    }
+
+    impl DeliveryZone {
+        pub fn distance_km(&self, address: &StreetAddress) -> Option<f64> {
+            match (&self.area, &address.coordinates) {
+                (DeliveryArea::Radius { center, .. }, Some(coordinates)) => {
+                    Some(center.distance_km(coordinates))
+                }
+                _ => None,
+            }
+        }
+
+        pub fn covers(&self, address: &StreetAddress) -> bool {
+            match &self.area {
+                DeliveryArea::PostalCodes(postal_codes) => {
+                    postal_codes.contains(address.postal_code.trim())
+                }
+                DeliveryArea::Radius { radius_km, .. } => self
+                    .distance_km(address)
+                    .is_some_and(|distance_km| distance_km <= *radius_km),
+            }
+        }
+
+        pub fn delivery_fee(&self, address: &StreetAddress, order_price: &Money) -> Money {
+            if let Some(free_delivery_threshold) = &self.fee_schedule.free_delivery_threshold {
+                if order_price >= free_delivery_threshold {
+                    return Money::default();
+                }
+            }
+            let distance_km = self.distance_km(address).unwrap_or(0.0);
+            self.fee_schedule.base_fee.clone() + self.fee_schedule.fee_per_km.multiply(distance_km)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use common::error::OrderDomainError;
+        use common::value_object::money::Money;
+
+        use super::{DeliveryArea, DeliveryFeeSchedule, DeliveryZone};
+        use crate::fixtures;
+        use crate::value_object::Coordinates;
+
+        fn radius_zone(radius_km: f64) -> DeliveryZone {
+            DeliveryZone {
+                id: fixtures::id(50),
+                area: DeliveryArea::Radius {
+                    center: Coordinates {
+                        latitude: 52.3676,
+                        longitude: 4.9041,
+                    },
+                    radius_km,
+                },
+                fee_schedule: DeliveryFeeSchedule {
+                    base_fee: Money::new(200.0),
+                    fee_per_km: Money::new(100.0),
+                    free_delivery_threshold: Some(Money::new(5000.0)),
+                },
+                minimum_order_amount: Money::default(),
+            }
+        }
+
+        #[test]
+        fn postal_code_zone_covers_listed_postal_codes() {
+            let zone = fixtures::delivery_zone("1011AB", 250.0);
+            assert!(zone.covers(&fixtures::street_address(" 1011AB ", "Amsterdam")));
+            assert!(!zone.covers(&fixtures::street_address("1012CD", "Amsterdam")));
+        }
+
+        #[test]
+        fn radius_zone_charges_per_km_below_the_free_delivery_threshold() {
+            let zone = radius_zone(5.0);
+            let mut address = fixtures::street_address("1011AB", "Amsterdam");
+            address.coordinates = Some(Coordinates {
+                latitude: 52.3766,
+                longitude: 4.9041,
+            });
+            assert!(zone.covers(&address));
+            let fee = zone.delivery_fee(&address, &Money::new(1000.0));
+            assert!((fee.amount - 300.0).abs() < 1.0);
+            assert_eq!(zone.delivery_fee(&address, &Money::new(5000.0)).amount, 0.0);
+            address.coordinates = None;
+            assert!(!zone.covers(&address));
+        }
+
+        #[test]
+        fn apply_delivery_zone_rejects_restaurants_without_zones() {
+            let mut order = fixtures::order(vec![fixtures::order_item(
+                1,
+                fixtures::product(10, 1000.0),
+                1,
+            )]);
+            let mut restaurant = fixtures::restaurant(vec![]);
+            restaurant.delivery_zones = vec![];
+            assert!(matches!(
+                order.apply_delivery_zone(&restaurant),
+                Err(OrderDomainError::UndeliverableAddress)
+            ));
+        }
+
+        #[test]
+        fn apply_delivery_zone_uses_the_covering_zone() {
+            let mut order = fixtures::order(vec![fixtures::order_item(
+                1,
+                fixtures::product(10, 1000.0),
+                1,
+            )]);
+            let mut restaurant = fixtures::restaurant(vec![]);
+            restaurant.delivery_zones = vec![
+                fixtures::delivery_zone("2000AA", 100.0),
+                fixtures::delivery_zone("1011AB", 250.0),
+            ];
+            order.apply_delivery_zone(&restaurant).unwrap();
+            assert_eq!(order.delivery_fee.amount, 250.0);
+            order.street_address = fixtures::street_address("3000AA", "Rotterdam");
+            assert!(matches!(
+                order.apply_delivery_zone(&restaurant),
+                Err(OrderDomainError::UndeliverableAddress)
+            ));
+        }
+
+        #[test]
+        fn apply_delivery_zone_enforces_the_zone_minimum() {
+            let mut order = fixtures::order(vec![fixtures::order_item(
+                1,
+                fixtures::product(10, 1000.0),
+                1,
+            )]);
+            let mut zone = fixtures::delivery_zone("1011AB", 250.0);
+            zone.minimum_order_amount = Money::new(1500.0);
+            let mut restaurant = fixtures::restaurant(vec![]);
+            restaurant.delivery_zones = vec![zone];
+            assert!(matches!(
+                order.apply_delivery_zone(&restaurant),
+                Err(OrderDomainError::DeliveryZoneMinimumNotMet)
+            ));
+        }
+    }
+}
+
+pub mod eta {
+    use chrono::{DateTime, Duration, Utc};
+    use common::value_object::OrderStatus;
+    use derive_builder::Builder;
+
+    use crate::entity::{Order, Restaurant};
+
+    pub trait EtaEstimator: Send + Sync {
+        fn estimate_delivery(
+            &self,
+            order: &Order,
+            restaurant: &Restaurant,
+            open_orders: u64,
+            now: DateTime<Utc>,
+        ) -> Option<DateTime<Utc>>;
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct HeuristicEtaEstimator {
+        pub default_prep_time: Duration,
+        pub approval_wait: Duration,
+        pub delay_per_open_order: Duration,
+        pub courier_speed_kmh: f64,
+        pub handoff_time: Duration,
+    }
+
+    impl Default for HeuristicEtaEstimator {
+        fn default() -> Self {
+            Self {
+                default_prep_time: Duration::minutes(15),
+                approval_wait: Duration::minutes(5),
+                delay_per_open_order: Duration::minutes(2),
+                courier_speed_kmh: 20.0,
+                handoff_time: Duration::minutes(5),
+            }
+        }
+    }
+
+    impl HeuristicEtaEstimator {
+        pub fn prep_time(&self, order: &Order, restaurant: &Restaurant) -> Duration {
+            order
+                .items
+                .iter()
+                .map(|item| {
+                    restaurant
+                        .products
+                        .iter()
+                        .find(|product| product.has_same_id(&item.product))
+                        .and_then(|product| product.prep_time)
+                        .or(item.product.prep_time)
+                        .unwrap_or(self.default_prep_time)
+                })
+                .max()
+                .unwrap_or(self.default_prep_time)
+        }
+
+        pub fn kitchen_delay(&self, open_orders: u64) -> Duration {
+            self.delay_per_open_order * open_orders.min(i32::MAX as u64) as i32
+        }
+
+        pub fn travel_time(&self, order: &Order, restaurant: &Restaurant) -> Option<Duration> {
+            let distance_km = restaurant
+                .find_delivery_zone(&order.street_address)?
+                .distance_km(&order.street_address)
+                .unwrap_or(0.0);
+            let travel_seconds = if self.courier_speed_kmh > 0.0 {
+                (distance_km / self.courier_speed_kmh * 3600.0).round() as i64
+            } else {
+                0
+            };
+            Some(Duration::seconds(travel_seconds) + self.handoff_time)
+        }
+    }
+
+    impl EtaEstimator for HeuristicEtaEstimator {
+        fn estimate_delivery(
+            &self,
+            order: &Order,
+            restaurant: &Restaurant,
+            open_orders: u64,
+            now: DateTime<Utc>,
+        ) -> Option<DateTime<Utc>> {
+            let prep_time = self.prep_time(order, restaurant);
+            let ready_at = match order.order_status {
+                OrderStatus::Scheduled => return order.scheduled_for,
+                OrderStatus::Cancelling | OrderStatus::Cancelled => return None,
+                OrderStatus::Pending | OrderStatus::Paid => {
+                    now + self.approval_wait + self.kitchen_delay(open_orders) + prep_time
+                }
+                OrderStatus::Approved => {
+                    let approved_at = order.status_changed_at.unwrap_or(now);
+                    (approved_at + self.kitchen_delay(open_orders) + prep_time).max(now)
+                }
+            };
+            let estimated_delivery_at = ready_at + self.travel_time(order, restaurant)?;
+            match order.scheduled_for {
+                Some(scheduled_for) => Some(scheduled_for.max(estimated_delivery_at)),
+                None => Some(estimated_delivery_at),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use chrono::Duration;
+
+        use super::{EtaEstimator, HeuristicEtaEstimator};
+        use crate::fixtures;
+
+        #[test]
+        fn pending_orders_queue_behind_the_kitchen_orders() {
+            let product = fixtures::product(10, 1000.0);
+            let order = fixtures::order(vec![fixtures::order_item(1, product.clone(), 1)]);
+            let restaurant = fixtures::restaurant(vec![product]);
+            assert!(
+                HeuristicEtaEstimator::default().estimate_delivery(
+                    &order,
+                    &restaurant,
+                    3,
+                    fixtures::now()
+                ) == Some(fixtures::now() + Duration::minutes(5 + 3 * 2 + 15 + 5))
+            );
+        }
+
+        #[test]
+        fn orders_outside_every_delivery_zone_get_no_estimate() {
+            let product = fixtures::product(10, 1000.0);
+            let order = fixtures::order(vec![fixtures::order_item(1, product.clone(), 1)]);
+            let mut restaurant = fixtures::restaurant(vec![product]);
+            restaurant.delivery_zones = vec![];
+            assert!(HeuristicEtaEstimator::default()
+                .estimate_delivery(&order, &restaurant, 0, fixtures::now())
+                .is_none());
+        }
+
+        #[test]
+        fn cancelling_an_order_clears_its_delivery_estimate() {
+            let mut order = fixtures::order(vec![fixtures::order_item(
+                1,
+                fixtures::product(10, 1000.0),
+                1,
+            )]);
+            order.estimated_delivery_at = Some(fixtures::now());
+            order.cancel(vec![]).unwrap();
+            assert!(order.estimated_delivery_at.is_none());
+        }
+    }
+}
+
+pub mod opening_hours {
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc, Weekday};
+    use chrono_tz::Tz;
+    use derive_builder::Builder;
+
+    #[derive(Clone, PartialEq)]
+    pub enum RestaurantAvailability {
+        Open,
+        Closed,
+        Paused(String),
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OpeningPeriod {
+        pub opens_at: NaiveTime,
+        pub closes_at: NaiveTime,
+    }
+
+    impl OpeningPeriod {
+        pub fn is_overnight(&self) -> bool {
+            self.closes_at <= self.opens_at
+        }
+
+        fn contains_same_day(&self, time: NaiveTime) -> bool {
+            if self.is_overnight() {
+                return time >= self.opens_at;
+            }
+            time >= self.opens_at && time < self.closes_at
+        }
+
+        fn contains_next_day(&self, time: NaiveTime) -> bool {
+            self.is_overnight() && time < self.closes_at
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct WeeklyOpeningPeriod {
+        pub weekday: Weekday,
+        pub period: OpeningPeriod,
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OpeningException {
+        pub date: NaiveDate,
+        #[builder(default)]
+        pub periods: Vec<OpeningPeriod>,
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct Pause {
+        pub starts_at: DateTime<Utc>,
+        pub ends_at: DateTime<Utc>,
+        pub reason: String,
+    }
+
+    impl Pause {
+        pub fn is_active_at(&self, at: DateTime<Utc>) -> bool {
+            at >= self.starts_at && at < self.ends_at
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OpeningHours {
+        pub time_zone: Tz,
+        pub weekly_periods: Vec<WeeklyOpeningPeriod>,
+        #[builder(default)]
+        pub exceptions: Vec<OpeningException>,
+        #[builder(default)]
+        pub pauses: Vec<Pause>,
+    }
+
+    impl OpeningHours {
+        pub fn periods_on(&self, date: NaiveDate) -> Vec<OpeningPeriod> {
+            if let Some(exception) = self.exceptions.iter().find(|e| e.date == date) {
+                return exception.periods.clone();
+            }
+            self.weekly_periods
+                .iter()
+                .filter(|weekly_period| weekly_period.weekday == date.weekday())
+                .map(|weekly_period| weekly_period.period.clone())
+                .collect()
+        }
+
+        pub fn is_open_at(&self, at: DateTime<Utc>) -> bool {
+            let local = at.with_timezone(&self.time_zone).naive_local();
+            let (date, time) = (local.date(), local.time());
+            let open_today = self
+                .periods_on(date)
+                .iter()
+                .any(|period| period.contains_same_day(time));
+            let open_since_yesterday = date.pred_opt().is_some_and(|yesterday| {
+                self.periods_on(yesterday)
+                    .iter()
+                    .any(|period| period.contains_next_day(time))
+            });
+            open_today || open_since_yesterday
+        }
+
+        pub fn active_pause_at(&self, at: DateTime<Utc>) -> Option<&Pause> {
+            self.pauses.iter().find(|pause| pause.is_active_at(at))
+        }
+
+        pub fn availability_at(&self, at: DateTime<Utc>) -> RestaurantAvailability {
+            if let Some(pause) = self.active_pause_at(at) {
+                return RestaurantAvailability::Paused(pause.reason.clone());
+            }
+            if self.is_open_at(at) {
+                return RestaurantAvailability::Open;
+            }
+            RestaurantAvailability::Closed
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+
+        use super::{
+            OpeningException, OpeningHours, OpeningPeriod, Pause, RestaurantAvailability,
+            WeeklyOpeningPeriod,
+        };
+        use crate::fixtures;
+
+        fn period(opens_at: (u32, u32), closes_at: (u32, u32)) -> OpeningPeriod {
+            OpeningPeriod {
+                opens_at: NaiveTime::from_hms_opt(opens_at.0, opens_at.1, 0).unwrap(),
+                closes_at: NaiveTime::from_hms_opt(closes_at.0, closes_at.1, 0).unwrap(),
+            }
+        }
+
+        fn opening_hours() -> OpeningHours {
+            OpeningHours {
+                time_zone: chrono_tz::Europe::Amsterdam,
+                weekly_periods: vec![
+                    WeeklyOpeningPeriod {
+                        weekday: Weekday::Mon,
+                        period: period((11, 0), (14, 0)),
+                    },
+                    WeeklyOpeningPeriod {
+                        weekday: Weekday::Fri,
+                        period: period((18, 0), (2, 0)),
+                    },
+                ],
+                exceptions: vec![OpeningException {
+                    date: NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(),
+                    periods: vec![],
+                }],
+                pauses: vec![],
+            }
+        }
+
+        #[test]
+        fn is_open_at_uses_the_restaurant_time_zone() {
+            let opening_hours = opening_hours();
+            let monday_noon_local = Utc.with_ymd_and_hms(2026, 3, 2, 11, 0, 0).unwrap();
+            let monday_evening_local = Utc.with_ymd_and_hms(2026, 3, 2, 13, 30, 0).unwrap();
+            assert!(opening_hours.is_open_at(monday_noon_local));
+            assert!(!opening_hours.is_open_at(monday_evening_local));
+        }
+
+        #[test]
+        fn is_open_at_covers_overnight_periods() {
+            let opening_hours = opening_hours();
+            let saturday_one_am_local = Utc.with_ymd_and_hms(2026, 3, 7, 0, 0, 0).unwrap();
+            let saturday_three_am_local = Utc.with_ymd_and_hms(2026, 3, 7, 2, 0, 0).unwrap();
+            assert!(opening_hours.is_open_at(saturday_one_am_local));
+            assert!(!opening_hours.is_open_at(saturday_three_am_local));
+        }
+
+        #[test]
+        fn exceptions_replace_the_weekly_periods() {
+            let opening_hours = opening_hours();
+            let holiday_noon_local = Utc.with_ymd_and_hms(2026, 3, 9, 11, 0, 0).unwrap();
+            assert!(!opening_hours.is_open_at(holiday_noon_local));
+        }
+
+        #[test]
+        fn active_pauses_take_precedence_over_opening_hours() {
+            let mut opening_hours = opening_hours();
+            let monday_noon_local = Utc.with_ymd_and_hms(2026, 3, 2, 11, 0, 0).unwrap();
+            opening_hours.pauses = vec![Pause {
+                starts_at: monday_noon_local - Duration::minutes(10),
+                ends_at: monday_noon_local + Duration::minutes(10),
+                reason: String::from("kitchen busy"),
+            }];
+            assert!(
+                opening_hours.availability_at(monday_noon_local)
+                    == RestaurantAvailability::Paused(String::from("kitchen busy"))
+            );
+            assert!(
+                opening_hours.availability_at(monday_noon_local + Duration::minutes(10))
+                    == RestaurantAvailability::Open
+            );
+        }
+
+        #[test]
+        fn restaurants_without_opening_hours_are_open() {
+            let mut restaurant = fixtures::restaurant(vec![]);
+            restaurant.opening_hours = None;
+            assert!(restaurant.availability_at(fixtures::now()) == RestaurantAvailability::Open);
+            assert!(restaurant.validate_open_at(fixtures::now()).is_ok());
+        }
+    }
+}
+
+pub mod scheduling {
+    use chrono::{DateTime, Duration, Utc};
+    use common::error::OrderDomainError;
+    use derive_builder::Builder;
+
+    use crate::entity::Restaurant;
+
+    #[derive(Clone, Builder)]
+    pub struct SchedulingPolicy {
+        pub minimum_lead_time: Duration,
+        pub maximum_advance: Duration,
+        pub release_lead_time: Duration,
+    }
+
+    impl Default for SchedulingPolicy {
+        fn default() -> Self {
+            Self {
+                minimum_lead_time: Duration::minutes(60),
+                maximum_advance: Duration::days(7),
+                release_lead_time: Duration::minutes(45),
+            }
+        }
+    }
+
+    impl SchedulingPolicy {
+        pub fn validate_slot(
+            &self,
+            restaurant: &Restaurant,
+            requested_delivery_time: DateTime<Utc>,
+            now: DateTime<Utc>,
+        ) -> Result<(), OrderDomainError> {
+            if requested_delivery_time < now + self.minimum_lead_time {
+                return Err(OrderDomainError::InvalidScheduledTime(format!(
+                    "must be at least {} minutes from now",
+                    self.minimum_lead_time.num_minutes()
+                )));
+            }
+            if requested_delivery_time > now + self.maximum_advance {
+                return Err(OrderDomainError::InvalidScheduledTime(format!(
+                    "must be at most {} days from now",
+                    self.maximum_advance.num_days()
+                )));
+            }
+            restaurant
+                .validate_open_at(requested_delivery_time)
+                .map_err(|e| OrderDomainError::InvalidScheduledTime(e.to_string()))
+        }
+
+        pub fn release_cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+            now + self.release_lead_time
+        }
+    }
+}
+
+pub mod tax {
+    use common::value_object::{money::Money, TaxCategory};
+    use derive_builder::Builder;
+
+    use crate::{entity::OrderItem, value_object::StreetAddress};
+
+    pub trait TaxCalculator: Send + Sync {
+        fn calculate_item_tax(&self, address: &StreetAddress, item: &OrderItem) -> Money;
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct TaxRule {
+        #[builder(default)]
+        pub city: Option<String>,
+        #[builder(default)]
+        pub postal_code_prefix: Option<String>,
+        pub tax_category: TaxCategory,
+        pub rate: f64,
+    }
+
+    impl TaxRule {
+        pub fn matches(&self, address: &StreetAddress, tax_category: &TaxCategory) -> bool {
+            if self.tax_category != *tax_category {
+                return false;
+            }
+            if let Some(city) = &self.city {
+                if !city.eq_ignore_ascii_case(&address.city) {
+                    return false;
+                }
+            }
+            if let Some(postal_code_prefix) = &self.postal_code_prefix {
+                if !address.postal_code.starts_with(postal_code_prefix.as_str()) {
+                    return false;
+                }
+            }
+            true
+        }
+
+        fn specificity(&self) -> usize {
+            match (&self.city, &self.postal_code_prefix) {
+                (_, Some(postal_code_prefix)) => 2 + postal_code_prefix.len(),
+                (Some(_), None) => 1,
+                (None, None) => 0,
+            }
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct TableTaxCalculator {
+        pub rules: Vec<TaxRule>,
+        #[builder(default)]
+        pub default_rate: f64,
+    }
+
+    impl TableTaxCalculator {
+        pub fn rate_for(&self, address: &StreetAddress, tax_category: &TaxCategory) -> f64 {
+            self.rules
+                .iter()
+                .filter(|rule| rule.matches(address, tax_category))
+                .max_by_key(|rule| rule.specificity())
+                .map(|rule| rule.rate)
+                .unwrap_or(self.default_rate)
+        }
+    }
+
+    impl TaxCalculator for TableTaxCalculator {
+        fn calculate_item_tax(&self, address: &StreetAddress, item: &OrderItem) -> Money {
+            let rate = self.rate_for(address, &item.product.tax_category);
+            item.sub_total.multiply(rate)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use common::value_object::TaxCategory;
+
+        use super::{TableTaxCalculator, TaxRule};
+        use crate::fixtures;
+
+        fn rule(city: Option<&str>, postal_code_prefix: Option<&str>, rate: f64) -> TaxRule {
+            TaxRule {
+                city: city.map(String::from),
+                postal_code_prefix: postal_code_prefix.map(String::from),
+                tax_category: TaxCategory::Standard,
+                rate,
+            }
+        }
+
+        fn calculator() -> TableTaxCalculator {
+            TableTaxCalculator {
+                rules: vec![
+                    rule(None, None, 0.21),
+                    rule(Some("Amsterdam"), None, 0.19),
+                    rule(Some("Amsterdam"), Some("10"), 0.15),
+                    rule(None, Some("1011"), 0.09),
+                ],
+                default_rate: 0.05,
+            }
+        }
+
+        #[test]
+        fn rate_for_prefers_the_most_specific_rule() {
+            let calculator = calculator();
+            let standard = TaxCategory::Standard;
+            assert_eq!(
+                calculator.rate_for(&fixtures::street_address("1011AB", "Amsterdam"), &standard),
+                0.09
+            );
+            assert_eq!(
+                calculator.rate_for(&fixtures::street_address("1020AB", "amsterdam"), &standard),
+                0.15
+            );
+            assert_eq!(
+                calculator.rate_for(&fixtures::street_address("2000AB", "Amsterdam"), &standard),
+                0.19
+            );
+            assert_eq!(
+                calculator.rate_for(&fixtures::street_address("3000AB", "Utrecht"), &standard),
+                0.21
+            );
+        }
+
+        #[test]
+        fn rate_for_falls_back_to_the_default_rate() {
+            let calculator = calculator();
+            assert_eq!(
+                calculator.rate_for(
+                    &fixtures::street_address("1011AB", "Amsterdam"),
+                    &TaxCategory::Alcohol
+                ),
+                0.05
+            );
+        }
+
+        #[test]
+        fn apply_taxes_rounds_each_line_and_sums_to_the_order_tax() {
+            let mut order = fixtures::order(vec![
+                fixtures::order_item(1, fixtures::product(10, 333.0), 1),
+                fixtures::order_item(2, fixtures::product(11, 333.0), 1),
+                fixtures::order_item(3, fixtures::product(12, 333.0), 1),
+            ]);
+            order.apply_taxes(&calculator());
+            for item in order.items.iter() {
+                assert_eq!(item.tax.amount, 30.0);
+            }
+            assert_eq!(order.tax.amount, 90.0);
+            let stored_line_taxes: i64 = order
+                .items
+                .iter()
+                .map(|item| i64::from(item.tax.clone()))
+                .sum();
+            assert_eq!(stored_line_taxes, i64::from(order.tax.clone()));
+        }
+    }
+}
+
+pub mod dietary {
+    use std::{fmt::Display, str::FromStr};
+
+    use derive_builder::Builder;
+
+    use crate::entity::Product;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Allergen {
+        Gluten,
+        Crustaceans,
+        Eggs,
+        Fish,
+        Peanuts,
+        Soybeans,
+        Milk,
+        TreeNuts,
+        Celery,
+        Mustard,
+        Sesame,
+        Sulphites,
+        Lupin,
+        Molluscs,
+    }
+
+    impl FromStr for Allergen {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Gluten" => Ok(Allergen::Gluten),
+                "Crustaceans" => Ok(Allergen::Crustaceans),
+                "Eggs" => Ok(Allergen::Eggs),
+                "Fish" => Ok(Allergen::Fish),
+                "Peanuts" => Ok(Allergen::Peanuts),
+                "Soybeans" => Ok(Allergen::Soybeans),
+                "Milk" => Ok(Allergen::Milk),
+                "TreeNuts" => Ok(Allergen::TreeNuts),
+                "Celery" => Ok(Allergen::Celery),
+                "Mustard" => Ok(Allergen::Mustard),
+                "Sesame" => Ok(Allergen::Sesame),
+                "Sulphites" => Ok(Allergen::Sulphites),
+                "Lupin" => Ok(Allergen::Lupin),
+                "Molluscs" => Ok(Allergen::Molluscs),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for Allergen {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Allergen::Gluten => write!(f, "Gluten"),
+                Allergen::Crustaceans => write!(f, "Crustaceans"),
+                Allergen::Eggs => write!(f, "Eggs"),
+                Allergen::Fish => write!(f, "Fish"),
+                Allergen::Peanuts => write!(f, "Peanuts"),
+                Allergen::Soybeans => write!(f, "Soybeans"),
+                Allergen::Milk => write!(f, "Milk"),
+                Allergen::TreeNuts => write!(f, "TreeNuts"),
+                Allergen::Celery => write!(f, "Celery"),
+                Allergen::Mustard => write!(f, "Mustard"),
+                Allergen::Sesame => write!(f, "Sesame"),
+                Allergen::Sulphites => write!(f, "Sulphites"),
+                Allergen::Lupin => write!(f, "Lupin"),
+                Allergen::Molluscs => write!(f, "Molluscs"),
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum DietaryTag {
+        Vegetarian,
+        Vegan,
+        GlutenFree,
+        LactoseFree,
+        Halal,
+        Kosher,
+    }
+
+    impl FromStr for DietaryTag {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Vegetarian" => Ok(DietaryTag::Vegetarian),
+                "Vegan" => Ok(DietaryTag::Vegan),
+                "GlutenFree" => Ok(DietaryTag::GlutenFree),
+                "LactoseFree" => Ok(DietaryTag::LactoseFree),
+                "Halal" => Ok(DietaryTag::Halal),
+                "Kosher" => Ok(DietaryTag::Kosher),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for DietaryTag {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                DietaryTag::Vegetarian => write!(f, "Vegetarian"),
+                DietaryTag::Vegan => write!(f, "Vegan"),
+                DietaryTag::GlutenFree => write!(f, "GlutenFree"),
+                DietaryTag::LactoseFree => write!(f, "LactoseFree"),
+                DietaryTag::Halal => write!(f, "Halal"),
+                DietaryTag::Kosher => write!(f, "Kosher"),
+            }
+        }
+    }
+
+    #[derive(Clone, Default, PartialEq)]
+    pub enum DietaryConflictPolicy {
+        #[default]
+        Warn,
+        Reject,
+    }
+
+    impl FromStr for DietaryConflictPolicy {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Warn" => Ok(DietaryConflictPolicy::Warn),
+                "Reject" => Ok(DietaryConflictPolicy::Reject),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for DietaryConflictPolicy {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                DietaryConflictPolicy::Warn => write!(f, "Warn"),
+                DietaryConflictPolicy::Reject => write!(f, "Reject"),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum DietaryConflictReason {
+        ContainsAllergen(Allergen),
+        MissingTag(DietaryTag),
+    }
+
+    impl DietaryConflictReason {
+        pub fn parse(kind: &str, value: &str) -> Option<Self> {
+            match kind {
+                "ContainsAllergen" => value
+                    .parse()
+                    .ok()
+                    .map(DietaryConflictReason::ContainsAllergen),
+                "MissingTag" => value.parse().ok().map(DietaryConflictReason::MissingTag),
+                _ => None,
+            }
+        }
+
+        pub fn kind(&self) -> String {
+            match self {
+                DietaryConflictReason::ContainsAllergen(_) => String::from("ContainsAllergen"),
+                DietaryConflictReason::MissingTag(_) => String::from("MissingTag"),
+            }
+        }
+
+        pub fn value(&self) -> String {
+            match self {
+                DietaryConflictReason::ContainsAllergen(allergen) => allergen.to_string(),
+                DietaryConflictReason::MissingTag(tag) => tag.to_string(),
+            }
+        }
+    }
+
+    impl Display for DietaryConflictReason {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                DietaryConflictReason::ContainsAllergen(allergen) => {
+                    write!(f, "contains {}", allergen)
+                }
+                DietaryConflictReason::MissingTag(tag) => write!(f, "is not {}", tag),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Builder)]
+    pub struct DietaryConflict {
+        pub product_id: uuid::Uuid,
+        pub product_name: String,
+        pub reason: DietaryConflictReason,
+    }
+
+    impl Display for DietaryConflict {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} {}", self.product_name, self.reason)
+        }
+    }
+
+    #[derive(Clone, Default, Builder)]
+    pub struct DietaryPreferences {
+        #[builder(default)]
+        pub allergies: Vec<Allergen>,
+        #[builder(default)]
+        pub requirements: Vec<DietaryTag>,
+        #[builder(default)]
+        pub conflict_policy: DietaryConflictPolicy,
+    }
+
+    impl DietaryPreferences {
+        pub fn is_empty(&self) -> bool {
+            self.allergies.is_empty() && self.requirements.is_empty()
+        }
+
+        pub fn rejects_conflicts(&self) -> bool {
+            self.conflict_policy == DietaryConflictPolicy::Reject
+        }
+
+        pub fn combine(preferences: Vec<DietaryPreferences>) -> Option<DietaryPreferences> {
+            let mut combined = DietaryPreferences::default();
+            for preference in preferences {
+                for allergen in preference.allergies {
+                    if !combined.allergies.contains(&allergen) {
+                        combined.allergies.push(allergen);
+                    }
+                }
+                for tag in preference.requirements {
+                    if !combined.requirements.contains(&tag) {
+                        combined.requirements.push(tag);
+                    }
+                }
+                if preference.conflict_policy == DietaryConflictPolicy::Reject {
+                    combined.conflict_policy = DietaryConflictPolicy::Reject;
+                }
+            }
+            if combined.is_empty() {
+                return None;
+            }
+            Some(combined)
+        }
+
+        pub fn conflicts_for(&self, product: &Product) -> Vec<DietaryConflict> {
+            let allergen_reasons = self
+                .allergies
+                .iter()
+                .filter(|allergen| product.allergens.contains(allergen))
+                .map(|allergen| DietaryConflictReason::ContainsAllergen(*allergen));
+            let tag_reasons = self
+                .requirements
+                .iter()
+                .filter(|tag| !product.dietary_tags.contains(tag))
+                .map(|tag| DietaryConflictReason::MissingTag(*tag));
+            allergen_reasons
+                .chain(tag_reasons)
+                .map(|reason| DietaryConflict {
+                    product_id: product.clone().into(),
+                    product_name: product.name.clone(),
+                    reason,
+                })
+                .collect()
+        }
+    }
+}
+
+pub mod risk {
+    use std::{fmt::Display, str::FromStr};
+
+    use chrono::{DateTime, Duration, Utc};
+    use common::value_object::money::Money;
+    use derive_builder::Builder;
+
+    use crate::entity::Order;
+    use crate::value_object::StreetAddress;
+
+    #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum RiskDecision {
+        #[default]
+        Allow,
+        Review,
+        Deny,
+    }
+
+    impl FromStr for RiskDecision {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Allow" => Ok(RiskDecision::Allow),
+                "Review" => Ok(RiskDecision::Review),
+                "Deny" => Ok(RiskDecision::Deny),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for RiskDecision {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                RiskDecision::Allow => write!(f, "Allow"),
+                RiskDecision::Review => write!(f, "Review"),
+                RiskDecision::Deny => write!(f, "Deny"),
+            }
+        }
+    }
+
+    pub fn address_key(address: &StreetAddress) -> String {
+        format!(
+            "{}|{}",
+            address.street.trim().to_lowercase(),
+            address.postal_code.trim().to_lowercase()
+        )
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct BlockedAddress {
+        #[builder(default)]
+        pub street: Option<String>,
+        pub postal_code: String,
+    }
+
+    impl BlockedAddress {
+        pub fn matches(&self, address: &StreetAddress) -> bool {
+            let postal_code_matches =
+                self.postal_code.trim().to_lowercase() == address.postal_code.trim().to_lowercase();
+            match &self.street {
+                Some(street) => {
+                    postal_code_matches
+                        && street.trim().to_lowercase() == address.street.trim().to_lowercase()
+                }
+                None => postal_code_matches,
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub enum RiskRule {
+        CustomerVelocity {
+            max_orders: u64,
+            window: Duration,
+            decision: RiskDecision,
+        },
+        AddressVelocity {
+            max_orders: u64,
+            window: Duration,
+            decision: RiskDecision,
+        },
+        HighTotal {
+            threshold: Money,
+            decision: RiskDecision,
+        },
+        NewCustomerLargeOrder {
+            max_previous_orders: u64,
+            threshold: Money,
+            decision: RiskDecision,
+        },
+        BlockedAddress {
+            addresses: Vec<BlockedAddress>,
+            decision: RiskDecision,
+        },
+    }
+
+    #[derive(Clone, Default, Builder)]
+    pub struct RiskSignals {
+        #[builder(default)]
+        pub customer_order_times: Vec<DateTime<Utc>>,
+        #[builder(default)]
+        pub address_order_times: Vec<DateTime<Utc>>,
+        #[builder(default)]
+        pub customer_previous_orders: u64,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct RiskRuleOutcome {
+        pub rule: String,
+        pub decision: RiskDecision,
+        pub reasons: Vec<String>,
+    }
+
+    #[derive(Clone, Debug, Default)]
+    pub struct RiskAssessment {
+        pub decision: RiskDecision,
+        pub outcomes: Vec<RiskRuleOutcome>,
+    }
+
+    impl RiskAssessment {
+        pub fn reasons(&self) -> Vec<String> {
+            self.outcomes
+                .iter()
+                .filter(|outcome| outcome.decision != RiskDecision::Allow)
+                .flat_map(|outcome| {
+                    outcome
+                        .reasons
+                        .iter()
+                        .map(|reason| format!("{}: {}", outcome.rule, reason))
+                })
+                .collect()
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct RiskCheck {
+        pub id: uuid::Uuid,
+        pub order_id: uuid::Uuid,
+        pub customer_id: uuid::Uuid,
+        pub address_key: String,
+        pub assessment: RiskAssessment,
+        pub checked_at: DateTime<Utc>,
+    }
+
+    fn count_within(times: &[DateTime<Utc>], window: Duration, now: DateTime<Utc>) -> u64 {
+        times
+            .iter()
+            .filter(|time| **time > now - window && **time <= now)
+            .count() as u64
+    }
+
+    impl RiskRule {
+        pub fn name(&self) -> &'static str {
+            match self {
+                RiskRule::CustomerVelocity { .. } => "CustomerVelocity",
+                RiskRule::AddressVelocity { .. } => "AddressVelocity",
+                RiskRule::HighTotal { .. } => "HighTotal",
+                RiskRule::NewCustomerLargeOrder { .. } => "NewCustomerLargeOrder",
+                RiskRule::BlockedAddress { .. } => "BlockedAddress",
+            }
+        }
+
+        pub fn window(&self) -> Duration {
+            match self {
+                RiskRule::CustomerVelocity { window, .. }
+                | RiskRule::AddressVelocity { window, .. } => *window,
+                _ => Duration::zero(),
+            }
+        }
+
+        pub fn evaluate(
+            &self,
+            order: &Order,
+            signals: &RiskSignals,
+            now: DateTime<Utc>,
+        ) -> Option<RiskRuleOutcome> {
+            let (decision, reason) = match self {
+                RiskRule::CustomerVelocity {
+                    max_orders,
+                    window,
+                    decision,
+                } => {
+                    let orders = count_within(&signals.customer_order_times, *window, now);
+                    if orders < *max_orders {
+                        return None;
+                    }
+                    (
+                        decision,
+                        format!(
+                            "customer placed {} orders in the last {} minutes (limit {})",
+                            orders,
+                            window.num_minutes(),
+                            max_orders
+                        ),
+                    )
+                }
+                RiskRule::AddressVelocity {
+                    max_orders,
+                    window,
+                    decision,
+                } => {
+                    let orders = count_within(&signals.address_order_times, *window, now);
+                    if orders < *max_orders {
+                        return None;
+                    }
+                    (
+                        decision,
+                        format!(
+                            "address received {} orders in the last {} minutes (limit {})",
+                            orders,
+                            window.num_minutes(),
+                            max_orders
+                        ),
+                    )
+                }
+                RiskRule::HighTotal {
+                    threshold,
+                    decision,
+                } => {
+                    let total_price = order.total_price();
+                    if total_price <= *threshold {
+                        return None;
+                    }
+                    (
+                        decision,
+                        format!(
+                            "order total {} is above {}",
+                            total_price.amount, threshold.amount
+                        ),
+                    )
+                }
+                RiskRule::NewCustomerLargeOrder {
+                    max_previous_orders,
+                    threshold,
+                    decision,
+                } => {
+                    let total_price = order.total_price();
+                    if signals.customer_previous_orders > *max_previous_orders
+                        || total_price <= *threshold
+                    {
+                        return None;
+                    }
+                    (
+                        decision,
+                        format!(
+                            "customer with {} previous orders placed an order of {} (limit {})",
+                            signals.customer_previous_orders, total_price.amount, threshold.amount
+                        ),
+                    )
+                }
+                RiskRule::BlockedAddress {
+                    addresses,
+                    decision,
+                } => {
+                    if !addresses
+                        .iter()
+                        .any(|address| address.matches(&order.street_address))
+                    {
+                        return None;
+                    }
+                    (
+                        decision,
+                        format!(
+                            "address {}, {} is blocked",
+                            order.street_address.street, order.street_address.postal_code
+                        ),
+                    )
+                }
+            };
+            Some(RiskRuleOutcome {
+                rule: self.name().to_string(),
+                decision: decision.clone(),
+                reasons: vec![reason],
+            })
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct RiskRulesEngine {
+        pub rules: Vec<RiskRule>,
+    }
+
+    impl Default for RiskRulesEngine {
+        fn default() -> Self {
+            Self {
+                rules: vec![
+                    RiskRule::CustomerVelocity {
+                        max_orders: 5,
+                        window: Duration::hours(1),
+                        decision: RiskDecision::Review,
+                    },
+                    RiskRule::AddressVelocity {
+                        max_orders: 10,
+                        window: Duration::hours(1),
+                        decision: RiskDecision::Review,
+                    },
+                    RiskRule::HighTotal {
+                        threshold: Money::new(50000.0),
+                        decision: RiskDecision::Review,
+                    },
+                    RiskRule::NewCustomerLargeOrder {
+                        max_previous_orders: 0,
+                        threshold: Money::new(15000.0),
+                        decision: RiskDecision::Review,
+                    },
+                    RiskRule::BlockedAddress {
+                        addresses: vec![],
+                        decision: RiskDecision::Deny,
+                    },
+                ],
+            }
+        }
+    }
+
+    impl RiskRulesEngine {
+        pub fn lookback(&self) -> Duration {
+            self.rules
+                .iter()
+                .map(|rule| rule.window())
+                .max()
+                .unwrap_or_else(Duration::zero)
+        }
+
+        pub fn evaluate(
+            &self,
+            order: &Order,
+            signals: &RiskSignals,
+            now: DateTime<Utc>,
+        ) -> RiskAssessment {
+            let outcomes: Vec<RiskRuleOutcome> = self
+                .rules
+                .iter()
+                .filter_map(|rule| rule.evaluate(order, signals, now))
+                .collect();
+            let decision = outcomes
+                .iter()
+                .map(|outcome| outcome.decision.clone())
+                .max()
+                .unwrap_or_default();
+            RiskAssessment { decision, outcomes }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use chrono::Duration;
+        use common::value_object::money::Money;
+
+        use super::{BlockedAddress, RiskDecision, RiskRule, RiskRulesEngine, RiskSignals};
+        use crate::entity::Order;
+        use crate::fixtures;
+
+        fn order(price: f64) -> Order {
+            fixtures::order(vec![fixtures::order_item(
+                1,
+                fixtures::product(10, price),
+                1,
+            )])
+        }
+
+        fn engine(rules: Vec<RiskRule>) -> RiskRulesEngine {
+            RiskRulesEngine { rules }
+        }
+
+        fn order_times(minutes_ago: &[i64]) -> Vec<chrono::DateTime<chrono::Utc>> {
+            minutes_ago
+                .iter()
+                .map(|minutes| fixtures::now() - Duration::minutes(*minutes))
+                .collect()
+        }
+
+        #[test]
+        fn customer_velocity_counts_only_orders_inside_the_window() {
+            let engine = engine(vec![RiskRule::CustomerVelocity {
+                max_orders: 2,
+                window: Duration::hours(1),
+                decision: RiskDecision::Review,
+            }]);
+            let signals = RiskSignals {
+                customer_order_times: order_times(&[10, 20, 90]),
+                ..Default::default()
+            };
+            let assessment = engine.evaluate(&order(10.0), &signals, fixtures::now());
+            assert_eq!(assessment.decision, RiskDecision::Review);
+            assert_eq!(
+                assessment.reasons(),
+                vec![String::from(
+                    "CustomerVelocity: customer placed 2 orders in the last 60 minutes (limit 2)"
+                )]
+            );
+            let signals = RiskSignals {
+                customer_order_times: order_times(&[10, 90]),
+                ..Default::default()
+            };
+            let assessment = engine.evaluate(&order(10.0), &signals, fixtures::now());
+            assert_eq!(assessment.decision, RiskDecision::Allow);
+        }
+
+        #[test]
+        fn address_velocity_counts_orders_to_the_address() {
+            let engine = engine(vec![RiskRule::AddressVelocity {
+                max_orders: 3,
+                window: Duration::minutes(30),
+                decision: RiskDecision::Deny,
+            }]);
+            let signals = RiskSignals {
+                address_order_times: order_times(&[1, 5, 29]),
+                customer_order_times: order_times(&[1, 5, 29, 31]),
+                ..Default::default()
+            };
+            let assessment = engine.evaluate(&order(10.0), &signals, fixtures::now());
+            assert_eq!(assessment.decision, RiskDecision::Deny);
+            assert_eq!(assessment.outcomes[0].rule, "AddressVelocity");
+            let signals = RiskSignals {
+                address_order_times: order_times(&[1, 5, 31]),
+                ..Default::default()
+            };
+            let assessment = engine.evaluate(&order(10.0), &signals, fixtures::now());
+            assert_eq!(assessment.decision, RiskDecision::Allow);
+        }
+
+        #[test]
+        fn high_total_flags_orders_above_the_threshold() {
+            let engine = engine(vec![RiskRule::HighTotal {
+                threshold: Money::new(500.0),
+                decision: RiskDecision::Review,
+            }]);
+            let signals = RiskSignals::default();
+            assert_eq!(
+                engine
+                    .evaluate(&order(500.0), &signals, fixtures::now())
+                    .decision,
+                RiskDecision::Allow
+            );
+            assert_eq!(
+                engine
+                    .evaluate(&order(500.5), &signals, fixtures::now())
+                    .decision,
+                RiskDecision::Review
+            );
+            let mut order_with_fees = order(450.0);
+            order_with_fees.delivery_fee = Money::new(100.0);
+            assert_eq!(
+                engine
+                    .evaluate(&order_with_fees, &signals, fixtures::now())
+                    .decision,
+                RiskDecision::Review
+            );
+        }
+
+        #[test]
+        fn new_customer_large_order_ignores_returning_customers() {
+            let engine = engine(vec![RiskRule::NewCustomerLargeOrder {
+                max_previous_orders: 1,
+                threshold: Money::new(150.0),
+                decision: RiskDecision::Review,
+            }]);
+            let new_customer = RiskSignals {
+                customer_previous_orders: 1,
+                ..Default::default()
+            };
+            let returning_customer = RiskSignals {
+                customer_previous_orders: 2,
+                ..Default::default()
+            };
+            assert_eq!(
+                engine
+                    .evaluate(&order(200.0), &new_customer, fixtures::now())
+                    .decision,
+                RiskDecision::Review
+            );
+            assert_eq!(
+                engine
+                    .evaluate(&order(100.0), &new_customer, fixtures::now())
+                    .decision,
+                RiskDecision::Allow
+            );
+            assert_eq!(
+                engine
+                    .evaluate(&order(200.0), &returning_customer, fixtures::now())
+                    .decision,
+                RiskDecision::Allow
+            );
+        }
+
+        #[test]
+        fn blocked_address_matches_the_postal_code_and_optional_street() {
+            let blocked = |street: Option<&str>| {
+                engine(vec![RiskRule::BlockedAddress {
+                    addresses: vec![BlockedAddress {
+                        street: street.map(String::from),
+                        postal_code: String::from(" 1011ab "),
+                    }],
+                    decision: RiskDecision::Deny,
+                }])
+            };
+            let order = order(10.0);
+            let signals = RiskSignals::default();
+            assert_eq!(
+                blocked(None)
+                    .evaluate(&order, &signals, fixtures::now())
+                    .decision,
+                RiskDecision::Deny
+            );
+            assert_eq!(
+                blocked(Some(order.street_address.street.as_str()))
+                    .evaluate(&order, &signals, fixtures::now())
+                    .decision,
+                RiskDecision::Deny
+            );
+            assert_eq!(
+                blocked(Some("another street"))
+                    .evaluate(&order, &signals, fixtures::now())
+                    .decision,
+                RiskDecision::Allow
+            );
+        }
+
+        #[test]
+        fn the_strictest_matching_rule_decides() {
+            let engine = engine(vec![
+                RiskRule::HighTotal {
+                    threshold: Money::new(100.0),
+                    decision: RiskDecision::Review,
+                },
+                RiskRule::BlockedAddress {
+                    addresses: vec![BlockedAddress {
+                        street: None,
+                        postal_code: String::from("1011AB"),
+                    }],
+                    decision: RiskDecision::Deny,
+                },
+                RiskRule::CustomerVelocity {
+                    max_orders: 1,
+                    window: Duration::hours(1),
+                    decision: RiskDecision::Allow,
+                },
+            ]);
+            let signals = RiskSignals {
+                customer_order_times: order_times(&[5]),
+                ..Default::default()
+            };
+            let assessment = engine.evaluate(&order(200.0), &signals, fixtures::now());
+            assert_eq!(assessment.decision, RiskDecision::Deny);
+            assert_eq!(assessment.outcomes.len(), 3);
+            assert_eq!(assessment.reasons().len(), 2);
+            assert!(assessment.reasons()[0].starts_with("HighTotal: "));
+            assert!(assessment.reasons()[1].starts_with("BlockedAddress: "));
+        }
+    }
+}
+
+pub mod group_order {
+    use std::{fmt::Display, str::FromStr};
+
+    use common::entity::{AggregateRootBuilder, BaseEntityBuilder};
+    use common::error::OrderDomainError;
+    use common::value_object::money::Money;
+    use common::value_object::{CustomerId, OrderStatus, RestaurantId};
+    use derive_builder::Builder;
+
+    use crate::dietary::DietaryPreferences;
+    use crate::entity::{Order, OrderBuilder, OrderItem, PaymentShareBuilder};
+    use crate::value_object::{StreetAddress, TrackingId};
+
+    #[derive(Clone, Default, PartialEq)]
+    pub enum GroupOrderStatus {
+        #[default]
+        Open,
+        Locked,
+        Submitted,
+    }
+
+    impl FromStr for GroupOrderStatus {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Open" => Ok(GroupOrderStatus::Open),
+                "Locked" => Ok(GroupOrderStatus::Locked),
+                "Submitted" => Ok(GroupOrderStatus::Submitted),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for GroupOrderStatus {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                GroupOrderStatus::Open => write!(f, "Open"),
+                GroupOrderStatus::Locked => write!(f, "Locked"),
+                GroupOrderStatus::Submitted => write!(f, "Submitted"),
+            }
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct GroupParticipant {
+        pub customer_id: CustomerId,
+        #[builder(default)]
+        pub items: Vec<OrderItem>,
+        #[builder(default)]
+        pub dietary_preferences: Option<DietaryPreferences>,
+    }
+
+    impl GroupParticipant {
+        pub fn customer_uuid(&self) -> uuid::Uuid {
+            self.customer_id.clone().into()
+        }
+
+        pub fn items_price(&self) -> Money {
+            let mut items_price = Money::new(0.0);
+            for item in self.items.iter() {
+                items_price += item.sub_total.clone();
+            }
+            items_price
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct GroupOrder {
+        pub id: uuid::Uuid,
+        pub host_id: CustomerId,
+        pub restaurant_id: RestaurantId,
+        pub street_address: StreetAddress,
+        #[builder(default)]
+        pub status: GroupOrderStatus,
+        #[builder(default)]
+        pub participants: Vec<GroupParticipant>,
+        #[builder(default)]
+        pub order_id: Option<uuid::Uuid>,
+        #[builder(default)]
+        pub version: i64,
+    }
+
+    impl GroupOrder {
+        pub fn open(
+            id: uuid::Uuid,
+            host_id: uuid::Uuid,
+            restaurant_id: uuid::Uuid,
+            street_address: StreetAddress,
+            participant_ids: Vec<uuid::Uuid>,
+        ) -> Self {
+            let mut participants = vec![GroupParticipantBuilder::default()
+                .customer_id(host_id.into())
+                .build()
+                .unwrap()];
+            for participant_id in participant_ids {
+                if !participants
+                    .iter()
+                    .any(|participant| participant.customer_uuid() == participant_id)
+                {
+                    participants.push(
+                        GroupParticipantBuilder::default()
+                            .customer_id(participant_id.into())
+                            .build()
+                            .unwrap(),
+                    );
+                }
+            }
+            GroupOrderBuilder::default()
+                .id(id)
+                .host_id(host_id.into())
+                .restaurant_id(restaurant_id.into())
+                .street_address(street_address)
+                .participants(participants)
+                .build()
+                .unwrap()
+        }
+
+        pub fn ensure_host(&self, customer_id: uuid::Uuid) -> Result<(), OrderDomainError> {
+            let host_id: uuid::Uuid = self.host_id.clone().into();
+            if host_id != customer_id {
+                return Err(OrderDomainError::NotGroupOrderHost);
+            }
+            Ok(())
+        }
+
+        fn item_count(&self) -> usize {
+            self.participants
+                .iter()
+                .map(|participant| participant.items.len())
+                .sum()
+        }
+
+        pub fn add_items(
+            &mut self,
+            customer_id: uuid::Uuid,
+            items: Vec<OrderItem>,
+        ) -> Result<(), OrderDomainError> {
+            if self.status != GroupOrderStatus::Open {
+                return Err(OrderDomainError::InvalidGroupOrderStatus(String::from(
+                    "adding items",
+                )));
+            }
+            let first_item_id = self.item_count();
+            let group_order_id = self.id;
+            match self
+                .participants
+                .iter_mut()
+                .find(|participant| participant.customer_uuid() == customer_id)
+            {
+                Some(participant) => {
+                    participant.items.extend(items.into_iter().enumerate().map(
+                        |(index, mut item)| {
+                            item.base_entity.id = ((first_item_id + index) as i64).into();
+                            item.order_id = group_order_id.into();
+                            item
+                        },
+                    ));
+                    Ok(())
+                }
+                None => Err(OrderDomainError::NotGroupOrderParticipant),
+            }
+        }
+
+        pub fn lock(&mut self, customer_id: uuid::Uuid) -> Result<(), OrderDomainError> {
+            self.ensure_host(customer_id)?;
+            if self.status != GroupOrderStatus::Open {
+                return Err(OrderDomainError::InvalidGroupOrderStatus(String::from(
+                    "locking",
+                )));
+            }
+            if self.item_count() == 0 {
+                return Err(OrderDomainError::EmptyGroupOrder);
+            }
+            self.status = GroupOrderStatus::Locked;
+            Ok(())
+        }
+
+        pub fn submit(
+            &mut self,
+            customer_id: uuid::Uuid,
+            order_id: uuid::Uuid,
+            tracking_id: uuid::Uuid,
+        ) -> Result<Order, OrderDomainError> {
+            self.ensure_host(customer_id)?;
+            let resubmitting =
+                self.status == GroupOrderStatus::Submitted && self.order_id == Some(order_id);
+            if self.status != GroupOrderStatus::Locked && !resubmitting {
+                return Err(OrderDomainError::InvalidGroupOrderStatus(String::from(
+                    "submitting",
+                )));
+            }
+            let mut price = Money::new(0.0);
+            let mut items: Vec<OrderItem> = vec![];
+            for participant in self.participants.iter() {
+                price += participant.items_price();
+                for item in participant.items.iter() {
+                    let mut item = item.clone();
+                    item.base_entity.id = (items.len() as i64).into();
+                    item.order_id = order_id.into();
+                    item.dietary_preferences = participant.dietary_preferences.clone();
+                    items.push(item);
+                }
+            }
+            let payment_shares = self
+                .participants
+                .iter()
+                .filter(|participant| !participant.items.is_empty())
+                .map(|participant| {
+                    PaymentShareBuilder::default()
+                        .customer_id(participant.customer_id.clone())
+                        .items_price(participant.items_price())
+                        .build()
+                        .unwrap()
+                })
+                .collect();
+            let tracking_id: TrackingId = tracking_id.into();
+            let order = OrderBuilder::default()
+                .aggregate_root(
+                    AggregateRootBuilder::default()
+                        .base_entity(
+                            BaseEntityBuilder::default()
+                                .id(order_id.into())
+                                .build()
+                                .unwrap(),
+                        )
+                        .build()
+                        .unwrap(),
+                )
+                .customer_id(self.host_id.clone())
+                .restaurant_id(self.restaurant_id.clone())
+                .street_address(self.street_address.clone())
+                .price(price)
+                .items(items)
+                .tracking_id(tracking_id)
+                .order_status(OrderStatus::Pending)
+                .failure_messages(vec![])
+                .payment_shares(payment_shares)
+                .build()
+                .unwrap();
+            self.status = GroupOrderStatus::Submitted;
+            self.order_id = Some(order_id);
+            Ok(order)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use common::error::OrderDomainError;
+
+        use super::{GroupOrder, GroupOrderStatus};
+        use crate::dietary::{Allergen, DietaryConflictPolicy, DietaryPreferencesBuilder};
+        use crate::entity::Order;
+        use crate::{fixtures, OrderDomainService};
+
+        fn group_order() -> GroupOrder {
+            GroupOrder::open(
+                fixtures::id(40),
+                fixtures::id(2),
+                fixtures::id(3),
+                fixtures::street_address("1011AB", "Amsterdam"),
+                vec![fixtures::id(2), fixtures::id(21), fixtures::id(21)],
+            )
+        }
+
+        fn submitted_order(guest_product: u128) -> Order {
+            let mut group_order = group_order();
+            group_order
+                .add_items(
+                    fixtures::id(2),
+                    vec![fixtures::order_item(1, fixtures::product(10, 250.0), 1)],
+                )
+                .unwrap();
+            group_order
+                .add_items(
+                    fixtures::id(21),
+                    vec![fixtures::order_item(
+                        1,
+                        fixtures::product(guest_product, 250.0),
+                        1,
+                    )],
+                )
+                .unwrap();
+            group_order.lock(fixtures::id(2)).unwrap();
+            group_order.participants[1].dietary_preferences = Some(
+                DietaryPreferencesBuilder::default()
+                    .allergies(vec![Allergen::Gluten])
+                    .conflict_policy(DietaryConflictPolicy::Reject)
+                    .build()
+                    .unwrap(),
+            );
+            group_order
+                .submit(fixtures::id(2), fixtures::id(41), fixtures::id(42))
+                .unwrap()
+        }
+
+        fn catalog() -> Vec<crate::entity::Product> {
+            let mut bread = fixtures::product(10, 250.0);
+            bread.allergens = vec![Allergen::Gluten];
+            vec![bread, fixtures::product(11, 250.0)]
+        }
+
+        #[test]
+        fn participants_are_only_checked_against_their_own_items() {
+            let order_created_event = fixtures::domain_service(fixtures::tax_calculator(0.0))
+                .validate_and_initiate_order(
+                    submitted_order(11),
+                    fixtures::restaurant(catalog()),
+                    fixtures::causation(),
+                )
+                .unwrap();
+            assert!(order_created_event.order.dietary_conflicts.is_empty());
+            let declared = order_created_event
+                .order
+                .declared_dietary_preferences()
+                .unwrap();
+            assert!(declared.allergies == vec![Allergen::Gluten]);
+            assert!(matches!(
+                fixtures::domain_service(fixtures::tax_calculator(0.0))
+                    .validate_and_initiate_order(
+                        submitted_order(10),
+                        fixtures::restaurant(catalog()),
+                        fixtures::causation(),
+                    ),
+                Err(OrderDomainError::ValidationFailed(_))
+            ));
+        }
+
+        #[test]
+        fn open_invites_each_participant_once() {
+            let participants: Vec<uuid::Uuid> = group_order()
+                .participants
+                .iter()
+                .map(|participant| participant.customer_uuid())
+                .collect();
+            assert_eq!(participants, vec![fixtures::id(2), fixtures::id(21)]);
+        }
+
+        #[test]
+        fn add_items_rejects_customers_who_were_not_invited() {
+            let mut group_order = group_order();
+            let items = vec![fixtures::order_item(1, fixtures::product(10, 250.0), 1)];
+            assert!(matches!(
+                group_order.add_items(fixtures::id(22), items.clone()),
+                Err(OrderDomainError::NotGroupOrderParticipant)
+            ));
+            assert!(group_order.add_items(fixtures::id(21), items).is_ok());
+            assert_eq!(group_order.participants[1].items.len(), 1);
+        }
+
+        #[test]
+        fn submit_can_only_be_repeated_for_the_claimed_order() {
+            let mut group_order = group_order();
+            group_order
+                .add_items(
+                    fixtures::id(21),
+                    vec![fixtures::order_item(1, fixtures::product(10, 250.0), 1)],
+                )
+                .unwrap();
+            group_order.lock(fixtures::id(2)).unwrap();
+            group_order
+                .submit(fixtures::id(2), fixtures::id(41), fixtures::id(42))
+                .unwrap();
+            assert!(group_order.status == GroupOrderStatus::Submitted);
+            assert!(matches!(
+                group_order.submit(fixtures::id(2), fixtures::id(43), fixtures::id(44)),
+                Err(OrderDomainError::InvalidGroupOrderStatus(_))
+            ));
+            let order = group_order
+                .submit(fixtures::id(2), fixtures::id(41), fixtures::id(45))
+                .unwrap();
+            assert_eq!(
+                order.aggregate_root.base_entity.id.base_id.value,
+                fixtures::id(41)
+            );
+        }
+    }
+}
+
+pub mod checkout {
+    use std::{fmt::Display, str::FromStr};
+
+    use common::error::OrderDomainError;
+    use common::value_object::money::Money;
+    use common::value_object::CustomerId;
+    use derive_builder::Builder;
+
+    use crate::entity::Order;
+
+    #[derive(Clone, Default, PartialEq)]
+    pub enum CheckoutCancellationPolicy {
+        #[default]
+        CancelAll,
+        CancelRejectedOnly,
+    }
+
+    impl FromStr for CheckoutCancellationPolicy {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "CancelAll" => Ok(CheckoutCancellationPolicy::CancelAll),
+                "CancelRejectedOnly" => Ok(CheckoutCancellationPolicy::CancelRejectedOnly),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for CheckoutCancellationPolicy {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                CheckoutCancellationPolicy::CancelAll => write!(f, "CancelAll"),
+                CheckoutCancellationPolicy::CancelRejectedOnly => write!(f, "CancelRejectedOnly"),
+            }
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OrderCheckout {
+        pub id: uuid::Uuid,
+        #[builder(default)]
+        pub cancellation_policy: CheckoutCancellationPolicy,
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct Checkout {
+        pub id: uuid::Uuid,
+        pub customer_id: CustomerId,
+        #[builder(default)]
+        pub cancellation_policy: CheckoutCancellationPolicy,
+        pub orders: Vec<Order>,
+    }
+
+    impl Checkout {
+        pub fn new(
+            id: uuid::Uuid,
+            customer_id: CustomerId,
+            cancellation_policy: CheckoutCancellationPolicy,
+            orders: Vec<Order>,
+        ) -> Result<Self, OrderDomainError> {
+            if orders.is_empty() {
+                return Err(OrderDomainError::EmptyBasket);
+            }
+            let orders = orders
+                .into_iter()
+                .map(|mut order| {
+                    order.checkout = Some(
+                        OrderCheckoutBuilder::default()
+                            .id(id)
+                            .cancellation_policy(cancellation_policy.clone())
+                            .build()
+                            .unwrap(),
+                    );
+                    order
+                })
+                .collect();
+            Ok(CheckoutBuilder::default()
+                .id(id)
+                .customer_id(customer_id)
+                .cancellation_policy(cancellation_policy)
+                .orders(orders)
+                .build()
+                .unwrap())
+        }
+
+        pub fn total_price(&self) -> Money {
+            let mut total_price = Money::new(0.0);
+            for order in self.orders.iter() {
+                total_price += order.total_price();
+            }
+            total_price
+        }
+
+        pub fn tax(&self) -> Money {
+            let mut tax = Money::new(0.0);
+            for order in self.orders.iter() {
+                tax += order.tax.clone();
+            }
+            tax
+        }
+
+        pub fn tip_amount(&self) -> Money {
+            let mut tip_amount = Money::new(0.0);
+            for order in self.orders.iter() {
+                tip_amount += order.tip_amount();
+            }
+            tip_amount
+        }
+    }
+}
+
+pub mod subscription {
+    use std::{fmt::Display, str::FromStr};
+
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+    use chrono_tz::Tz;
+    use common::error::OrderDomainError;
+    use common::value_object::{CustomerId, RestaurantId};
+    use derive_builder::Builder;
+
+    use crate::entity::OrderItem;
+    use crate::value_object::{StreetAddress, Tip};
+
+    #[derive(Clone, Default, PartialEq)]
+    pub enum SubscriptionStatus {
+        #[default]
+        Active,
+        Paused,
+        Ended,
+    }
+
+    impl FromStr for SubscriptionStatus {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Active" => Ok(SubscriptionStatus::Active),
+                "Paused" => Ok(SubscriptionStatus::Paused),
+                "Ended" => Ok(SubscriptionStatus::Ended),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Display for SubscriptionStatus {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SubscriptionStatus::Active => write!(f, "Active"),
+                SubscriptionStatus::Paused => write!(f, "Paused"),
+                SubscriptionStatus::Ended => write!(f, "Ended"),
+            }
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct SubscriptionSchedule {
+        pub weekdays: Vec<Weekday>,
+        pub delivery_time: NaiveTime,
+        pub time_zone: Tz,
+    }
+
+    impl SubscriptionSchedule {
+        pub fn new(
+            weekdays: Vec<Weekday>,
+            delivery_time: NaiveTime,
+            time_zone: &str,
+        ) -> Result<Self, OrderDomainError> {
+            let time_zone: Tz = time_zone.parse().map_err(|_| {
+                OrderDomainError::InvalidSubscriptionSchedule(format!(
+                    "unknown time zone {}",
+                    time_zone
+                ))
+            })?;
+            Ok(Self {
+                weekdays,
+                delivery_time,
+                time_zone,
+            })
+        }
+
+        pub fn includes(&self, date: NaiveDate) -> bool {
+            self.weekdays.contains(&date.weekday())
+        }
+
+        pub fn delivery_at(&self, date: NaiveDate) -> Option<DateTime<Utc>> {
+            self.time_zone
+                .from_local_datetime(&date.and_time(self.delivery_time))
+                .earliest()
+                .map(|delivery_at| delivery_at.with_timezone(&Utc))
+        }
+
+        pub fn local_date(&self, at: DateTime<Utc>) -> NaiveDate {
+            at.with_timezone(&self.time_zone).date_naive()
+        }
+    }
+
+    #[derive(Clone, PartialEq)]
+    pub struct SubscriptionOccurrence {
+        pub date: NaiveDate,
+        pub delivery_at: DateTime<Utc>,
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OrderSubscription {
+        pub id: uuid::Uuid,
+        pub customer_id: CustomerId,
+        pub restaurant_id: RestaurantId,
+        pub street_address: StreetAddress,
+        pub items: Vec<OrderItem>,
+        #[builder(default)]
+        pub tip: Option<Tip>,
+        pub schedule: SubscriptionSchedule,
+        #[builder(default)]
+        pub ends_on: Option<NaiveDate>,
+        #[builder(default)]
+        pub status: SubscriptionStatus,
+        #[builder(default)]
+        pub last_occurrence_on: Option<NaiveDate>,
+    }
+
+    impl OrderSubscription {
+        pub fn validate(&self, today: NaiveDate) -> Result<(), OrderDomainError> {
+            if self.schedule.weekdays.is_empty() {
+                return Err(OrderDomainError::InvalidSubscriptionSchedule(String::from(
+                    "at least one weekday is required",
+                )));
+            }
+            if self.items.is_empty() {
+                return Err(OrderDomainError::InvalidSubscriptionSchedule(String::from(
+                    "the order template has no items",
+                )));
+            }
+            if self.ends_on.is_some_and(|ends_on| ends_on < today) {
+                return Err(OrderDomainError::InvalidSubscriptionSchedule(String::from(
+                    "the end date is in the past",
+                )));
+            }
+            Ok(())
+        }
+
+        pub fn is_owned_by(&self, customer_id: uuid::Uuid) -> bool {
+            let owner_id: uuid::Uuid = self.customer_id.clone().into();
+            owner_id == customer_id
+        }
+
+        pub fn pause(&mut self) -> Result<(), OrderDomainError> {
+            if self.status != SubscriptionStatus::Active {
+                return Err(OrderDomainError::InvalidSubscriptionStatus(String::from(
+                    "pause",
+                )));
+            }
+            self.status = SubscriptionStatus::Paused;
+            Ok(())
+        }
+
+        pub fn resume(&mut self) -> Result<(), OrderDomainError> {
+            if self.status != SubscriptionStatus::Paused {
+                return Err(OrderDomainError::InvalidSubscriptionStatus(String::from(
+                    "resume",
+                )));
+            }
+            self.status = SubscriptionStatus::Active;
+            Ok(())
+        }
+
+        pub fn next_occurrence(
+            &self,
+            earliest_delivery_at: DateTime<Utc>,
+            latest_delivery_at: DateTime<Utc>,
+        ) -> Option<SubscriptionOccurrence> {
+            if self.status != SubscriptionStatus::Active {
+                return None;
+            }
+            let mut date = self.schedule.local_date(earliest_delivery_at);
+            while date <= self.schedule.local_date(latest_delivery_at) {
+                if self.ends_on.is_some_and(|ends_on| date > ends_on) {
+                    return None;
+                }
+                let already_occurred = self
+                    .last_occurrence_on
+                    .is_some_and(|last_occurrence_on| date <= last_occurrence_on);
+                if self.schedule.includes(date) && !already_occurred {
+                    if let Some(delivery_at) = self.schedule.delivery_at(date) {
+                        if delivery_at >= earliest_delivery_at && delivery_at <= latest_delivery_at
+                        {
+                            return Some(SubscriptionOccurrence { date, delivery_at });
+                        }
+                    }
+                }
+                date = date.succ_opt()?;
+            }
+            None
+        }
+
+        pub fn record_occurrence(&mut self, occurrence: &SubscriptionOccurrence) {
+            self.last_occurrence_on = Some(occurrence.date);
+            if self
+                .ends_on
+                .is_some_and(|ends_on| occurrence.date >= ends_on)
+            {
+                self.status = SubscriptionStatus::Ended;
+            }
+        }
+    }
+}
+
+pub mod event {
+
+    use common::event::{DomainEvent, EventEnvelope};
+    use derive_builder::Builder;
+
+    use crate::checkout::Checkout;
+    use crate::entity::{Order, OrderAdjustment};
+    use crate::subscription::{OrderSubscription, SubscriptionOccurrence};
+
+    #[derive(Clone, Builder)]
+    pub struct OrderSubscriptionFailed {
+        pub subscription: OrderSubscription,
+        pub occurrence: SubscriptionOccurrence,
+        pub failure_messages: Vec<String>,
+        pub envelope: EventEnvelope,
+    }
+
+    impl DomainEvent<OrderSubscription> for OrderSubscriptionFailed {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct CheckoutCreated {
+        pub checkout: Checkout,
+        pub envelope: EventEnvelope,
+    }
+
+    impl DomainEvent<Checkout> for CheckoutCreated {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OrderCreated {
+        pub order: Order,
+        pub envelope: EventEnvelope,
+    }
+
+    impl DomainEvent<Order> for OrderCreated {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OrderRiskReviewRequested {
+        pub order: Order,
+        pub envelope: EventEnvelope,
+    }
+
+    impl DomainEvent<Order> for OrderRiskReviewRequested {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
+
+    #[derive(Clone)]
+    pub enum OrderEvent {
+        Scheduled(chrono::DateTime<chrono::Utc>),
+        Released,
+        Paid,
+        Approved,
+        ItemAdjusted(OrderAdjustment),
+        CancelInitiated(Vec<String>),
+        Cancelled(Vec<String>),
+        SharePaid(uuid::Uuid),
+        ShareFailed(uuid::Uuid),
+        DeliveryEstimated(Option<chrono::DateTime<chrono::Utc>>),
+    }
+
+    impl OrderEvent {
+        pub fn changes_status(&self) -> bool {
+            !matches!(
+                self,
+                OrderEvent::ItemAdjusted(_)
+                    | OrderEvent::SharePaid(_)
+                    | OrderEvent::ShareFailed(_)
+                    | OrderEvent::DeliveryEstimated(_)
+            )
+        }
+    }
 
     #[derive(Clone, Builder)]
-    pub struct OrderCreated {
+    pub struct OrderPaymentSharePaid {
         pub order: Order,
-        pub created_at: chrono::DateTime<chrono::Utc>,
+        pub customer_id: uuid::Uuid,
+        pub envelope: EventEnvelope,
     }
 
-    impl DomainEvent<Order> for OrderCreated {}
+    impl DomainEvent<Order> for OrderPaymentSharePaid {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
 
     #[derive(Clone, Builder)]
     pub struct OrderCancelled {
         pub order: Order,
-        pub created_at: chrono::DateTime<chrono::Utc>,
+        pub envelope: EventEnvelope,
     }
 
-    impl DomainEvent<Order> for OrderCancelled {}
+    impl DomainEvent<Order> for OrderCancelled {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
 
     #[derive(Clone, Builder)]
     pub struct OrderPaid {
         pub order: Order,
-        pub created_at: chrono::DateTime<chrono::Utc>,
+        pub envelope: EventEnvelope,
     }
 
-    impl DomainEvent<Order> for OrderPaid {}
-}
+    impl DomainEvent<Order> for OrderPaid {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OrderApproved {
+        pub order: Order,
+        pub envelope: EventEnvelope,
+    }
+
+    impl DomainEvent<Order> for OrderApproved {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OrderCancelledFinal {
+        pub order: Order,
+        pub envelope: EventEnvelope,
+    }
 
+    impl DomainEvent<Order> for OrderCancelledFinal {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
+
+    #[derive(Clone, Builder)]
+    pub struct OrderItemAdjusted {
+        pub order: Order,
+        pub adjustment: OrderAdjustment,
+        pub envelope: EventEnvelope,
+    }
+
+    impl DomainEvent<Order> for OrderItemAdjusted {
+        fn envelope(&self) -> &EventEnvelope {
+            &self.envelope
+        }
+    }
+}
 pub trait OrderDomainService: Send + Sync {
     fn validate_and_initiate_order(
         &self,
         order: Order,
         restaurant: Restaurant,
+        causation: Causation,
+    ) -> Result<event::OrderCreated, OrderDomainError>;
+
+    fn validate_and_initiate_checkout(
+        &self,
+        checkout_id: uuid::Uuid,
+        customer_id: uuid::Uuid,
+        cancellation_policy: CheckoutCancellationPolicy,
+        orders: Vec<(Order, Restaurant)>,
+        causation: Causation,
+    ) -> Result<event::CheckoutCreated, OrderDomainError>;
+
+    fn release_scheduled_order(
+        &self,
+        order: Order,
+        restaurant: &Restaurant,
+        causation: Causation,
     ) -> Result<event::OrderCreated, OrderDomainError>;
 
-    fn pay_order(order: Order) -> Result<event::OrderPaid, OrderDomainError>;
+    fn scheduled_release_cutoff(&self) -> chrono::DateTime<chrono::Utc>;
+
+    fn saga_step_cutoff(&self, timeout: chrono::Duration) -> chrono::DateTime<chrono::Utc>;
+
+    fn pay_order(
+        &self,
+        order: Order,
+        causation: Causation,
+    ) -> Result<event::OrderPaid, OrderDomainError>;
+
+    fn pay_order_share(
+        &self,
+        order: Order,
+        customer_id: uuid::Uuid,
+        causation: Causation,
+    ) -> Result<event::OrderPaymentSharePaid, OrderDomainError>;
+
+    fn fail_order_share(
+        &self,
+        order: Order,
+        customer_id: uuid::Uuid,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> Result<event::OrderCancelled, OrderDomainError>;
 
-    fn approve_order(order: Order) -> Result<(), OrderDomainError>;
+    fn approve_order(
+        &self,
+        order: Order,
+        causation: Causation,
+    ) -> Result<event::OrderApproved, OrderDomainError>;
 
     fn cancel_order_payment(
+        &self,
+        order: Order,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> Result<event::OrderCancelled, OrderDomainError>;
+
+    fn cancel_order(
+        &self,
+        order: Order,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> Result<event::OrderCancelledFinal, OrderDomainError>;
+
+    fn cancel_pending_order(
+        &self,
+        order: Order,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> Result<event::OrderCancelled, OrderDomainError>;
+
+    fn cancel_checkout_order(
+        &self,
         order: Order,
         failure_messages: Vec<String>,
+        causation: Causation,
     ) -> Result<event::OrderCancelled, OrderDomainError>;
 
-    fn cancel_order(order: Order, failure_messages: Vec<String>) -> Result<(), OrderDomainError>;
+    fn validate_subscription(
+        &self,
+        subscription: &OrderSubscription,
+    ) -> Result<(), OrderDomainError>;
+
+    fn due_subscription_occurrence(
+        &self,
+        subscription: &OrderSubscription,
+        materialize_lead_time: chrono::Duration,
+    ) -> Option<SubscriptionOccurrence>;
+
+    fn fail_subscription_occurrence(
+        &self,
+        subscription: OrderSubscription,
+        occurrence: SubscriptionOccurrence,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> event::OrderSubscriptionFailed;
+
+    fn adjust_order_item(
+        &self,
+        order: Order,
+        order_item_id: i64,
+        quantity: u64,
+        reason: AdjustmentReason,
+        causation: Causation,
+    ) -> Result<event::OrderItemAdjusted, OrderDomainError>;
+
+    fn risk_lookback_start(&self) -> chrono::DateTime<chrono::Utc>;
+
+    fn assess_order_risk(&self, order: &Order, signals: RiskSignals) -> RiskCheck;
+
+    fn request_risk_review(
+        &self,
+        order: Order,
+        causation: Causation,
+    ) -> event::OrderRiskReviewRequested;
+
+    fn estimate_delivery(
+        &self,
+        order: &Order,
+        restaurant: &Restaurant,
+        kitchen_orders: u64,
+    ) -> Option<chrono::DateTime<chrono::Utc>>;
+}
+
+pub struct OrderDomainServiceImpl<TC: TaxCalculator, C: Clock, G: IdGenerator, E: EtaEstimator> {
+    tax_calculator: TC,
+    clock: C,
+    id_generator: G,
+    scheduling_policy: SchedulingPolicy,
+    risk_rules_engine: RiskRulesEngine,
+    eta_estimator: E,
 }
 
-pub struct OrderDomainServiceImpl {}
+impl<TC: TaxCalculator, C: Clock, G: IdGenerator, E: EtaEstimator>
+    OrderDomainServiceImpl<TC, C, G, E>
+{
+    pub fn new(
+        tax_calculator: TC,
+        clock: C,
+        id_generator: G,
+        scheduling_policy: SchedulingPolicy,
+        risk_rules_engine: RiskRulesEngine,
+        eta_estimator: E,
+    ) -> Self {
+        Self {
+            tax_calculator,
+            clock,
+            id_generator,
+            scheduling_policy,
+            risk_rules_engine,
+            eta_estimator,
+        }
+    }
+
+    fn envelope(&self, order: &Order, causation: Causation) -> EventEnvelope {
+        EventEnvelopeBuilder::default()
+            .event_id(self.id_generator.generate())
+            .aggregate_id(order.clone().into())
+            .aggregate_version(order.aggregate_root.version)
+            .occurred_at(self.clock.now())
+            .correlation_id(causation.correlation_id)
+            .causation_id(causation.causation_id)
+            .build()
+            .unwrap()
+    }
+}
 
-impl OrderDomainService for OrderDomainServiceImpl {
+impl<TC: TaxCalculator, C: Clock, G: IdGenerator, E: EtaEstimator> OrderDomainService
+    for OrderDomainServiceImpl<TC, C, G, E>
+{
     fn validate_and_initiate_order(
         &self,
         mut order: Order,
         restaurant: Restaurant,
+        causation: Causation,
     ) -> Result<event::OrderCreated, OrderDomainError> {
-        if !restaurant.is_active() {
-            return Err(OrderDomainError::InactiveRestaurant);
-        } else {
-            for item in order.items.iter_mut() {
-                for product in restaurant.products.iter() {
-                    if *product == item.product {
-                        item.product.name = product.name.clone();
-                        item.product.price = product.price.clone();
-                    }
+        for item in order.items.iter_mut() {
+            for product in restaurant.products.iter() {
+                if product.has_same_id(&item.product) {
+                    item.apply_catalog_product(product);
                 }
             }
-            order.validate_order()?;
-            let order_created_event = OrderCreatedBuilder::default().order(order).build().unwrap();
-            Ok(order_created_event)
         }
+        order.assess_dietary_constraints();
+        order.validate_for_restaurant(&restaurant)?;
+        match order.scheduled_for {
+            Some(requested_delivery_time) => self.scheduling_policy.validate_slot(
+                &restaurant,
+                requested_delivery_time,
+                self.clock.now(),
+            )?,
+            None => restaurant.validate_open_at(self.clock.now())?,
+        }
+        order.validate_minimum_order_amount(&restaurant)?;
+        order.apply_delivery_zone(&restaurant)?;
+        order.apply_taxes(&self.tax_calculator);
+        order.allocate_payment_shares();
+        if let Some(requested_delivery_time) = order.scheduled_for {
+            order.schedule(requested_delivery_time)?;
+        }
+        let _creation_events = order.take_pending_events();
+        order.aggregate_root.version = 1;
+        order.created_at = Some(self.clock.now());
+        order.status_changed_at = order.created_at;
+        let envelope = self.envelope(&order, causation);
+        let order_created_event = OrderCreatedBuilder::default()
+            .order(order)
+            .envelope(envelope)
+            .build()
+            .unwrap();
+        Ok(order_created_event)
+    }
+
+    fn validate_and_initiate_checkout(
+        &self,
+        checkout_id: uuid::Uuid,
+        customer_id: uuid::Uuid,
+        cancellation_policy: CheckoutCancellationPolicy,
+        orders: Vec<(Order, Restaurant)>,
+        causation: Causation,
+    ) -> Result<event::CheckoutCreated, OrderDomainError> {
+        let mut initiated_orders: Vec<Order> = vec![];
+        for (order, restaurant) in orders {
+            let order_created_event =
+                self.validate_and_initiate_order(order, restaurant, causation.clone())?;
+            initiated_orders.push(order_created_event.order);
+        }
+        let checkout = Checkout::new(
+            checkout_id,
+            customer_id.into(),
+            cancellation_policy,
+            initiated_orders,
+        )?;
+        let envelope = EventEnvelopeBuilder::default()
+            .event_id(self.id_generator.generate())
+            .aggregate_id(checkout_id)
+            .aggregate_version(0)
+            .occurred_at(self.clock.now())
+            .correlation_id(causation.correlation_id)
+            .causation_id(causation.causation_id)
+            .build()
+            .unwrap();
+        Ok(CheckoutCreatedBuilder::default()
+            .checkout(checkout)
+            .envelope(envelope)
+            .build()
+            .unwrap())
+    }
+
+    fn release_scheduled_order(
+        &self,
+        mut order: Order,
+        restaurant: &Restaurant,
+        causation: Causation,
+    ) -> Result<event::OrderCreated, OrderDomainError> {
+        if !restaurant.is_active() {
+            return Err(OrderDomainError::InactiveRestaurant);
+        }
+        restaurant.validate_open_at(order.scheduled_for.unwrap_or_else(|| self.clock.now()))?;
+        order.release()?;
+        order.status_changed_at = Some(self.clock.now());
+        Ok(OrderCreatedBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .build()
+            .unwrap())
+    }
+
+    fn scheduled_release_cutoff(&self) -> chrono::DateTime<chrono::Utc> {
+        self.scheduling_policy.release_cutoff(self.clock.now())
+    }
+
+    fn saga_step_cutoff(&self, timeout: chrono::Duration) -> chrono::DateTime<chrono::Utc> {
+        self.clock.now() - timeout
     }
 
-    fn pay_order(mut order: Order) -> Result<event::OrderPaid, OrderDomainError> {
+    fn pay_order(
+        &self,
+        mut order: Order,
+        causation: Causation,
+    ) -> Result<event::OrderPaid, OrderDomainError> {
         order.pay()?;
-        Ok(OrderPaidBuilder::default().order(order).build().unwrap())
+        order.status_changed_at = Some(self.clock.now());
+        Ok(OrderPaidBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .build()
+            .unwrap())
     }
 
-    fn approve_order(mut order: Order) -> Result<(), OrderDomainError> {
+    fn pay_order_share(
+        &self,
+        mut order: Order,
+        customer_id: uuid::Uuid,
+        causation: Causation,
+    ) -> Result<event::OrderPaymentSharePaid, OrderDomainError> {
+        order.pay_share(customer_id)?;
+        Ok(OrderPaymentSharePaidBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .customer_id(customer_id)
+            .build()
+            .unwrap())
+    }
+
+    fn fail_order_share(
+        &self,
+        mut order: Order,
+        customer_id: uuid::Uuid,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> Result<event::OrderCancelled, OrderDomainError> {
+        order.fail_share(customer_id)?;
+        order.cancel(failure_messages)?;
+        order.status_changed_at = Some(self.clock.now());
+        Ok(OrderCancelledBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .build()
+            .unwrap())
+    }
+
+    fn approve_order(
+        &self,
+        mut order: Order,
+        causation: Causation,
+    ) -> Result<event::OrderApproved, OrderDomainError> {
         order.approve()?;
-        Ok(())
+        order.status_changed_at = Some(self.clock.now());
+        Ok(OrderApprovedBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .build()
+            .unwrap())
     }
 
     fn cancel_order_payment(
+        &self,
         mut order: Order,
         failure_messages: Vec<String>,
+        causation: Causation,
     ) -> Result<event::OrderCancelled, OrderDomainError> {
         order.init_cancel(failure_messages)?;
+        order.status_changed_at = Some(self.clock.now());
         Ok(OrderCancelledBuilder::default()
+            .envelope(self.envelope(&order, causation))
             .order(order)
             .build()
             .unwrap())
     }
 
     fn cancel_order(
+        &self,
+        mut order: Order,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> Result<event::OrderCancelledFinal, OrderDomainError> {
+        order.cancel(failure_messages)?;
+        order.status_changed_at = Some(self.clock.now());
+        Ok(OrderCancelledFinalBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .build()
+            .unwrap())
+    }
+
+    fn cancel_pending_order(
+        &self,
         mut order: Order,
         failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> Result<event::OrderCancelled, OrderDomainError> {
+        if order.order_status != OrderStatus::Pending {
+            return Err(OrderDomainError::InvalidOrderStatus(String::from(
+                "cancel pending order",
+            )));
+        }
+        order.cancel(failure_messages)?;
+        order.status_changed_at = Some(self.clock.now());
+        Ok(OrderCancelledBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .build()
+            .unwrap())
+    }
+
+    fn cancel_checkout_order(
+        &self,
+        mut order: Order,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> Result<event::OrderCancelled, OrderDomainError> {
+        order.init_checkout_cancel(failure_messages)?;
+        order.status_changed_at = Some(self.clock.now());
+        Ok(OrderCancelledBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .build()
+            .unwrap())
+    }
+
+    fn validate_subscription(
+        &self,
+        subscription: &OrderSubscription,
     ) -> Result<(), OrderDomainError> {
-        order.cancel(failure_messages.clone())
+        subscription.validate(subscription.schedule.local_date(self.clock.now()))
+    }
+
+    fn due_subscription_occurrence(
+        &self,
+        subscription: &OrderSubscription,
+        materialize_lead_time: chrono::Duration,
+    ) -> Option<SubscriptionOccurrence> {
+        let now = self.clock.now();
+        subscription.next_occurrence(
+            now + self.scheduling_policy.minimum_lead_time,
+            now + materialize_lead_time,
+        )
+    }
+
+    fn fail_subscription_occurrence(
+        &self,
+        subscription: OrderSubscription,
+        occurrence: SubscriptionOccurrence,
+        failure_messages: Vec<String>,
+        causation: Causation,
+    ) -> event::OrderSubscriptionFailed {
+        let envelope = EventEnvelopeBuilder::default()
+            .event_id(self.id_generator.generate())
+            .aggregate_id(subscription.id)
+            .aggregate_version(0)
+            .occurred_at(self.clock.now())
+            .correlation_id(causation.correlation_id)
+            .causation_id(causation.causation_id)
+            .build()
+            .unwrap();
+        OrderSubscriptionFailedBuilder::default()
+            .subscription(subscription)
+            .occurrence(occurrence)
+            .failure_messages(failure_messages)
+            .envelope(envelope)
+            .build()
+            .unwrap()
+    }
+
+    fn adjust_order_item(
+        &self,
+        mut order: Order,
+        order_item_id: i64,
+        quantity: u64,
+        reason: AdjustmentReason,
+        causation: Causation,
+    ) -> Result<event::OrderItemAdjusted, OrderDomainError> {
+        let adjustment = order.adjust_item(
+            self.id_generator.generate(),
+            order_item_id,
+            quantity,
+            reason,
+            self.clock.now(),
+        )?;
+        Ok(OrderItemAdjustedBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .adjustment(adjustment)
+            .build()
+            .unwrap())
+    }
+
+    fn risk_lookback_start(&self) -> chrono::DateTime<chrono::Utc> {
+        self.clock.now() - self.risk_rules_engine.lookback()
+    }
+
+    fn assess_order_risk(&self, order: &Order, signals: RiskSignals) -> RiskCheck {
+        let now = self.clock.now();
+        RiskCheckBuilder::default()
+            .id(self.id_generator.generate())
+            .order_id(order.clone().into())
+            .customer_id(order.customer_id.clone().into())
+            .address_key(risk::address_key(&order.street_address))
+            .assessment(self.risk_rules_engine.evaluate(order, &signals, now))
+            .checked_at(now)
+            .build()
+            .unwrap()
+    }
+
+    fn request_risk_review(
+        &self,
+        order: Order,
+        causation: Causation,
+    ) -> event::OrderRiskReviewRequested {
+        OrderRiskReviewRequestedBuilder::default()
+            .envelope(self.envelope(&order, causation))
+            .order(order)
+            .build()
+            .unwrap()
+    }
+
+    fn estimate_delivery(
+        &self,
+        order: &Order,
+        restaurant: &Restaurant,
+        kitchen_orders: u64,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.eta_estimator
+            .estimate_delivery(order, restaurant, kitchen_orders, self.clock.now())
+    }
+}
+
+#[cfg(test)]
+mod fixtures {
+    use std::collections::HashSet;
+
+    use chrono::{DateTime, NaiveTime, TimeZone, Utc, Weekday};
+    use common::clock::FixedClock;
+    use common::entity::{AggregateRootBuilder, BaseEntityBuilder};
+    use common::event::Causation;
+    use common::id::SequentialIdGenerator;
+    use common::value_object::{money::Money, OrderStatus};
+
+    use crate::delivery::{DeliveryArea, DeliveryFeeSchedule, DeliveryZone};
+    use crate::entity::{Order, OrderBuilder, OrderItem, OrderItemBuilder, Product, Restaurant};
+    use crate::eta::HeuristicEtaEstimator;
+    use crate::opening_hours::{OpeningHours, OpeningPeriod, WeeklyOpeningPeriod};
+    use crate::risk::RiskRulesEngine;
+    use crate::scheduling::SchedulingPolicy;
+    use crate::tax::TableTaxCalculator;
+    use crate::value_object::{StreetAddress, StreetAddressBuilder};
+    use crate::OrderDomainServiceImpl;
+
+    pub type TestOrderDomainService = OrderDomainServiceImpl<
+        TableTaxCalculator,
+        FixedClock,
+        SequentialIdGenerator,
+        HeuristicEtaEstimator,
+    >;
+
+    pub fn id(n: u128) -> uuid::Uuid {
+        uuid::Uuid::from_u128(n)
+    }
+
+    pub fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap()
+    }
+
+    pub fn causation() -> Causation {
+        Causation::new(id(800), None)
+    }
+
+    pub fn tax_calculator(default_rate: f64) -> TableTaxCalculator {
+        TableTaxCalculator {
+            rules: vec![],
+            default_rate,
+        }
+    }
+
+    pub fn domain_service(tax_calculator: TableTaxCalculator) -> TestOrderDomainService {
+        OrderDomainServiceImpl::new(
+            tax_calculator,
+            FixedClock::new(now()),
+            SequentialIdGenerator::new(1000),
+            SchedulingPolicy::default(),
+            RiskRulesEngine::default(),
+            HeuristicEtaEstimator::default(),
+        )
+    }
+
+    pub fn street_address(postal_code: &str, city: &str) -> StreetAddress {
+        StreetAddressBuilder::default()
+            .id(id(900))
+            .street(String::from("1 Main Street"))
+            .postal_code(String::from(postal_code))
+            .city(String::from(city))
+            .build()
+            .unwrap()
+    }
+
+    pub fn product(n: u128, price: f64) -> Product {
+        Product::new(id(n), format!("product {}", n), Money::new(price))
+    }
+
+    pub fn order_item(item_id: i64, product: Product, quantity: u64) -> OrderItem {
+        OrderItemBuilder::default()
+            .base_entity(
+                BaseEntityBuilder::default()
+                    .id(item_id.into())
+                    .build()
+                    .unwrap(),
+            )
+            .order_id(id(1).into())
+            .price(product.price.clone())
+            .sub_total(product.price.clone() * quantity)
+            .product(product)
+            .quantity(quantity)
+            .build()
+            .unwrap()
+    }
+
+    pub fn order(items: Vec<OrderItem>) -> Order {
+        let mut price = Money::default();
+        for item in items.iter() {
+            price += item.sub_total.clone();
+        }
+        OrderBuilder::default()
+            .aggregate_root(
+                AggregateRootBuilder::default()
+                    .base_entity(
+                        BaseEntityBuilder::default()
+                            .id(id(1).into())
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .customer_id(id(2).into())
+            .restaurant_id(id(3).into())
+            .street_address(street_address("1011AB", "Amsterdam"))
+            .price(price)
+            .items(items)
+            .tracking_id(id(4).into())
+            .order_status(OrderStatus::Pending)
+            .failure_messages(vec![])
+            .build()
+            .unwrap()
+    }
+
+    pub fn delivery_zone(postal_code: &str, base_fee: f64) -> DeliveryZone {
+        DeliveryZone {
+            id: id(51),
+            area: DeliveryArea::PostalCodes(HashSet::from([String::from(postal_code)])),
+            fee_schedule: DeliveryFeeSchedule {
+                base_fee: Money::new(base_fee),
+                fee_per_km: Money::default(),
+                free_delivery_threshold: None,
+            },
+            minimum_order_amount: Money::default(),
+        }
+    }
+
+    pub fn restaurant(products: Vec<Product>) -> Restaurant {
+        let mut restaurant = Restaurant::new(id(3), products, true);
+        restaurant.delivery_zones = vec![delivery_zone("1011AB", 0.0)];
+        restaurant.opening_hours = Some(always_open());
+        restaurant
+    }
+
+    pub fn always_open() -> OpeningHours {
+        let midnight = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+        OpeningHours {
+            time_zone: chrono_tz::UTC,
+            weekly_periods: [
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Sat,
+                Weekday::Sun,
+            ]
+            .into_iter()
+            .map(|weekday| WeeklyOpeningPeriod {
+                weekday,
+                period: OpeningPeriod {
+                    opens_at: midnight,
+                    closes_at: midnight,
+                },
+            })
+            .collect(),
+            exceptions: vec![],
+            pauses: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{error::OrderDomainError, value_object::OrderStatus};
+
+    use crate::fixtures;
+    use crate::OrderDomainService;
+
+    #[test]
+    fn validate_and_initiate_order_applies_catalog_products_by_id() {
+        let mut catalog_product = fixtures::product(10, 250.0);
+        catalog_product.name = String::from("Margherita");
+        let mut requested_product = fixtures::product(10, 250.0);
+        requested_product.name = String::new();
+        let order = fixtures::order(vec![fixtures::order_item(1, requested_product, 2)]);
+        let order_created_event = fixtures::domain_service(fixtures::tax_calculator(0.1))
+            .validate_and_initiate_order(
+                order,
+                fixtures::restaurant(vec![catalog_product]),
+                fixtures::causation(),
+            )
+            .unwrap();
+        let order = order_created_event.order;
+        assert_eq!(order.items[0].product.name, "Margherita");
+        assert_eq!(order.tax.amount, 50.0);
+    }
+
+    #[test]
+    fn cancel_pending_order_only_cancels_pending_orders() {
+        let mut order = fixtures::order(vec![fixtures::order_item(
+            1,
+            fixtures::product(10, 250.0),
+            1,
+        )]);
+        order.order_status = OrderStatus::Paid;
+        let domain_service = fixtures::domain_service(fixtures::tax_calculator(0.0));
+        assert!(matches!(
+            domain_service.cancel_pending_order(order.clone(), vec![], fixtures::causation()),
+            Err(OrderDomainError::InvalidOrderStatus(_))
+        ));
+        order.order_status = OrderStatus::Pending;
+        let order_cancelled_event = domain_service
+            .cancel_pending_order(order, vec![], fixtures::causation())
+            .unwrap();
+        assert!(order_cancelled_event.order.order_status == OrderStatus::Cancelled);
+        assert_eq!(
+            order_cancelled_event.order.status_changed_at,
+            Some(fixtures::now())
+        );
+    }
+
+    #[test]
+    fn envelopes_use_the_injected_clock_and_id_generator() {
+        let order = fixtures::order(vec![fixtures::order_item(
+            1,
+            fixtures::product(10, 250.0),
+            1,
+        )]);
+        let order_created_event = fixtures::domain_service(fixtures::tax_calculator(0.0))
+            .validate_and_initiate_order(
+                order,
+                fixtures::restaurant(vec![fixtures::product(10, 250.0)]),
+                fixtures::causation(),
+            )
+            .unwrap();
+        assert_eq!(order_created_event.envelope.event_id, fixtures::id(1000));
+        assert_eq!(order_created_event.envelope.occurred_at, fixtures::now());
+        assert_eq!(
+            order_created_event.envelope.correlation_id,
+            fixtures::id(800)
+        );
+        assert_eq!(order_created_event.order.created_at, Some(fixtures::now()));
+    }
+
+    #[test]
+    fn created_orders_start_at_the_first_stream_version() {
+        let mut order = fixtures::order(vec![fixtures::order_item(
+            1,
+            fixtures::product(10, 250.0),
+            1,
+        )]);
+        order.scheduled_for = Some(fixtures::now() + chrono::Duration::hours(3));
+        let mut order_created_event = fixtures::domain_service(fixtures::tax_calculator(0.0))
+            .validate_and_initiate_order(
+                order,
+                fixtures::restaurant(vec![fixtures::product(10, 250.0)]),
+                fixtures::causation(),
+            )
+            .unwrap();
+        assert_eq!(order_created_event.envelope.aggregate_version, 1);
+        assert_eq!(order_created_event.order.aggregate_root.version, 1);
+        assert!(order_created_event.order.take_pending_events().is_empty());
     }
 }
@@ -1,16 +1,41 @@
 use domain_core::{
-    entity::{Order, Restaurant},
+    entity::{Customer, Order, OrderItem, Product, Restaurant},
     event::OrderCreated,
+    group_order::GroupOrder,
+    risk::{address_key, RiskCheck, RiskDecision, RiskSignalsBuilder},
+    subscription::OrderSubscription,
     value_object::TrackingId,
     OrderDomainService,
 };
 
-use common::error::OrderDomainError;
+use common::{
+    clock::Clock, error::OrderDomainError, event::Causation, id::IdGenerator,
+    value_object::OrderStatus,
+};
+use config::{LiveStatusConfig, OrderLimits, RetryPolicy, SagaTimeouts, SubscriptionPolicy};
 use dto::{
+    adjust::{AdjustOrderItemCommand, AdjustOrderItemResponse},
+    batch::BatchResult,
+    checkout::{CheckoutBasketCommand, CheckoutBasketResponse},
     create::{CreateOrderCommand, CreateOrderResponse},
+    error::ErrorResponse,
+    group::{
+        group_order_products, AddGroupOrderItemsCommand, GroupOrderResponse, LockGroupOrderCommand,
+        OpenGroupOrderCommand, SubmitGroupOrderCommand,
+    },
+    list::{
+        ListCustomerOrdersQuery, OrderCursor, OrderPageResponse, OrderSearchCriteria,
+        SearchOrdersQuery,
+    },
     message::RestaurantApprovalResponse,
-    track::{TrackOrderQuery, TrackOrderResponse},
+    reorder::{ReorderCommand, ReorderQuote, ReorderResponse},
+    subscription::{
+        occurrence_order_command, ChangeSubscriptionStatusCommand, CreateSubscriptionCommand,
+        SubscriptionResponse,
+    },
+    track::{SubscribeOrderStatusQuery, TrackOrderQuery, TrackOrderResponse},
 };
+use live::{OrderStatusBroadcaster, OrderStatusSubscription};
 use ports::{
     input::{
         message::listener::{
@@ -19,26 +44,45 @@ use ports::{
         service::OrderApplicationService,
     },
     output::{
-        message::publisher::payment::OrderCreatedPaymentRequestMessagePublisher,
-        repository::{CustomerRepository, OrderRepository, RestaurantRepository},
+        message::publisher::{
+            notification::OrderSubscriptionFailedNotificationMessagePublisher,
+            payment::{
+                CheckoutCreatedPaymentRequestMessagePublisher,
+                OrderCancelledPaymentRequestMessagePublisher,
+                OrderCreatedPaymentRequestMessagePublisher, OrderRefundRequestMessagePublisher,
+            },
+            restaurant_approval::OrderPaidRestaurantRequestMessagePublisher,
+            risk_review::OrderRiskReviewRequestMessagePublisher,
+        },
+        repository::{
+            CustomerRepository, GroupOrderRepository, OrderQueryRepository, OrderRepository,
+            OrderSubscriptionRepository, OrderTrackingViewRepository, RestaurantRepository,
+            RiskCheckRepository,
+        },
     },
 };
+use retry::retry_on_concurrent_modification;
 
 pub mod dto {
     pub mod create {
         use domain_core::{
             entity::{
                 Order, OrderBuilder, OrderItem as OrderItemEntity, OrderItemBuilder, Product,
-                Restaurant,
+                Restaurant, SelectedOption, SelectedOptionBuilder,
+            },
+            value_object::{
+                Coordinates, OrderItemId, StreetAddress, StreetAddressBuilder, Tip, TipAmount,
+                TipBuilder, TipRecipient, TrackingId,
             },
-            value_object::{OrderItemId, StreetAddress, StreetAddressBuilder, TrackingId},
         };
 
+        use chrono::{DateTime, Utc};
         use common::{
-            entity::{BaseEntity, BaseEntityBuilder},
+            entity::{AggregateRoot, AggregateRootBuilder, BaseEntity, BaseEntityBuilder},
+            id::IdGenerator,
             value_object::{
                 money::{Money, ZERO},
-                CustomerId, OrderStatus, RestaurantId,
+                CustomerId, OrderId, OrderStatus, RestaurantId,
             },
         };
         use derive_builder::Builder;
@@ -48,15 +92,59 @@ pub mod dto {
             street: String,
             postal_code: String,
             city: String,
+            latitude: Option<f64>,
+            longitude: Option<f64>,
         }
 
-        impl Into<StreetAddress> for OrderAddress {
-            fn into(self) -> StreetAddress {
+        impl OrderAddress {
+            pub fn into_street_address<G: IdGenerator>(self, id_generator: &G) -> StreetAddress {
+                let coordinates: Option<Coordinates> = match (self.latitude, self.longitude) {
+                    (Some(latitude), Some(longitude)) => Some(Coordinates {
+                        latitude,
+                        longitude,
+                    }),
+                    _ => None,
+                };
                 StreetAddressBuilder::default()
-                    .id(uuid::Uuid::new_v4())
+                    .id(id_generator.generate())
                     .street(self.street)
                     .city(self.city)
                     .postal_code(self.postal_code)
+                    .coordinates(coordinates)
+                    .build()
+                    .unwrap()
+            }
+        }
+
+        impl From<StreetAddress> for OrderAddress {
+            fn from(street_address: StreetAddress) -> Self {
+                Self {
+                    street: street_address.street,
+                    postal_code: street_address.postal_code,
+                    city: street_address.city,
+                    latitude: street_address
+                        .coordinates
+                        .as_ref()
+                        .map(|coordinates| coordinates.latitude),
+                    longitude: street_address
+                        .coordinates
+                        .as_ref()
+                        .map(|coordinates| coordinates.longitude),
+                }
+            }
+        }
+
+        #[derive(Clone)]
+        pub struct OrderItemOption {
+            pub group_id: uuid::Uuid,
+            pub option_id: uuid::Uuid,
+        }
+
+        impl From<OrderItemOption> for SelectedOption {
+            fn from(order_item_option: OrderItemOption) -> Self {
+                SelectedOptionBuilder::default()
+                    .group_id(order_item_option.group_id)
+                    .option_id(order_item_option.option_id)
                     .build()
                     .unwrap()
             }
@@ -70,16 +158,42 @@ pub mod dto {
             sub_total: f64,
             item_id: i64,
             order_id: uuid::Uuid,
+            options: Vec<OrderItemOption>,
+        }
+
+        impl OrderItem {
+            pub fn new(
+                product_id: uuid::Uuid,
+                quantity: u64,
+                price: Money,
+                options: Vec<OrderItemOption>,
+            ) -> Self {
+                Self {
+                    product_id,
+                    quantity,
+                    price: price.amount,
+                    sub_total: (price * quantity).amount,
+                    item_id: 0,
+                    order_id: uuid::Uuid::nil(),
+                    options,
+                }
+            }
+
+            pub fn sub_total(&self) -> Money {
+                Money::new(self.sub_total)
+            }
         }
 
-        impl Into<OrderItemEntity> for OrderItem {
-            fn into(self) -> OrderItemEntity {
-                let product: Product = Product::new(self.product_id, "".to_string(), ZERO);
-                let price = Money::new(self.price);
-                let sub_total = Money::new(self.sub_total);
-                let quantity = self.quantity;
+        impl From<OrderItem> for OrderItemEntity {
+            fn from(order_item: OrderItem) -> Self {
+                let product: Product = Product::new(order_item.product_id, "".to_string(), ZERO);
+                let price = Money::new(order_item.price);
+                let sub_total = Money::new(order_item.sub_total);
+                let quantity = order_item.quantity;
+                let selected_options: Vec<SelectedOption> =
+                    order_item.options.into_iter().map(|o| o.into()).collect();
                 let base_entity: BaseEntity<OrderItemId> = BaseEntityBuilder::default()
-                    .id(self.item_id.into())
+                    .id(order_item.item_id.into())
                     .build()
                     .unwrap();
                 OrderItemBuilder::default()
@@ -87,13 +201,53 @@ pub mod dto {
                     .price(price)
                     .sub_total(sub_total)
                     .quantity(quantity)
-                    .order_id(self.order_id.into())
+                    .order_id(order_item.order_id.into())
                     .base_entity(base_entity)
+                    .selected_options(selected_options)
+                    .build()
+                    .unwrap()
+            }
+        }
+
+        #[derive(Clone)]
+        pub enum OrderTipAmount {
+            Fixed(f64),
+            Percentage(f64),
+        }
+
+        #[derive(Clone)]
+        pub struct OrderTip {
+            pub amount: OrderTipAmount,
+            pub recipient: TipRecipient,
+        }
+
+        impl From<OrderTip> for Tip {
+            fn from(order_tip: OrderTip) -> Self {
+                let amount: TipAmount = match order_tip.amount {
+                    OrderTipAmount::Fixed(amount) => TipAmount::Fixed(Money::new(amount)),
+                    OrderTipAmount::Percentage(percentage) => TipAmount::Percentage(percentage),
+                };
+                TipBuilder::default()
+                    .amount(amount)
+                    .recipient(order_tip.recipient)
                     .build()
                     .unwrap()
             }
         }
 
+        impl From<Tip> for OrderTip {
+            fn from(tip: Tip) -> Self {
+                let amount: OrderTipAmount = match tip.amount {
+                    TipAmount::Fixed(amount) => OrderTipAmount::Fixed(amount.amount),
+                    TipAmount::Percentage(percentage) => OrderTipAmount::Percentage(percentage),
+                };
+                Self {
+                    amount,
+                    recipient: tip.recipient,
+                }
+            }
+        }
+
         #[derive(Clone)]
         pub struct CreateOrderCommand {
             pub customer_id: uuid::Uuid,
@@ -101,39 +255,56 @@ pub mod dto {
             pub price: f64,
             pub order_address: OrderAddress,
             pub items: Vec<OrderItem>,
+            pub requested_delivery_time: Option<DateTime<Utc>>,
+            pub tip: Option<OrderTip>,
+            pub order_id: Option<uuid::Uuid>,
         }
 
-        impl Into<domain_core::entity::Restaurant> for CreateOrderCommand {
-            fn into(self) -> domain_core::entity::Restaurant {
-                let products: Vec<Product> = self
+        impl From<CreateOrderCommand> for domain_core::entity::Restaurant {
+            fn from(create_order_command: CreateOrderCommand) -> Self {
+                let products: Vec<Product> = create_order_command
                     .items
                     .iter()
                     .map(|i| Product::new(i.product_id, "".to_string(), ZERO))
                     .collect();
-                Restaurant::new(self.restaurant_id, products, true)
+                Restaurant::new(create_order_command.restaurant_id, products, true)
             }
         }
 
-        impl Into<domain_core::entity::Order> for CreateOrderCommand {
-            fn into(self) -> domain_core::entity::Order {
+        impl CreateOrderCommand {
+            pub fn into_order<G: IdGenerator>(self, id_generator: &G) -> Order {
                 let customer_id: CustomerId = self.customer_id.into();
                 let restaurant_id: RestaurantId = self.restaurant_id.into();
-                let delivery_address: StreetAddress = self.order_address.into();
+                let delivery_address: StreetAddress =
+                    self.order_address.into_street_address(id_generator);
                 let price: Money = Money::new(self.price);
-                let tracking_id: TrackingId = uuid::Uuid::new_v4().into();
-                let order_id = uuid::Uuid::new_v4();
+                let tracking_id: TrackingId = id_generator.generate().into();
+                let order_id = match self.order_id {
+                    Some(order_id) => order_id,
+                    None => id_generator.generate(),
+                };
+                let aggregate_root: AggregateRoot<OrderId> = AggregateRootBuilder::default()
+                    .base_entity(
+                        BaseEntityBuilder::default()
+                            .id(order_id.into())
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap();
                 let order_items: Vec<OrderItemEntity> = self
                     .items
                     .into_iter()
                     .enumerate()
                     .map(|(index, item)| {
                         let mut new_item = item;
-                        new_item.order_id = order_id.into();
+                        new_item.order_id = order_id;
                         new_item.item_id = index as i64;
-                        return new_item.into();
+                        new_item.into()
                     })
                     .collect();
                 OrderBuilder::default()
+                    .aggregate_root(aggregate_root)
                     .customer_id(customer_id)
                     .restaurant_id(restaurant_id)
                     .street_address(delivery_address)
@@ -141,6 +312,9 @@ pub mod dto {
                     .tracking_id(tracking_id)
                     .order_status(OrderStatus::Pending)
                     .items(order_items)
+                    .failure_messages(vec![])
+                    .scheduled_for(self.requested_delivery_time)
+                    .tip(self.tip.map(|tip| tip.into()))
                     .build()
                     .unwrap()
             }
@@ -151,6 +325,8 @@ pub mod dto {
             pub order_tracking_id: uuid::Uuid,
             pub order_status: OrderStatus,
             pub message: String,
+            #[builder(default)]
+            pub dietary_warnings: Vec<String>,
         }
 
         impl From<Order> for CreateOrderResponse {
@@ -159,6 +335,448 @@ pub mod dto {
                     order_tracking_id: o.tracking_id.into(),
                     order_status: o.order_status,
                     message: "Order created successfully".to_string(),
+                    dietary_warnings: o
+                        .dietary_conflicts
+                        .iter()
+                        .map(|conflict| conflict.to_string())
+                        .collect(),
+                }
+            }
+        }
+    }
+
+    pub mod reorder {
+        use common::{error::OrderDomainError, value_object::money::Money};
+        use derive_builder::Builder;
+        use domain_core::entity::{Order, Restaurant};
+
+        use super::create::{CreateOrderCommand, CreateOrderResponse, OrderItem, OrderItemOption};
+
+        #[derive(Clone, Builder)]
+        pub struct ReorderCommand {
+            pub order_tracking_id: uuid::Uuid,
+            pub customer_id: uuid::Uuid,
+            #[builder(default)]
+            pub confirm: bool,
+            #[builder(default)]
+            pub expected_price: Option<f64>,
+        }
+
+        #[derive(Clone)]
+        pub struct UnavailableReorderItem {
+            pub product_id: uuid::Uuid,
+            pub quantity: u64,
+            pub reason: String,
+        }
+
+        #[derive(Clone)]
+        pub struct RepricedReorderItem {
+            pub product_id: uuid::Uuid,
+            pub previous_price: Money,
+            pub current_price: Money,
+        }
+
+        #[derive(Clone)]
+        pub struct ReorderQuote {
+            pub original_order_tracking_id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub unavailable_items: Vec<UnavailableReorderItem>,
+            pub repriced_items: Vec<RepricedReorderItem>,
+            pub price: Money,
+        }
+
+        #[derive(Clone)]
+        pub enum ReorderResponse {
+            Quote(ReorderQuote),
+            Created(CreateOrderResponse),
+        }
+
+        impl ReorderQuote {
+            pub fn for_order(order: &Order, restaurant: &Restaurant) -> (Self, CreateOrderCommand) {
+                let mut items: Vec<OrderItem> = vec![];
+                let mut unavailable_items: Vec<UnavailableReorderItem> = vec![];
+                let mut repriced_items: Vec<RepricedReorderItem> = vec![];
+                let mut price = Money::new(0.0);
+                for item in order.items.iter() {
+                    let product_id: uuid::Uuid = item.product.clone().into();
+                    let unavailable_item = |reason: &str| UnavailableReorderItem {
+                        product_id,
+                        quantity: item.quantity,
+                        reason: reason.to_string(),
+                    };
+                    let product = match restaurant
+                        .products
+                        .iter()
+                        .find(|product| product.base_entity.id.base_id.value == product_id)
+                    {
+                        Some(product) => product,
+                        None => {
+                            unavailable_items
+                                .push(unavailable_item("product is no longer offered"));
+                            continue;
+                        }
+                    };
+                    if !product.availability.can_fulfill(item.quantity) {
+                        unavailable_items.push(unavailable_item("product is unavailable"));
+                        continue;
+                    }
+                    let mut current_price = product.price.clone();
+                    let mut options: Vec<OrderItemOption> = vec![];
+                    for selected_option in item.selected_options.iter() {
+                        if let Some(option) =
+                            product.find_option(selected_option.group_id, selected_option.option_id)
+                        {
+                            current_price += option.price_delta.clone();
+                            options.push(OrderItemOption {
+                                group_id: selected_option.group_id,
+                                option_id: selected_option.option_id,
+                            });
+                        }
+                    }
+                    if options.len() != item.selected_options.len() {
+                        unavailable_items
+                            .push(unavailable_item("a selected option is no longer offered"));
+                        continue;
+                    }
+                    if current_price != item.price {
+                        repriced_items.push(RepricedReorderItem {
+                            product_id,
+                            previous_price: item.price.clone(),
+                            current_price: current_price.clone(),
+                        });
+                    }
+                    let order_item =
+                        OrderItem::new(product_id, item.quantity, current_price, options);
+                    price += order_item.sub_total();
+                    items.push(order_item);
+                }
+                let create_order_command = CreateOrderCommand {
+                    customer_id: order.customer_id.clone().into(),
+                    restaurant_id: order.restaurant_id.clone().into(),
+                    price: price.amount,
+                    order_address: order.street_address.clone().into(),
+                    items,
+                    requested_delivery_time: None,
+                    tip: order.tip.clone().map(|tip| tip.into()),
+                    order_id: None,
+                };
+                let reorder_quote = Self {
+                    original_order_tracking_id: order.tracking_id.clone().into(),
+                    restaurant_id: order.restaurant_id.clone().into(),
+                    unavailable_items,
+                    repriced_items,
+                    price,
+                };
+                (reorder_quote, create_order_command)
+            }
+
+            pub fn confirm(&self, expected_price: Option<f64>) -> Result<(), OrderDomainError> {
+                match expected_price {
+                    Some(expected_price) if Money::new(expected_price) == self.price => Ok(()),
+                    _ => Err(OrderDomainError::ReorderQuoteChanged(self.price.amount)),
+                }
+            }
+        }
+    }
+
+    pub mod checkout {
+        use common::value_object::money::Money;
+        use domain_core::checkout::{Checkout, CheckoutCancellationPolicy};
+
+        use super::create::{CreateOrderCommand, CreateOrderResponse, OrderAddress, OrderItem};
+
+        #[derive(Clone)]
+        pub struct BasketItem {
+            pub restaurant_id: uuid::Uuid,
+            pub item: OrderItem,
+        }
+
+        #[derive(Clone)]
+        pub struct CheckoutBasketCommand {
+            pub customer_id: uuid::Uuid,
+            pub order_address: OrderAddress,
+            pub items: Vec<BasketItem>,
+            pub cancellation_policy: CheckoutCancellationPolicy,
+        }
+
+        impl CheckoutBasketCommand {
+            pub fn into_create_order_commands(self) -> Vec<CreateOrderCommand> {
+                let mut create_order_commands: Vec<CreateOrderCommand> = vec![];
+                for basket_item in self.items {
+                    let sub_total = basket_item.item.sub_total();
+                    match create_order_commands
+                        .iter_mut()
+                        .find(|command| command.restaurant_id == basket_item.restaurant_id)
+                    {
+                        Some(command) => {
+                            command.price = (Money::new(command.price) + sub_total).amount;
+                            command.items.push(basket_item.item);
+                        }
+                        None => create_order_commands.push(CreateOrderCommand {
+                            customer_id: self.customer_id,
+                            restaurant_id: basket_item.restaurant_id,
+                            price: sub_total.amount,
+                            order_address: self.order_address.clone(),
+                            items: vec![basket_item.item],
+                            requested_delivery_time: None,
+                            tip: None,
+                            order_id: None,
+                        }),
+                    }
+                }
+                create_order_commands
+            }
+        }
+
+        #[derive(Clone)]
+        pub struct CheckoutBasketResponse {
+            pub checkout_id: uuid::Uuid,
+            pub price: Money,
+            pub orders: Vec<CreateOrderResponse>,
+        }
+
+        impl From<Checkout> for CheckoutBasketResponse {
+            fn from(c: Checkout) -> Self {
+                Self {
+                    checkout_id: c.id,
+                    price: c.total_price(),
+                    orders: c.orders.into_iter().map(|order| order.into()).collect(),
+                }
+            }
+        }
+    }
+
+    pub mod subscription {
+        use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+        use common::{error::OrderDomainError, id::IdGenerator, value_object::money::Money};
+        use domain_core::subscription::{
+            OrderSubscription, OrderSubscriptionBuilder, SubscriptionSchedule, SubscriptionStatus,
+        };
+
+        use super::create::{CreateOrderCommand, OrderItem, OrderItemOption};
+
+        #[derive(Clone)]
+        pub struct CreateSubscriptionCommand {
+            pub template: CreateOrderCommand,
+            pub weekdays: Vec<Weekday>,
+            pub delivery_time: NaiveTime,
+            pub time_zone: String,
+            pub ends_on: Option<NaiveDate>,
+        }
+
+        impl CreateSubscriptionCommand {
+            pub fn into_subscription<G: IdGenerator>(
+                self,
+                id_generator: &G,
+            ) -> Result<OrderSubscription, OrderDomainError> {
+                let schedule = SubscriptionSchedule::new(
+                    self.weekdays,
+                    self.delivery_time,
+                    self.time_zone.as_str(),
+                )?;
+                let template = self.template.into_order(id_generator);
+                Ok(OrderSubscriptionBuilder::default()
+                    .id(id_generator.generate())
+                    .customer_id(template.customer_id)
+                    .restaurant_id(template.restaurant_id)
+                    .street_address(template.street_address)
+                    .items(template.items)
+                    .tip(template.tip)
+                    .schedule(schedule)
+                    .ends_on(self.ends_on)
+                    .build()
+                    .unwrap())
+            }
+        }
+
+        #[derive(Clone)]
+        pub struct ChangeSubscriptionStatusCommand {
+            pub subscription_id: uuid::Uuid,
+            pub customer_id: uuid::Uuid,
+        }
+
+        #[derive(Clone)]
+        pub struct SubscriptionResponse {
+            pub subscription_id: uuid::Uuid,
+            pub status: SubscriptionStatus,
+            pub weekdays: Vec<Weekday>,
+            pub delivery_time: NaiveTime,
+            pub ends_on: Option<NaiveDate>,
+            pub last_occurrence_on: Option<NaiveDate>,
+        }
+
+        impl From<OrderSubscription> for SubscriptionResponse {
+            fn from(s: OrderSubscription) -> Self {
+                Self {
+                    subscription_id: s.id,
+                    status: s.status,
+                    weekdays: s.schedule.weekdays,
+                    delivery_time: s.schedule.delivery_time,
+                    ends_on: s.ends_on,
+                    last_occurrence_on: s.last_occurrence_on,
+                }
+            }
+        }
+
+        pub fn occurrence_order_command(
+            subscription: &OrderSubscription,
+            delivery_at: DateTime<Utc>,
+            order_id: uuid::Uuid,
+        ) -> CreateOrderCommand {
+            let mut price = Money::new(0.0);
+            let items: Vec<OrderItem> = subscription
+                .items
+                .iter()
+                .map(|item| {
+                    let options: Vec<OrderItemOption> = item
+                        .selected_options
+                        .iter()
+                        .map(|selected_option| OrderItemOption {
+                            group_id: selected_option.group_id,
+                            option_id: selected_option.option_id,
+                        })
+                        .collect();
+                    let order_item = OrderItem::new(
+                        item.product.clone().into(),
+                        item.quantity,
+                        item.price.clone(),
+                        options,
+                    );
+                    price += order_item.sub_total();
+                    order_item
+                })
+                .collect();
+            CreateOrderCommand {
+                customer_id: subscription.customer_id.clone().into(),
+                restaurant_id: subscription.restaurant_id.clone().into(),
+                price: price.amount,
+                order_address: subscription.street_address.clone().into(),
+                items,
+                requested_delivery_time: Some(delivery_at),
+                tip: subscription.tip.clone().map(|tip| tip.into()),
+                order_id: Some(order_id),
+            }
+        }
+    }
+
+    pub mod group {
+        use common::value_object::money::Money;
+        use domain_core::{
+            entity::Product,
+            group_order::{GroupOrder, GroupOrderStatus, GroupParticipant},
+        };
+
+        use super::create::{OrderAddress, OrderItem};
+
+        #[derive(Clone)]
+        pub struct OpenGroupOrderCommand {
+            pub host_id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub order_address: OrderAddress,
+            pub participant_ids: Vec<uuid::Uuid>,
+        }
+
+        #[derive(Clone)]
+        pub struct AddGroupOrderItemsCommand {
+            pub group_order_id: uuid::Uuid,
+            pub customer_id: uuid::Uuid,
+            pub items: Vec<OrderItem>,
+        }
+
+        #[derive(Clone)]
+        pub struct LockGroupOrderCommand {
+            pub group_order_id: uuid::Uuid,
+            pub host_id: uuid::Uuid,
+        }
+
+        #[derive(Clone)]
+        pub struct SubmitGroupOrderCommand {
+            pub group_order_id: uuid::Uuid,
+            pub host_id: uuid::Uuid,
+        }
+
+        #[derive(Clone)]
+        pub struct GroupParticipantResponse {
+            pub customer_id: uuid::Uuid,
+            pub item_count: usize,
+            pub items_price: Money,
+        }
+
+        impl From<GroupParticipant> for GroupParticipantResponse {
+            fn from(participant: GroupParticipant) -> Self {
+                Self {
+                    customer_id: participant.customer_uuid(),
+                    item_count: participant.items.len(),
+                    items_price: participant.items_price(),
+                }
+            }
+        }
+
+        #[derive(Clone)]
+        pub struct GroupOrderResponse {
+            pub group_order_id: uuid::Uuid,
+            pub host_id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub status: GroupOrderStatus,
+            pub participants: Vec<GroupParticipantResponse>,
+            pub order_id: Option<uuid::Uuid>,
+        }
+
+        impl From<GroupOrder> for GroupOrderResponse {
+            fn from(g: GroupOrder) -> Self {
+                Self {
+                    group_order_id: g.id,
+                    host_id: g.host_id.into(),
+                    restaurant_id: g.restaurant_id.into(),
+                    status: g.status,
+                    participants: g
+                        .participants
+                        .into_iter()
+                        .map(|participant| participant.into())
+                        .collect(),
+                    order_id: g.order_id,
+                }
+            }
+        }
+
+        pub fn group_order_products(group_order: &GroupOrder) -> Vec<Product> {
+            group_order
+                .participants
+                .iter()
+                .flat_map(|participant| participant.items.iter())
+                .map(|item| item.product.clone())
+                .collect()
+        }
+    }
+
+    pub mod adjust {
+        use common::value_object::money::Money;
+        use derive_builder::Builder;
+        use domain_core::{event::OrderItemAdjusted, value_object::AdjustmentReason};
+
+        #[derive(Clone)]
+        pub struct AdjustOrderItemCommand {
+            pub order_tracking_id: uuid::Uuid,
+            pub order_item_id: i64,
+            pub quantity: u64,
+            pub reason: AdjustmentReason,
+        }
+
+        #[derive(Clone, Builder)]
+        pub struct AdjustOrderItemResponse {
+            pub order_tracking_id: uuid::Uuid,
+            pub adjustment_id: uuid::Uuid,
+            pub refund_amount: Money,
+            pub order_price: Money,
+        }
+
+        impl From<OrderItemAdjusted> for AdjustOrderItemResponse {
+            fn from(e: OrderItemAdjusted) -> Self {
+                Self {
+                    order_tracking_id: e.order.tracking_id.clone().into(),
+                    adjustment_id: e.adjustment.id,
+                    refund_amount: e.adjustment.refund_amount,
+                    order_price: e.order.total_price(),
                 }
             }
         }
@@ -166,7 +784,36 @@ pub mod dto {
 
     pub mod message {
         use chrono::prelude::*;
-        use common::value_object::{OrderApprovalStatus, PaymentStatus};
+        use common::{
+            error::OrderDomainError,
+            event::Causation,
+            validation::ValidationReport,
+            value_object::{OrderApprovalStatus, PaymentStatus},
+        };
+
+        fn parse_uuid(field: &str, value: &str) -> Result<uuid::Uuid, OrderDomainError> {
+            uuid::Uuid::parse_str(value).map_err(|_| {
+                let mut report = ValidationReport::new();
+                report.add(
+                    field,
+                    format!("the {} is not a valid uuid", field.replace('_', " ")),
+                );
+                OrderDomainError::ValidationFailed(report)
+            })
+        }
+
+        fn parse_order_and_causation(
+            id: &str,
+            saga_id: &str,
+            order_id: &str,
+        ) -> Result<(uuid::Uuid, Causation), OrderDomainError> {
+            let order_id = parse_uuid("order_id", order_id)?;
+            let saga_id = parse_uuid("saga_id", saga_id)?;
+            Ok((
+                order_id,
+                Causation::new(saga_id, uuid::Uuid::parse_str(id).ok()),
+            ))
+        }
 
         pub struct PaymentResponse {
             pub id: String,
@@ -180,6 +827,16 @@ pub mod dto {
             pub failure_messages: Vec<String>,
         }
 
+        impl PaymentResponse {
+            pub fn order_and_causation(&self) -> Result<(uuid::Uuid, Causation), OrderDomainError> {
+                parse_order_and_causation(&self.id, &self.saga_id, &self.order_id)
+            }
+
+            pub fn customer_uuid(&self) -> Result<uuid::Uuid, OrderDomainError> {
+                parse_uuid("customer_id", &self.customer_id)
+            }
+        }
+
         pub struct RestaurantApprovalResponse {
             pub id: String,
             pub saga_id: String,
@@ -189,310 +846,4815 @@ pub mod dto {
             pub order_approval_status: OrderApprovalStatus,
             pub failure_messages: Vec<String>,
         }
+
+        impl RestaurantApprovalResponse {
+            pub fn order_and_causation(&self) -> Result<(uuid::Uuid, Causation), OrderDomainError> {
+                parse_order_and_causation(&self.id, &self.saga_id, &self.order_id)
+            }
+        }
     }
 
     pub mod track {
-        use common::value_object::OrderStatus;
+        use common::value_object::{money::Money, OrderStatus};
         use derive_builder::Builder;
-        use domain_core::entity::Order;
+        use domain_core::{entity::Order, value_object::TipRecipient};
 
         pub struct TrackOrderQuery {
             pub order_tracking_id: uuid::Uuid,
         }
 
+        pub struct SubscribeOrderStatusQuery {
+            pub order_tracking_id: uuid::Uuid,
+            pub customer_id: uuid::Uuid,
+            pub last_event_id: Option<u64>,
+        }
+
+        #[derive(Clone, Builder)]
+        pub struct OrderStatusChange {
+            pub order_status: OrderStatus,
+            pub changed_at: chrono::DateTime<chrono::Utc>,
+        }
+
         #[derive(Clone, Builder)]
         pub struct TrackOrderResponse {
             pub order_tracking_id: uuid::Uuid,
             pub order_status: OrderStatus,
             pub failure_messages: Vec<String>,
+            pub tip: Money,
+            pub tip_recipient: Option<TipRecipient>,
+            #[builder(default)]
+            pub status_history: Vec<OrderStatusChange>,
+            #[builder(default)]
+            pub estimated_delivery_at: Option<chrono::DateTime<chrono::Utc>>,
         }
 
         impl From<Order> for TrackOrderResponse {
             fn from(o: Order) -> Self {
                 Self {
+                    tip: o.tip_amount(),
+                    tip_recipient: o.tip.map(|tip| tip.recipient),
                     order_tracking_id: o.tracking_id.into(),
+                    status_history: o
+                        .status_changed_at
+                        .map(|changed_at| OrderStatusChange {
+                            order_status: o.order_status.clone(),
+                            changed_at,
+                        })
+                        .into_iter()
+                        .collect(),
+                    estimated_delivery_at: o.estimated_delivery_at.or(o.scheduled_for),
                     order_status: o.order_status,
                     failure_messages: o.failure_messages,
                 }
             }
         }
     }
-}
 
-pub mod ports {
-    pub mod input {
-        pub mod message {
-            pub mod listener {
-                pub mod payment {
-                    use crate::dto::message::PaymentResponse;
+    pub mod list {
+        use std::{fmt::Display, str::FromStr};
 
-                    #[async_trait::async_trait]
-                    pub trait PaymentResponseListener {
-                        async fn payment_completed(&self, response: PaymentResponse);
-                        async fn payment_cancelled(&self, response: PaymentResponse);
-                    }
-                }
+        use common::{
+            error::OrderDomainError,
+            value_object::{money::Money, OrderStatus},
+        };
+        use derive_builder::Builder;
+        use domain_core::entity::Order;
 
-                pub mod restaurant {
-                    use crate::dto::message::RestaurantApprovalResponse;
+        pub const DEFAULT_PAGE_SIZE: u64 = 20;
+        pub const MAX_PAGE_SIZE: u64 = 100;
 
-                    #[async_trait::async_trait]
-                    pub trait RestaurantApprovalResponseMessageListener {
-                        async fn order_approved(&self, response: RestaurantApprovalResponse);
-                        async fn order_rejected(&self, response: RestaurantApprovalResponse);
-                    }
-                }
+        #[derive(Clone, Copy, Default, PartialEq)]
+        pub enum OrderSort {
+            #[default]
+            NewestFirst,
+            OldestFirst,
+        }
+
+        #[derive(Clone, Copy, PartialEq)]
+        pub struct OrderCursor {
+            pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+            pub order_id: uuid::Uuid,
+        }
+
+        impl Display for OrderCursor {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let created_at = self
+                    .created_at
+                    .map(|created_at| created_at.to_rfc3339())
+                    .unwrap_or_default();
+                write!(f, "{}|{}", created_at, self.order_id)
             }
         }
 
-        pub mod service {
-            use common::error::OrderDomainError;
+        impl FromStr for OrderCursor {
+            type Err = OrderDomainError;
 
-            use crate::dto::{
-                create::{CreateOrderCommand, CreateOrderResponse},
-                track::{TrackOrderQuery, TrackOrderResponse},
-            };
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let invalid_cursor = || OrderDomainError::InvalidCursor(s.to_string());
+                let (created_at, order_id) = s.split_once('|').ok_or_else(invalid_cursor)?;
+                let created_at = match created_at {
+                    "" => None,
+                    created_at => Some(
+                        chrono::DateTime::parse_from_rfc3339(created_at)
+                            .map_err(|_| invalid_cursor())?
+                            .with_timezone(&chrono::Utc),
+                    ),
+                };
+                Ok(Self {
+                    created_at,
+                    order_id: order_id.parse().map_err(|_| invalid_cursor())?,
+                })
+            }
+        }
 
-            #[async_trait::async_trait]
-            pub trait OrderApplicationService: std::marker::Sync {
-                async fn create_order(
-                    &self,
-                    command: CreateOrderCommand,
-                ) -> Result<CreateOrderResponse, OrderDomainError>;
-                async fn track_order(
-                    &self,
-                    query: TrackOrderQuery,
-                ) -> Result<TrackOrderResponse, OrderDomainError>;
+        #[derive(Clone, Builder)]
+        pub struct ListCustomerOrdersQuery {
+            pub customer_id: uuid::Uuid,
+            #[builder(default)]
+            pub cursor: Option<String>,
+            #[builder(default = "DEFAULT_PAGE_SIZE")]
+            pub limit: u64,
+        }
+
+        #[derive(Clone, Builder)]
+        pub struct SearchOrdersQuery {
+            #[builder(default)]
+            pub customer_id: Option<uuid::Uuid>,
+            #[builder(default)]
+            pub restaurant_id: Option<uuid::Uuid>,
+            #[builder(default)]
+            pub order_status: Option<OrderStatus>,
+            #[builder(default)]
+            pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+            #[builder(default)]
+            pub created_to: Option<chrono::DateTime<chrono::Utc>>,
+            #[builder(default)]
+            pub sort: OrderSort,
+            #[builder(default)]
+            pub cursor: Option<String>,
+            #[builder(default = "DEFAULT_PAGE_SIZE")]
+            pub limit: u64,
+        }
+
+        impl From<ListCustomerOrdersQuery> for SearchOrdersQuery {
+            fn from(query: ListCustomerOrdersQuery) -> Self {
+                SearchOrdersQueryBuilder::default()
+                    .customer_id(Some(query.customer_id))
+                    .cursor(query.cursor)
+                    .limit(query.limit)
+                    .build()
+                    .unwrap()
             }
         }
-    }
 
-    pub mod output {
-        pub mod message {
-            pub mod publisher {
-                pub mod payment {
-                    use domain_core::{
-                        entity::Order,
-                        event::{OrderCancelled, OrderCreated},
-                    };
+        #[derive(Clone, Builder)]
+        pub struct OrderSearchCriteria {
+            #[builder(default)]
+            pub customer_id: Option<uuid::Uuid>,
+            #[builder(default)]
+            pub restaurant_id: Option<uuid::Uuid>,
+            #[builder(default)]
+            pub order_status: Option<OrderStatus>,
+            #[builder(default)]
+            pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+            #[builder(default)]
+            pub created_to: Option<chrono::DateTime<chrono::Utc>>,
+            #[builder(default)]
+            pub sort: OrderSort,
+            #[builder(default)]
+            pub after: Option<OrderCursor>,
+            pub limit: u64,
+        }
 
-                    use common::event::publisher::DomainEventPublisher;
+        impl TryFrom<SearchOrdersQuery> for OrderSearchCriteria {
+            type Error = OrderDomainError;
 
-                    pub trait OrderCancelledPaymentRequestMessagePublisher:
-                        DomainEventPublisher<Order, OrderCancelled>
-                    {
-                    }
+            fn try_from(query: SearchOrdersQuery) -> Result<Self, Self::Error> {
+                Ok(Self {
+                    customer_id: query.customer_id,
+                    restaurant_id: query.restaurant_id,
+                    order_status: query.order_status,
+                    created_from: query.created_from,
+                    created_to: query.created_to,
+                    sort: query.sort,
+                    after: query.cursor.map(|cursor| cursor.parse()).transpose()?,
+                    limit: query.limit.clamp(1, MAX_PAGE_SIZE),
+                })
+            }
+        }
 
-                    pub trait OrderCreatedPaymentRequestMessagePublisher:
-                        DomainEventPublisher<Order, OrderCreated>
-                    {
-                    }
+        #[derive(Clone, Builder)]
+        pub struct OrderSummaryResponse {
+            pub order_tracking_id: uuid::Uuid,
+            pub customer_id: uuid::Uuid,
+            pub restaurant_id: uuid::Uuid,
+            pub order_status: OrderStatus,
+            pub price: Money,
+            pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+        }
+
+        impl From<Order> for OrderSummaryResponse {
+            fn from(o: Order) -> Self {
+                Self {
+                    order_tracking_id: o.tracking_id.into(),
+                    customer_id: o.customer_id.into(),
+                    restaurant_id: o.restaurant_id.into(),
+                    order_status: o.order_status,
+                    price: o.price,
+                    created_at: o.created_at,
                 }
+            }
+        }
 
-                pub mod restaurant_approval {
-                    use domain_core::{entity::Order, event::OrderPaid};
+        #[derive(Clone, Builder)]
+        pub struct OrderPageResponse {
+            pub orders: Vec<OrderSummaryResponse>,
+            pub next_cursor: Option<String>,
+        }
+    }
 
-                    use common::event::publisher::DomainEventPublisher;
+    pub mod batch {
+        use common::error::OrderDomainError;
 
-                    pub trait OrderPaidRestaurantRequestMessagePublisher:
-                        DomainEventPublisher<Order, OrderPaid>
-                    {
-                    }
+        pub struct BatchFailure {
+            pub id: uuid::Uuid,
+            pub error: OrderDomainError,
+        }
+
+        #[derive(Default)]
+        pub struct BatchResult {
+            pub succeeded: usize,
+            pub failures: Vec<BatchFailure>,
+        }
+
+        impl BatchResult {
+            pub fn record(&mut self, id: uuid::Uuid, result: Result<(), OrderDomainError>) {
+                match result {
+                    Ok(()) => self.succeeded += 1,
+                    Err(error) => self.failures.push(BatchFailure { id, error }),
                 }
             }
+
+            pub fn merge(mut self, other: BatchResult) -> Self {
+                self.succeeded += other.succeeded;
+                self.failures.extend(other.failures);
+                self
+            }
         }
+    }
 
-        pub mod repository {
-            use common::error::OrderDomainError;
-            use domain_core::{
-                entity::{Customer, Order, Restaurant},
-                value_object::TrackingId,
-            };
+    pub mod error {
+        use common::error::OrderDomainError;
+        use derive_builder::Builder;
 
-            #[async_trait::async_trait]
-            pub trait OrderRepository: Send + Sync {
-                async fn save(&self, order: Order) -> Result<Order, OrderDomainError>;
-                async fn find_by_tracking_id(
-                    &self,
-                    id: TrackingId,
-                ) -> Result<Order, OrderDomainError>;
+        #[derive(Clone, Builder)]
+        pub struct FieldErrorResponse {
+            pub field: String,
+            pub message: String,
+        }
+
+        #[derive(Clone, Builder)]
+        pub struct ErrorResponse {
+            pub message: String,
+            #[builder(default)]
+            pub field_errors: Vec<FieldErrorResponse>,
+            #[builder(default)]
+            pub unavailable_products: Vec<uuid::Uuid>,
+        }
+
+        impl From<OrderDomainError> for ErrorResponse {
+            fn from(error: OrderDomainError) -> Self {
+                let message = error.to_string();
+                match error {
+                    OrderDomainError::ValidationFailed(report) => Self {
+                        message: String::from("the order is invalid"),
+                        unavailable_products: report.unavailable_products(),
+                        field_errors: report
+                            .errors
+                            .into_iter()
+                            .map(|field_error| FieldErrorResponse {
+                                field: field_error.field,
+                                message: field_error.message,
+                            })
+                            .collect(),
+                    },
+                    OrderDomainError::ProductsUnavailable(unavailable_products) => Self {
+                        message,
+                        field_errors: vec![],
+                        unavailable_products,
+                    },
+                    _ => Self {
+                        message,
+                        field_errors: vec![],
+                        unavailable_products: vec![],
+                    },
+                }
             }
+        }
+    }
+}
 
-            #[async_trait::async_trait]
-            pub trait CustomerRepository: Send + Sync {
-                async fn find_customer(
-                    &self,
-                    customer_id: uuid::Uuid,
-                ) -> Result<Customer, OrderDomainError>;
+pub mod config {
+    use common::{error::OrderDomainError, value_object::money::Money};
+    use derive_builder::Builder;
+    use domain_core::{
+        risk::{BlockedAddress, RiskDecision, RiskRule, RiskRulesEngine},
+        tax::{TableTaxCalculator, TaxRule},
+    };
+    use serde::Deserialize;
+
+    #[derive(Clone, Builder)]
+    pub struct OrderLimits {
+        pub max_open_orders_per_customer: u64,
+    }
+
+    impl Default for OrderLimits {
+        fn default() -> Self {
+            Self {
+                max_open_orders_per_customer: 5,
             }
+        }
+    }
 
-            #[async_trait::async_trait]
-            pub trait RestaurantRepository: Send + Sync {
-                async fn find_restaurant_info(
-                    &self,
-                    restaurant: Restaurant,
-                ) -> Result<Restaurant, OrderDomainError>;
+    #[derive(Clone, Builder)]
+    pub struct RetryPolicy {
+        pub max_attempts: u32,
+        pub backoff: std::time::Duration,
+    }
+
+    impl RetryPolicy {
+        pub fn backoff_before(&self, attempt: u32) -> std::time::Duration {
+            self.backoff
+                .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(2)))
+        }
+    }
+
+    impl Default for RetryPolicy {
+        fn default() -> Self {
+            Self {
+                max_attempts: 3,
+                backoff: std::time::Duration::from_millis(50),
             }
         }
     }
-}
 
-pub struct OrderCreateHelper<
-    ODS: OrderDomainService,
-    OR: OrderRepository,
-    CR: CustomerRepository,
-    RR: RestaurantRepository,
-> {
-    order_domain_service: ODS,
-    order_repository: OR,
-    customer_repository: CR,
-    restaurant_repository: RR,
-}
+    #[derive(Clone, Builder)]
+    pub struct SagaTimeouts {
+        pub payment_response: chrono::Duration,
+        pub restaurant_approval_response: chrono::Duration,
+    }
 
-impl<
-        ODS: OrderDomainService,
-        OR: OrderRepository,
-        CR: CustomerRepository,
-        RR: RestaurantRepository,
-    > OrderCreateHelper<ODS, OR, CR, RR>
-{
-    pub async fn persist_order(
-        &self,
-        command: CreateOrderCommand,
-    ) -> Result<OrderCreated, OrderDomainError> {
-        self.check_customer(command.customer_id).await?;
-        let restaurant = self.check_restaurant(command.clone()).await?;
-        let order: Order = command.into();
-        let order_created_event = self
-            .order_domain_service
-            .validate_and_initiate_order(order, restaurant)?;
-        Ok(order_created_event)
+    #[derive(Clone, Builder)]
+    pub struct SubscriptionPolicy {
+        pub materialize_lead_time: chrono::Duration,
     }
 
-    pub async fn check_customer(&self, customer_id: uuid::Uuid) -> Result<(), OrderDomainError> {
-        let _ = self.customer_repository.find_customer(customer_id).await?;
-        Ok(())
+    impl Default for SubscriptionPolicy {
+        fn default() -> Self {
+            Self {
+                materialize_lead_time: chrono::Duration::hours(3),
+            }
+        }
     }
 
-    pub async fn check_restaurant(
-        &self,
-        command: CreateOrderCommand,
-    ) -> Result<Restaurant, OrderDomainError> {
-        let restaurant: Restaurant = command.into();
-        let _ = self
-            .restaurant_repository
-            .find_restaurant_info(restaurant.clone())
-            .await?;
-        Ok(restaurant)
+    #[derive(Clone, Builder)]
+    pub struct LiveStatusConfig {
+        pub heartbeat_interval: std::time::Duration,
+        pub replay_capacity: usize,
     }
 
-    pub async fn save_order(&self, order: Order) -> Result<Order, OrderDomainError> {
-        self.order_repository.save(order).await
+    impl Default for LiveStatusConfig {
+        fn default() -> Self {
+            Self {
+                heartbeat_interval: std::time::Duration::from_secs(15),
+                replay_capacity: 1024,
+            }
+        }
     }
-}
 
-pub struct OrderCreateCommandHandler<
-    OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
-    ODS: OrderDomainService,
-    OR: OrderRepository,
-    CR: CustomerRepository,
-    RR: RestaurantRepository,
-> {
-    order_create_helper: OrderCreateHelper<ODS, OR, CR, RR>,
-    order_created_payment_request_message_publisher: OCPRMP,
-}
+    impl Default for SagaTimeouts {
+        fn default() -> Self {
+            Self {
+                payment_response: chrono::Duration::minutes(10),
+                restaurant_approval_response: chrono::Duration::minutes(15),
+            }
+        }
+    }
 
-impl<
-        OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
-        ODS: OrderDomainService,
-        OR: OrderRepository,
-        CR: CustomerRepository,
-        RR: RestaurantRepository,
-    > OrderCreateCommandHandler<OCPRMP, ODS, OR, CR, RR>
-{
-    pub async fn create_order(
-        &self,
-        command: CreateOrderCommand,
-    ) -> Result<CreateOrderResponse, OrderDomainError> {
-        let order_created_event = self.order_create_helper.persist_order(command).await?;
-        self.order_created_payment_request_message_publisher
-            .publish(order_created_event.clone())
-            .await;
-        let create_order_response: CreateOrderResponse = order_created_event.order.into();
-        Ok(create_order_response)
+    #[derive(Clone, Deserialize)]
+    pub struct TaxRuleConfig {
+        #[serde(default)]
+        pub city: Option<String>,
+        #[serde(default)]
+        pub postal_code_prefix: Option<String>,
+        pub tax_category: String,
+        pub rate: f64,
     }
-}
 
-pub struct OrderTrackCommandHandler<OR: OrderRepository> {
-    order_repository: OR,
-}
+    impl TaxRuleConfig {
+        pub fn into_rule(self) -> Result<TaxRule, OrderDomainError> {
+            let tax_category = self.tax_category.parse().map_err(|_| {
+                OrderDomainError::InvalidTaxRule(format!(
+                    "unknown tax category {}",
+                    self.tax_category
+                ))
+            })?;
+            if !(0.0..=1.0).contains(&self.rate) {
+                return Err(OrderDomainError::InvalidTaxRule(format!(
+                    "rate {} is out of range",
+                    self.rate
+                )));
+            }
+            Ok(TaxRule {
+                city: self.city,
+                postal_code_prefix: self.postal_code_prefix,
+                tax_category,
+                rate: self.rate,
+            })
+        }
+    }
 
-impl<OR: OrderRepository> OrderTrackCommandHandler<OR> {
-    pub async fn track_order(
-        &self,
-        query: TrackOrderQuery,
-    ) -> Result<TrackOrderResponse, OrderDomainError> {
-        let tracking_id: TrackingId = query.order_tracking_id.into();
-        let res = self
-            .order_repository
-            .find_by_tracking_id(tracking_id)
-            .await?;
-        Ok(res.into())
+    #[derive(Clone, Deserialize)]
+    pub struct TaxRulesConfig {
+        pub rules: Vec<TaxRuleConfig>,
+        #[serde(default)]
+        pub default_rate: f64,
     }
-}
 
-pub struct OrderApplicationServiceImpl<
-    OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
-    ODS: OrderDomainService,
-    OR: OrderRepository,
-    CR: CustomerRepository,
-    RR: RestaurantRepository,
-> {
-    order_create_command_helper: OrderCreateCommandHandler<OCPRMP, ODS, OR, CR, RR>,
-    order_track_comman_helper: OrderTrackCommandHandler<OR>,
-}
+    impl TaxRulesConfig {
+        pub fn from_json(json: &str) -> Result<Self, OrderDomainError> {
+            serde_json::from_str(json)
+                .map_err(|error| OrderDomainError::InvalidTaxRule(error.to_string()))
+        }
 
-#[async_trait::async_trait]
-impl<
-        OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
-        ODS: OrderDomainService,
-        OR: OrderRepository,
-        CR: CustomerRepository,
-        RR: RestaurantRepository,
-    > OrderApplicationService for OrderApplicationServiceImpl<OCPRMP, ODS, OR, CR, RR>
-{
-    async fn create_order(
-        &self,
-        command: dto::create::CreateOrderCommand,
-    ) -> Result<dto::create::CreateOrderResponse, common::error::OrderDomainError> {
-        self.order_create_command_helper.create_order(command).await
+        pub fn into_calculator(self) -> Result<TableTaxCalculator, OrderDomainError> {
+            if !(0.0..=1.0).contains(&self.default_rate) {
+                return Err(OrderDomainError::InvalidTaxRule(format!(
+                    "default rate {} is out of range",
+                    self.default_rate
+                )));
+            }
+            let rules = self
+                .rules
+                .into_iter()
+                .map(|rule| rule.into_rule())
+                .collect::<Result<Vec<TaxRule>, OrderDomainError>>()?;
+            Ok(TableTaxCalculator {
+                rules,
+                default_rate: self.default_rate,
+            })
+        }
     }
 
-    async fn track_order(
-        &self,
-        query: dto::track::TrackOrderQuery,
-    ) -> Result<dto::track::TrackOrderResponse, common::error::OrderDomainError> {
-        self.order_track_comman_helper.track_order(query).await
+    #[derive(Clone, Deserialize)]
+    pub struct BlockedAddressConfig {
+        #[serde(default)]
+        pub street: Option<String>,
+        pub postal_code: String,
+    }
+
+    #[derive(Clone, Deserialize)]
+    #[serde(tag = "type")]
+    pub enum RiskRuleConfig {
+        CustomerVelocity {
+            max_orders: u64,
+            window_minutes: i64,
+            decision: String,
+        },
+        AddressVelocity {
+            max_orders: u64,
+            window_minutes: i64,
+            decision: String,
+        },
+        HighTotal {
+            threshold: f64,
+            decision: String,
+        },
+        NewCustomerLargeOrder {
+            max_previous_orders: u64,
+            threshold: f64,
+            decision: String,
+        },
+        BlockedAddress {
+            addresses: Vec<BlockedAddressConfig>,
+            decision: String,
+        },
     }
-}
 
-pub struct PaymentResponseMessageListenerImpl {}
+    impl RiskRuleConfig {
+        pub fn into_rule(self) -> Result<RiskRule, OrderDomainError> {
+            let parse_decision = |decision: String| -> Result<RiskDecision, OrderDomainError> {
+                decision.parse().map_err(|_| {
+                    OrderDomainError::InvalidRiskRule(format!("unknown decision {}", decision))
+                })
+            };
+            let parse_window = |window_minutes: i64| -> Result<chrono::Duration, OrderDomainError> {
+                if window_minutes <= 0 {
+                    return Err(OrderDomainError::InvalidRiskRule(format!(
+                        "window of {} minutes is not positive",
+                        window_minutes
+                    )));
+                }
+                Ok(chrono::Duration::minutes(window_minutes))
+            };
+            let rule = match self {
+                RiskRuleConfig::CustomerVelocity {
+                    max_orders,
+                    window_minutes,
+                    decision,
+                } => RiskRule::CustomerVelocity {
+                    max_orders,
+                    window: parse_window(window_minutes)?,
+                    decision: parse_decision(decision)?,
+                },
+                RiskRuleConfig::AddressVelocity {
+                    max_orders,
+                    window_minutes,
+                    decision,
+                } => RiskRule::AddressVelocity {
+                    max_orders,
+                    window: parse_window(window_minutes)?,
+                    decision: parse_decision(decision)?,
+                },
+                RiskRuleConfig::HighTotal {
+                    threshold,
+                    decision,
+                } => RiskRule::HighTotal {
+                    threshold: Money::new(threshold),
+                    decision: parse_decision(decision)?,
+                },
+                RiskRuleConfig::NewCustomerLargeOrder {
+                    max_previous_orders,
+                    threshold,
+                    decision,
+                } => RiskRule::NewCustomerLargeOrder {
+                    max_previous_orders,
+                    threshold: Money::new(threshold),
+                    decision: parse_decision(decision)?,
+                },
+                RiskRuleConfig::BlockedAddress {
+                    addresses,
+                    decision,
+                } => RiskRule::BlockedAddress {
+                    addresses: addresses
+                        .into_iter()
+                        .map(|address| BlockedAddress {
+                            street: address.street,
+                            postal_code: address.postal_code,
+                        })
+                        .collect(),
+                    decision: parse_decision(decision)?,
+                },
+            };
+            Ok(rule)
+        }
+    }
 
-#[async_trait::async_trait]
-impl PaymentResponseListener for PaymentResponseMessageListenerImpl {
-    async fn payment_completed(&self, _response: dto::message::PaymentResponse) {
-        todo!()
+    #[derive(Clone, Deserialize)]
+    pub struct RiskRulesConfig {
+        pub rules: Vec<RiskRuleConfig>,
     }
 
-    async fn payment_cancelled(&self, _response: dto::message::PaymentResponse) {
-        todo!()
+    impl RiskRulesConfig {
+        pub fn from_json(json: &str) -> Result<Self, OrderDomainError> {
+            serde_json::from_str(json)
+                .map_err(|error| OrderDomainError::InvalidRiskRule(error.to_string()))
+        }
+
+        pub fn into_engine(self) -> Result<RiskRulesEngine, OrderDomainError> {
+            let rules = self
+                .rules
+                .into_iter()
+                .map(|rule| rule.into_rule())
+                .collect::<Result<Vec<RiskRule>, OrderDomainError>>()?;
+            Ok(RiskRulesEngine { rules })
+        }
     }
-}
 
-pub struct RestaurantApprovalResponseMessageListenerImpl {}
+    #[cfg(test)]
+    mod tests {
+        use common::error::OrderDomainError;
+        use common::value_object::TaxCategory;
+        use domain_core::risk::{RiskDecision, RiskRule};
 
-#[async_trait::async_trait]
-impl RestaurantApprovalResponseMessageListener for RestaurantApprovalResponseMessageListenerImpl {
-    async fn order_approved(&self, _response: RestaurantApprovalResponse) {}
-    async fn order_rejected(&self, _response: RestaurantApprovalResponse) {}
+        use super::{RiskRulesConfig, TaxRulesConfig};
+
+        #[test]
+        fn tax_rules_config_builds_a_calculator() {
+            let calculator = TaxRulesConfig::from_json(
+                r#"{
+                    "default_rate": 0.21,
+                    "rules": [
+                        {"tax_category": "Food", "rate": 0.09},
+                        {"city": "Amsterdam", "tax_category": "Alcohol", "rate": 0.25}
+                    ]
+                }"#,
+            )
+            .and_then(|tax_rules_config| tax_rules_config.into_calculator())
+            .unwrap();
+            assert_eq!(calculator.rules.len(), 2);
+            assert!(calculator.rules[0].tax_category == TaxCategory::Food);
+            assert_eq!(calculator.rules[1].city.as_deref(), Some("Amsterdam"));
+            assert_eq!(calculator.default_rate, 0.21);
+        }
+
+        #[test]
+        fn tax_rules_config_rejects_an_unknown_tax_category() {
+            let result = TaxRulesConfig::from_json(
+                r#"{"rules": [{"tax_category": "Luxury", "rate": 0.3}]}"#,
+            )
+            .and_then(|tax_rules_config| tax_rules_config.into_calculator());
+            assert!(matches!(result, Err(OrderDomainError::InvalidTaxRule(_))));
+        }
+
+        #[test]
+        fn tax_rules_config_rejects_a_rate_out_of_range() {
+            let result =
+                TaxRulesConfig::from_json(r#"{"rules": [{"tax_category": "Food", "rate": 9}]}"#)
+                    .and_then(|tax_rules_config| tax_rules_config.into_calculator());
+            assert!(matches!(result, Err(OrderDomainError::InvalidTaxRule(_))));
+        }
+
+        #[test]
+        fn risk_rules_config_builds_an_engine() {
+            let engine = RiskRulesConfig::from_json(
+                r#"{
+                    "rules": [
+                        {"type": "CustomerVelocity", "max_orders": 3, "window_minutes": 30, "decision": "Review"},
+                        {"type": "BlockedAddress", "addresses": [{"postal_code": "1011AB"}], "decision": "Deny"}
+                    ]
+                }"#,
+            )
+            .and_then(|risk_rules_config| risk_rules_config.into_engine())
+            .unwrap();
+            assert_eq!(engine.rules.len(), 2);
+            assert!(matches!(
+                &engine.rules[0],
+                RiskRule::CustomerVelocity { max_orders: 3, window, decision: RiskDecision::Review }
+                    if *window == chrono::Duration::minutes(30)
+            ));
+            assert!(matches!(
+                &engine.rules[1],
+                RiskRule::BlockedAddress { addresses, decision: RiskDecision::Deny }
+                    if addresses.len() == 1 && addresses[0].street.is_none()
+            ));
+            assert_eq!(engine.lookback(), chrono::Duration::minutes(30));
+        }
+
+        #[test]
+        fn risk_rules_config_rejects_an_unknown_decision() {
+            let result = RiskRulesConfig::from_json(
+                r#"{"rules": [{"type": "HighTotal", "threshold": 100, "decision": "Escalate"}]}"#,
+            )
+            .and_then(|risk_rules_config| risk_rules_config.into_engine());
+            assert!(matches!(result, Err(OrderDomainError::InvalidRiskRule(_))));
+        }
+
+        #[test]
+        fn risk_rules_config_rejects_a_negative_window() {
+            let result = RiskRulesConfig::from_json(
+                r#"{"rules": [{"type": "AddressVelocity", "max_orders": 3, "window_minutes": -5, "decision": "Review"}]}"#,
+            )
+            .and_then(|risk_rules_config| risk_rules_config.into_engine());
+            assert!(matches!(result, Err(OrderDomainError::InvalidRiskRule(_))));
+        }
+
+        #[test]
+        fn risk_rules_config_rejects_an_unknown_rule_type() {
+            let result = RiskRulesConfig::from_json(
+                r#"{"rules": [{"type": "Geofence", "decision": "Deny"}]}"#,
+            );
+            assert!(matches!(result, Err(OrderDomainError::InvalidRiskRule(_))));
+        }
+    }
+}
+
+pub mod retry {
+    use std::future::Future;
+
+    use common::error::OrderDomainError;
+
+    use crate::config::RetryPolicy;
+
+    pub async fn retry_on_concurrent_modification<T, F, Fut>(
+        retry_policy: &RetryPolicy,
+        operation: F,
+    ) -> Result<T, OrderDomainError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, OrderDomainError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(OrderDomainError::ConcurrentModification)
+                    if attempt < retry_policy.max_attempts =>
+                {
+                    attempt += 1;
+                    tokio::time::sleep(retry_policy.backoff_before(attempt)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::time::{Duration, Instant};
+
+        use common::error::OrderDomainError;
+
+        use super::retry_on_concurrent_modification;
+        use crate::config::RetryPolicy;
+
+        fn retry_policy(max_attempts: u32) -> RetryPolicy {
+            RetryPolicy {
+                max_attempts,
+                backoff: Duration::from_millis(5),
+            }
+        }
+
+        #[test]
+        fn backoff_doubles_with_each_retry() {
+            let retry_policy = retry_policy(4);
+            assert_eq!(retry_policy.backoff_before(2), Duration::from_millis(5));
+            assert_eq!(retry_policy.backoff_before(3), Duration::from_millis(10));
+            assert_eq!(retry_policy.backoff_before(4), Duration::from_millis(20));
+        }
+
+        #[tokio::test]
+        async fn retries_concurrent_modifications_after_backing_off() {
+            let attempts = AtomicU32::new(0);
+            let started_at = Instant::now();
+            let result = retry_on_concurrent_modification(&retry_policy(3), || async {
+                match attempts.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err(OrderDomainError::ConcurrentModification),
+                    _ => Ok(()),
+                }
+            })
+            .await;
+            assert!(result.is_ok());
+            assert_eq!(attempts.load(Ordering::SeqCst), 3);
+            assert!(started_at.elapsed() >= Duration::from_millis(15));
+        }
+
+        #[tokio::test]
+        async fn gives_up_after_max_attempts_and_does_not_retry_other_errors() {
+            let attempts = AtomicU32::new(0);
+            let result: Result<(), OrderDomainError> =
+                retry_on_concurrent_modification(&retry_policy(2), || async {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Err(OrderDomainError::ConcurrentModification)
+                })
+                .await;
+            assert!(matches!(
+                result,
+                Err(OrderDomainError::ConcurrentModification)
+            ));
+            assert_eq!(attempts.load(Ordering::SeqCst), 2);
+            let attempts = AtomicU32::new(0);
+            let result: Result<(), OrderDomainError> =
+                retry_on_concurrent_modification(&retry_policy(3), || async {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Err(OrderDomainError::OrderNotFound)
+                })
+                .await;
+            assert!(matches!(result, Err(OrderDomainError::OrderNotFound)));
+            assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        }
+    }
+}
+
+pub mod live {
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use common::{clock::Clock, error::OrderDomainError, value_object::OrderStatus};
+    use domain_core::{entity::Order, value_object::TrackingId};
+    use tokio::sync::broadcast::{self, error::RecvError};
+
+    use crate::{config::LiveStatusConfig, ports::output::repository::OrderRepository};
+
+    #[derive(Clone)]
+    pub struct OrderStatusUpdate {
+        pub event_id: u64,
+        pub order_tracking_id: uuid::Uuid,
+        pub order_status: OrderStatus,
+        pub failure_messages: Vec<String>,
+        pub occurred_at: chrono::DateTime<chrono::Utc>,
+        pub estimated_delivery_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    pub enum OrderStatusStreamItem {
+        Update(OrderStatusUpdate),
+        Resync,
+        Heartbeat,
+    }
+
+    impl OrderStatusStreamItem {
+        pub fn to_sse(&self) -> String {
+            match self {
+                OrderStatusStreamItem::Update(update) => {
+                    let data = serde_json::json!({
+                        "order_tracking_id": update.order_tracking_id.to_string(),
+                        "order_status": update.order_status.to_string(),
+                        "failure_messages": update.failure_messages,
+                        "occurred_at": update.occurred_at.to_rfc3339(),
+                        "estimated_delivery_at": update
+                            .estimated_delivery_at
+                            .map(|estimated_delivery_at| estimated_delivery_at.to_rfc3339()),
+                    });
+                    format!(
+                        "id: {}\nevent: order-status\ndata: {}\n\n",
+                        update.event_id, data
+                    )
+                }
+                OrderStatusStreamItem::Resync => String::from("event: resync\ndata: {}\n\n"),
+                OrderStatusStreamItem::Heartbeat => String::from(": heartbeat\n\n"),
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct OrderStatusBroadcaster<C: Clock> {
+        sender: broadcast::Sender<OrderStatusUpdate>,
+        replay_buffer: Arc<Mutex<VecDeque<OrderStatusUpdate>>>,
+        replay_capacity: usize,
+        clock: C,
+    }
+
+    impl<C: Clock> OrderStatusBroadcaster<C> {
+        pub fn new(live_status_config: &LiveStatusConfig, clock: C) -> Self {
+            let replay_capacity = live_status_config.replay_capacity.max(1);
+            let (sender, _receiver) = broadcast::channel(replay_capacity);
+            Self {
+                sender,
+                replay_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(replay_capacity))),
+                replay_capacity,
+                clock,
+            }
+        }
+
+        pub fn publish(&self, order: &Order) {
+            let mut replay_buffer = self.replay_buffer.lock().unwrap();
+            let update = OrderStatusUpdate {
+                event_id: order.aggregate_root.version as u64,
+                order_tracking_id: order.tracking_id.clone().into(),
+                order_status: order.order_status.clone(),
+                failure_messages: order.failure_messages.clone(),
+                occurred_at: order.status_changed_at.unwrap_or_else(|| self.clock.now()),
+                estimated_delivery_at: order.estimated_delivery_at,
+            };
+            if replay_buffer.len() == self.replay_capacity {
+                replay_buffer.pop_front();
+            }
+            replay_buffer.push_back(update.clone());
+            let _receivers = self.sender.send(update);
+        }
+
+        pub fn subscribe(
+            &self,
+            order_tracking_id: uuid::Uuid,
+            last_event_id: Option<u64>,
+            heartbeat_interval: Duration,
+        ) -> OrderStatusSubscription {
+            let replay_buffer = self.replay_buffer.lock().unwrap();
+            let backlog: VecDeque<OrderStatusUpdate> = match last_event_id {
+                Some(last_event_id) => replay_buffer
+                    .iter()
+                    .filter(|update| {
+                        update.order_tracking_id == order_tracking_id
+                            && update.event_id > last_event_id
+                    })
+                    .cloned()
+                    .collect(),
+                None => VecDeque::new(),
+            };
+            OrderStatusSubscription {
+                order_tracking_id,
+                last_event_id: last_event_id.unwrap_or(0),
+                backlog,
+                receiver: self.sender.subscribe(),
+                heartbeat_interval,
+            }
+        }
+    }
+
+    pub struct OrderStatusSubscription {
+        order_tracking_id: uuid::Uuid,
+        last_event_id: u64,
+        backlog: VecDeque<OrderStatusUpdate>,
+        receiver: broadcast::Receiver<OrderStatusUpdate>,
+        heartbeat_interval: Duration,
+    }
+
+    impl OrderStatusSubscription {
+        pub async fn next(&mut self) -> Option<OrderStatusStreamItem> {
+            if let Some(update) = self.backlog.pop_front() {
+                self.last_event_id = update.event_id;
+                return Some(OrderStatusStreamItem::Update(update));
+            }
+            loop {
+                match tokio::time::timeout(self.heartbeat_interval, self.receiver.recv()).await {
+                    Err(_elapsed) => return Some(OrderStatusStreamItem::Heartbeat),
+                    Ok(Ok(update)) => {
+                        if update.order_tracking_id != self.order_tracking_id
+                            || update.event_id <= self.last_event_id
+                        {
+                            continue;
+                        }
+                        self.last_event_id = update.event_id;
+                        return Some(OrderStatusStreamItem::Update(update));
+                    }
+                    Ok(Err(RecvError::Lagged(_))) => return Some(OrderStatusStreamItem::Resync),
+                    Ok(Err(RecvError::Closed)) => return None,
+                }
+            }
+        }
+    }
+
+    pub struct BroadcastingOrderRepository<OR: OrderRepository, C: Clock> {
+        order_repository: OR,
+        order_status_broadcaster: OrderStatusBroadcaster<C>,
+    }
+
+    impl<OR: OrderRepository, C: Clock> BroadcastingOrderRepository<OR, C> {
+        pub fn new(
+            order_repository: OR,
+            order_status_broadcaster: OrderStatusBroadcaster<C>,
+        ) -> Self {
+            Self {
+                order_repository,
+                order_status_broadcaster,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<OR: OrderRepository, C: Clock> OrderRepository for BroadcastingOrderRepository<OR, C> {
+        async fn save(&self, order: Order) -> Result<Order, OrderDomainError> {
+            let order = self.order_repository.save(order).await?;
+            self.order_status_broadcaster.publish(&order);
+            Ok(order)
+        }
+        async fn save_all(&self, orders: Vec<Order>) -> Result<Vec<Order>, OrderDomainError> {
+            let orders = self.order_repository.save_all(orders).await?;
+            for order in orders.iter() {
+                self.order_status_broadcaster.publish(order);
+            }
+            Ok(orders)
+        }
+        async fn update(&self, order: Order) -> Result<Order, OrderDomainError> {
+            let changes_status = order
+                .pending_events
+                .iter()
+                .any(|event| event.changes_status());
+            let order = self.order_repository.update(order).await?;
+            if changes_status {
+                self.order_status_broadcaster.publish(&order);
+            }
+            Ok(order)
+        }
+        async fn find_by_id(&self, order_id: uuid::Uuid) -> Result<Order, OrderDomainError> {
+            self.order_repository.find_by_id(order_id).await
+        }
+        async fn find_by_tracking_id(&self, id: TrackingId) -> Result<Order, OrderDomainError> {
+            self.order_repository.find_by_tracking_id(id).await
+        }
+        async fn find_scheduled_due(
+            &self,
+            release_cutoff: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            self.order_repository
+                .find_scheduled_due(release_cutoff)
+                .await
+        }
+        async fn find_stuck(
+            &self,
+            order_status: OrderStatus,
+            status_changed_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            self.order_repository
+                .find_stuck(order_status, status_changed_before)
+                .await
+        }
+        async fn find_by_checkout_id(
+            &self,
+            checkout_id: uuid::Uuid,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            self.order_repository.find_by_checkout_id(checkout_id).await
+        }
+        async fn count_open_orders_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<u64, OrderDomainError> {
+            self.order_repository
+                .count_open_orders_by_customer(customer_id)
+                .await
+        }
+        async fn count_kitchen_orders_by_restaurant(
+            &self,
+            restaurant_id: uuid::Uuid,
+            created_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, OrderDomainError> {
+            self.order_repository
+                .count_kitchen_orders_by_restaurant(restaurant_id, created_before)
+                .await
+        }
+        async fn count_orders_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<u64, OrderDomainError> {
+            self.order_repository
+                .count_orders_by_customer(customer_id)
+                .await
+        }
+    }
+}
+
+pub mod eta {
+    use common::error::OrderDomainError;
+    use domain_core::{
+        entity::{Order, Product, Restaurant},
+        OrderDomainService,
+    };
+
+    use crate::ports::output::repository::{OrderRepository, RestaurantRepository};
+
+    pub async fn find_order_restaurant<RR: RestaurantRepository>(
+        restaurant_repository: &RR,
+        order: &Order,
+    ) -> Result<Restaurant, OrderDomainError> {
+        let products: Vec<Product> = order
+            .items
+            .iter()
+            .map(|item| item.product.clone())
+            .collect();
+        restaurant_repository
+            .find_restaurant_info(Restaurant::new(
+                order.restaurant_id.clone().into(),
+                products,
+                true,
+            ))
+            .await
+    }
+
+    pub async fn estimate_delivery<ODS: OrderDomainService, OR: OrderRepository>(
+        order_domain_service: &ODS,
+        order_repository: &OR,
+        order: &Order,
+        restaurant: &Restaurant,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, OrderDomainError> {
+        let kitchen_orders = match order.created_at {
+            Some(created_at) => {
+                order_repository
+                    .count_kitchen_orders_by_restaurant(
+                        order.restaurant_id.clone().into(),
+                        created_at,
+                    )
+                    .await?
+            }
+            None => 0,
+        };
+        Ok(order_domain_service.estimate_delivery(order, restaurant, kitchen_orders))
+    }
+
+    pub async fn reestimate_delivery<
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        RR: RestaurantRepository,
+    >(
+        order_domain_service: &ODS,
+        order_repository: &OR,
+        restaurant_repository: &RR,
+        order: &mut Order,
+    ) -> Result<(), OrderDomainError> {
+        let restaurant = find_order_restaurant(restaurant_repository, order).await?;
+        let estimated_delivery_at =
+            estimate_delivery(order_domain_service, order_repository, order, &restaurant).await?;
+        order.update_delivery_estimate(estimated_delivery_at);
+        Ok(())
+    }
+}
+
+pub mod ports {
+    pub mod input {
+        pub mod message {
+            pub mod listener {
+                pub mod payment {
+                    use crate::dto::message::PaymentResponse;
+
+                    #[async_trait::async_trait]
+                    pub trait PaymentResponseListener {
+                        async fn payment_completed(&self, response: PaymentResponse);
+                        async fn payment_cancelled(&self, response: PaymentResponse);
+                    }
+                }
+
+                pub mod restaurant {
+                    use crate::dto::message::RestaurantApprovalResponse;
+
+                    #[async_trait::async_trait]
+                    pub trait RestaurantApprovalResponseMessageListener {
+                        async fn order_approved(&self, response: RestaurantApprovalResponse);
+                        async fn order_rejected(&self, response: RestaurantApprovalResponse);
+                    }
+                }
+            }
+        }
+
+        pub mod service {
+            use crate::dto::{
+                adjust::{AdjustOrderItemCommand, AdjustOrderItemResponse},
+                checkout::{CheckoutBasketCommand, CheckoutBasketResponse},
+                create::{CreateOrderCommand, CreateOrderResponse},
+                error::ErrorResponse,
+                group::{
+                    AddGroupOrderItemsCommand, GroupOrderResponse, LockGroupOrderCommand,
+                    OpenGroupOrderCommand, SubmitGroupOrderCommand,
+                },
+                list::{ListCustomerOrdersQuery, OrderPageResponse, SearchOrdersQuery},
+                reorder::{ReorderCommand, ReorderResponse},
+                subscription::{
+                    ChangeSubscriptionStatusCommand, CreateSubscriptionCommand,
+                    SubscriptionResponse,
+                },
+                track::{TrackOrderQuery, TrackOrderResponse},
+            };
+
+            #[async_trait::async_trait]
+            pub trait OrderApplicationService: std::marker::Sync {
+                async fn create_order(
+                    &self,
+                    command: CreateOrderCommand,
+                ) -> Result<CreateOrderResponse, ErrorResponse>;
+                async fn checkout_basket(
+                    &self,
+                    command: CheckoutBasketCommand,
+                ) -> Result<CheckoutBasketResponse, ErrorResponse>;
+                async fn track_order(
+                    &self,
+                    query: TrackOrderQuery,
+                ) -> Result<TrackOrderResponse, ErrorResponse>;
+                async fn rebuild_order_tracking_view(&self) -> Result<u64, ErrorResponse>;
+                async fn adjust_order_item(
+                    &self,
+                    command: AdjustOrderItemCommand,
+                ) -> Result<AdjustOrderItemResponse, ErrorResponse>;
+                async fn reorder(
+                    &self,
+                    command: ReorderCommand,
+                ) -> Result<ReorderResponse, ErrorResponse>;
+                async fn open_group_order(
+                    &self,
+                    command: OpenGroupOrderCommand,
+                ) -> Result<GroupOrderResponse, ErrorResponse>;
+                async fn add_group_order_items(
+                    &self,
+                    command: AddGroupOrderItemsCommand,
+                ) -> Result<GroupOrderResponse, ErrorResponse>;
+                async fn lock_group_order(
+                    &self,
+                    command: LockGroupOrderCommand,
+                ) -> Result<GroupOrderResponse, ErrorResponse>;
+                async fn submit_group_order(
+                    &self,
+                    command: SubmitGroupOrderCommand,
+                ) -> Result<CreateOrderResponse, ErrorResponse>;
+                async fn create_subscription(
+                    &self,
+                    command: CreateSubscriptionCommand,
+                ) -> Result<SubscriptionResponse, ErrorResponse>;
+                async fn pause_subscription(
+                    &self,
+                    command: ChangeSubscriptionStatusCommand,
+                ) -> Result<SubscriptionResponse, ErrorResponse>;
+                async fn resume_subscription(
+                    &self,
+                    command: ChangeSubscriptionStatusCommand,
+                ) -> Result<SubscriptionResponse, ErrorResponse>;
+                async fn list_customer_orders(
+                    &self,
+                    query: ListCustomerOrdersQuery,
+                ) -> Result<OrderPageResponse, ErrorResponse>;
+                async fn search_orders(
+                    &self,
+                    query: SearchOrdersQuery,
+                ) -> Result<OrderPageResponse, ErrorResponse>;
+            }
+        }
+    }
+
+    pub mod output {
+        pub mod message {
+            pub mod publisher {
+                pub mod payment {
+                    use domain_core::{
+                        checkout::Checkout,
+                        entity::Order,
+                        event::{CheckoutCreated, OrderCancelled, OrderCreated, OrderItemAdjusted},
+                    };
+
+                    use common::event::publisher::DomainEventPublisher;
+
+                    pub trait CheckoutCreatedPaymentRequestMessagePublisher:
+                        DomainEventPublisher<Checkout, CheckoutCreated>
+                    {
+                    }
+
+                    pub trait OrderCancelledPaymentRequestMessagePublisher:
+                        DomainEventPublisher<Order, OrderCancelled>
+                    {
+                    }
+
+                    pub trait OrderRefundRequestMessagePublisher:
+                        DomainEventPublisher<Order, OrderItemAdjusted>
+                    {
+                    }
+
+                    pub trait OrderCreatedPaymentRequestMessagePublisher:
+                        DomainEventPublisher<Order, OrderCreated>
+                    {
+                    }
+                }
+
+                pub mod notification {
+                    use domain_core::{
+                        event::OrderSubscriptionFailed, subscription::OrderSubscription,
+                    };
+
+                    use common::event::publisher::DomainEventPublisher;
+
+                    pub trait OrderSubscriptionFailedNotificationMessagePublisher:
+                        DomainEventPublisher<
+                        OrderSubscription,
+                        OrderSubscriptionFailed,
+                    >
+                    {
+                    }
+                }
+
+                pub mod risk_review {
+                    use domain_core::{entity::Order, event::OrderRiskReviewRequested};
+
+                    use common::event::publisher::DomainEventPublisher;
+
+                    pub trait OrderRiskReviewRequestMessagePublisher:
+                        DomainEventPublisher<Order, OrderRiskReviewRequested>
+                    {
+                    }
+                }
+
+                pub mod restaurant_approval {
+                    use domain_core::{entity::Order, event::OrderPaid};
+
+                    use common::event::publisher::DomainEventPublisher;
+
+                    pub trait OrderPaidRestaurantRequestMessagePublisher:
+                        DomainEventPublisher<Order, OrderPaid>
+                    {
+                    }
+                }
+            }
+        }
+
+        pub mod repository {
+            use common::{error::OrderDomainError, value_object::OrderStatus};
+
+            use crate::dto::{list::OrderSearchCriteria, track::TrackOrderResponse};
+            use domain_core::{
+                entity::{Customer, Order, Restaurant},
+                group_order::GroupOrder,
+                risk::RiskCheck,
+                subscription::OrderSubscription,
+                value_object::TrackingId,
+            };
+
+            #[async_trait::async_trait]
+            pub trait OrderRepository: Send + Sync {
+                async fn save(&self, order: Order) -> Result<Order, OrderDomainError>;
+                async fn save_all(
+                    &self,
+                    orders: Vec<Order>,
+                ) -> Result<Vec<Order>, OrderDomainError>;
+                async fn update(&self, order: Order) -> Result<Order, OrderDomainError>;
+                async fn find_by_id(&self, order_id: uuid::Uuid)
+                    -> Result<Order, OrderDomainError>;
+                async fn find_by_tracking_id(
+                    &self,
+                    id: TrackingId,
+                ) -> Result<Order, OrderDomainError>;
+                async fn find_scheduled_due(
+                    &self,
+                    release_cutoff: chrono::DateTime<chrono::Utc>,
+                ) -> Result<Vec<Order>, OrderDomainError>;
+                async fn find_stuck(
+                    &self,
+                    order_status: OrderStatus,
+                    status_changed_before: chrono::DateTime<chrono::Utc>,
+                ) -> Result<Vec<Order>, OrderDomainError>;
+                async fn find_by_checkout_id(
+                    &self,
+                    checkout_id: uuid::Uuid,
+                ) -> Result<Vec<Order>, OrderDomainError>;
+                async fn count_open_orders_by_customer(
+                    &self,
+                    customer_id: uuid::Uuid,
+                ) -> Result<u64, OrderDomainError>;
+                async fn count_kitchen_orders_by_restaurant(
+                    &self,
+                    restaurant_id: uuid::Uuid,
+                    created_before: chrono::DateTime<chrono::Utc>,
+                ) -> Result<u64, OrderDomainError>;
+                async fn count_orders_by_customer(
+                    &self,
+                    customer_id: uuid::Uuid,
+                ) -> Result<u64, OrderDomainError>;
+            }
+
+            #[async_trait::async_trait]
+            pub trait OrderTrackingViewRepository: Send + Sync {
+                async fn find_by_tracking_id(
+                    &self,
+                    id: TrackingId,
+                ) -> Result<TrackOrderResponse, OrderDomainError>;
+                async fn rebuild(&self) -> Result<u64, OrderDomainError>;
+            }
+
+            #[async_trait::async_trait]
+            pub trait OrderQueryRepository: Send + Sync {
+                async fn search(
+                    &self,
+                    criteria: OrderSearchCriteria,
+                ) -> Result<Vec<Order>, OrderDomainError>;
+            }
+
+            #[async_trait::async_trait]
+            pub trait GroupOrderRepository: Send + Sync {
+                async fn save(
+                    &self,
+                    group_order: GroupOrder,
+                ) -> Result<GroupOrder, OrderDomainError>;
+                async fn update(
+                    &self,
+                    group_order: GroupOrder,
+                ) -> Result<GroupOrder, OrderDomainError>;
+                async fn find_by_id(
+                    &self,
+                    group_order_id: uuid::Uuid,
+                ) -> Result<GroupOrder, OrderDomainError>;
+            }
+
+            #[async_trait::async_trait]
+            pub trait OrderSubscriptionRepository: Send + Sync {
+                async fn save(
+                    &self,
+                    subscription: OrderSubscription,
+                ) -> Result<OrderSubscription, OrderDomainError>;
+                async fn update(
+                    &self,
+                    subscription: OrderSubscription,
+                ) -> Result<OrderSubscription, OrderDomainError>;
+                async fn find_by_id(
+                    &self,
+                    subscription_id: uuid::Uuid,
+                ) -> Result<OrderSubscription, OrderDomainError>;
+                async fn find_active(&self) -> Result<Vec<OrderSubscription>, OrderDomainError>;
+                async fn claim_occurrence(
+                    &self,
+                    subscription: OrderSubscription,
+                    occurrence_on: chrono::NaiveDate,
+                    order_id: uuid::Uuid,
+                ) -> Result<OrderSubscription, OrderDomainError>;
+            }
+
+            #[async_trait::async_trait]
+            pub trait RiskCheckRepository: Send + Sync {
+                async fn save(&self, risk_check: RiskCheck) -> Result<RiskCheck, OrderDomainError>;
+                async fn find_check_times_by_customer(
+                    &self,
+                    customer_id: uuid::Uuid,
+                    since: chrono::DateTime<chrono::Utc>,
+                ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, OrderDomainError>;
+                async fn find_check_times_by_address(
+                    &self,
+                    address_key: String,
+                    since: chrono::DateTime<chrono::Utc>,
+                ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, OrderDomainError>;
+            }
+
+            #[async_trait::async_trait]
+            pub trait CustomerRepository: Send + Sync {
+                async fn find_customer(
+                    &self,
+                    customer_id: uuid::Uuid,
+                ) -> Result<Customer, OrderDomainError>;
+            }
+
+            #[async_trait::async_trait]
+            pub trait RestaurantRepository: Send + Sync {
+                async fn find_restaurant_info(
+                    &self,
+                    restaurant: Restaurant,
+                ) -> Result<Restaurant, OrderDomainError>;
+            }
+        }
+    }
+}
+
+pub struct OrderCreateHelper<
+    ORRRMP: OrderRiskReviewRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    CR: CustomerRepository,
+    RR: RestaurantRepository,
+    RCR: RiskCheckRepository,
+    IG: IdGenerator,
+> {
+    order_domain_service: ODS,
+    order_repository: OR,
+    customer_repository: CR,
+    restaurant_repository: RR,
+    risk_check_repository: RCR,
+    order_risk_review_request_message_publisher: ORRRMP,
+    id_generator: IG,
+    order_limits: OrderLimits,
+}
+
+impl<
+        ORRRMP: OrderRiskReviewRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        CR: CustomerRepository,
+        RR: RestaurantRepository,
+        RCR: RiskCheckRepository,
+        IG: IdGenerator,
+    > OrderCreateHelper<ORRRMP, ODS, OR, CR, RR, RCR, IG>
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        order_domain_service: ODS,
+        order_repository: OR,
+        customer_repository: CR,
+        restaurant_repository: RR,
+        risk_check_repository: RCR,
+        order_risk_review_request_message_publisher: ORRRMP,
+        id_generator: IG,
+        order_limits: OrderLimits,
+    ) -> Self {
+        Self {
+            order_domain_service,
+            order_repository,
+            customer_repository,
+            restaurant_repository,
+            risk_check_repository,
+            order_risk_review_request_message_publisher,
+            id_generator,
+            order_limits,
+        }
+    }
+
+    pub async fn persist_order(
+        &self,
+        command: CreateOrderCommand,
+    ) -> Result<OrderCreated, OrderDomainError> {
+        let customer = self.check_customer(command.customer_id).await?;
+        self.check_customer_order_limits(command.customer_id, 1)
+            .await?;
+        let restaurant = self.check_restaurant(command.clone()).await?;
+        let mut order: Order = command.into_order(&self.id_generator);
+        order.dietary_preferences = customer.dietary_preferences;
+        let mut order_created_event = self.order_domain_service.validate_and_initiate_order(
+            order,
+            restaurant.clone(),
+            Causation::new(self.id_generator.generate(), None),
+        )?;
+        let risk_check = self.check_risk(&mut order_created_event.order).await?;
+        self.estimate_delivery(&mut order_created_event.order, &restaurant)
+            .await?;
+        self.save_order(order_created_event.order.clone()).await?;
+        self.record_risk_check(
+            &order_created_event.order,
+            risk_check,
+            Causation::caused_by(&order_created_event.envelope),
+        )
+        .await?;
+        Ok(order_created_event)
+    }
+
+    pub async fn check_customer(
+        &self,
+        customer_id: uuid::Uuid,
+    ) -> Result<Customer, OrderDomainError> {
+        self.customer_repository.find_customer(customer_id).await
+    }
+
+    pub async fn check_risk(&self, order: &mut Order) -> Result<RiskCheck, OrderDomainError> {
+        let customer_id: uuid::Uuid = order.customer_id.clone().into();
+        let since = self.order_domain_service.risk_lookback_start();
+        let risk_signals = RiskSignalsBuilder::default()
+            .customer_order_times(
+                self.risk_check_repository
+                    .find_check_times_by_customer(customer_id, since)
+                    .await?,
+            )
+            .address_order_times(
+                self.risk_check_repository
+                    .find_check_times_by_address(address_key(&order.street_address), since)
+                    .await?,
+            )
+            .customer_previous_orders(
+                self.order_repository
+                    .count_orders_by_customer(customer_id)
+                    .await?,
+            )
+            .build()
+            .unwrap();
+        let risk_check = self
+            .order_domain_service
+            .assess_order_risk(order, risk_signals);
+        order.apply_risk_assessment(&risk_check.assessment)?;
+        Ok(risk_check)
+    }
+
+    pub async fn record_risk_check(
+        &self,
+        order: &Order,
+        risk_check: RiskCheck,
+        causation: Causation,
+    ) -> Result<(), OrderDomainError> {
+        self.risk_check_repository.save(risk_check).await?;
+        if order.risk_decision == RiskDecision::Review {
+            self.order_risk_review_request_message_publisher
+                .publish(
+                    self.order_domain_service
+                        .request_risk_review(order.clone(), causation),
+                )
+                .await;
+        }
+        Ok(())
+    }
+
+    pub async fn check_customer_order_limits(
+        &self,
+        customer_id: uuid::Uuid,
+        new_orders: u64,
+    ) -> Result<(), OrderDomainError> {
+        let open_orders = self
+            .order_repository
+            .count_open_orders_by_customer(customer_id)
+            .await?;
+        if open_orders + new_orders > self.order_limits.max_open_orders_per_customer {
+            return Err(OrderDomainError::TooManyOpenOrders(open_orders));
+        }
+        Ok(())
+    }
+
+    pub async fn check_restaurant(
+        &self,
+        command: CreateOrderCommand,
+    ) -> Result<Restaurant, OrderDomainError> {
+        let restaurant: Restaurant = command.into();
+        let restaurant = self
+            .restaurant_repository
+            .find_restaurant_info(restaurant)
+            .await?;
+        Ok(restaurant)
+    }
+
+    pub async fn estimate_delivery(
+        &self,
+        order: &mut Order,
+        restaurant: &Restaurant,
+    ) -> Result<(), OrderDomainError> {
+        order.estimated_delivery_at = eta::estimate_delivery(
+            &self.order_domain_service,
+            &self.order_repository,
+            order,
+            restaurant,
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn save_order(&self, order: Order) -> Result<Order, OrderDomainError> {
+        self.order_repository.save(order).await
+    }
+}
+
+pub struct OrderCreateCommandHandler<
+    OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+    ORRRMP: OrderRiskReviewRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    CR: CustomerRepository,
+    RR: RestaurantRepository,
+    RCR: RiskCheckRepository,
+    IG: IdGenerator,
+> {
+    order_create_helper: OrderCreateHelper<ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+    order_created_payment_request_message_publisher: OCPRMP,
+}
+
+impl<
+        OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+        ORRRMP: OrderRiskReviewRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        CR: CustomerRepository,
+        RR: RestaurantRepository,
+        RCR: RiskCheckRepository,
+        IG: IdGenerator,
+    > OrderCreateCommandHandler<OCPRMP, ORRRMP, ODS, OR, CR, RR, RCR, IG>
+{
+    pub fn new(
+        order_create_helper: OrderCreateHelper<ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+        order_created_payment_request_message_publisher: OCPRMP,
+    ) -> Self {
+        Self {
+            order_create_helper,
+            order_created_payment_request_message_publisher,
+        }
+    }
+
+    pub async fn create_order(
+        &self,
+        command: CreateOrderCommand,
+    ) -> Result<CreateOrderResponse, OrderDomainError> {
+        let order_created_event = self.order_create_helper.persist_order(command).await?;
+        if order_created_event.order.order_status != OrderStatus::Scheduled {
+            self.order_created_payment_request_message_publisher
+                .publish(order_created_event.clone())
+                .await;
+        }
+        let create_order_response: CreateOrderResponse = order_created_event.order.into();
+        Ok(create_order_response)
+    }
+}
+
+pub struct BasketCheckoutCommandHandler<
+    CCPRMP: CheckoutCreatedPaymentRequestMessagePublisher,
+    ORRRMP: OrderRiskReviewRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    CR: CustomerRepository,
+    RR: RestaurantRepository,
+    RCR: RiskCheckRepository,
+    IG: IdGenerator,
+> {
+    order_create_helper: OrderCreateHelper<ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+    checkout_created_payment_request_message_publisher: CCPRMP,
+}
+
+impl<
+        CCPRMP: CheckoutCreatedPaymentRequestMessagePublisher,
+        ORRRMP: OrderRiskReviewRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        CR: CustomerRepository,
+        RR: RestaurantRepository,
+        RCR: RiskCheckRepository,
+        IG: IdGenerator,
+    > BasketCheckoutCommandHandler<CCPRMP, ORRRMP, ODS, OR, CR, RR, RCR, IG>
+{
+    pub fn new(
+        order_create_helper: OrderCreateHelper<ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+        checkout_created_payment_request_message_publisher: CCPRMP,
+    ) -> Self {
+        Self {
+            order_create_helper,
+            checkout_created_payment_request_message_publisher,
+        }
+    }
+
+    pub async fn checkout_basket(
+        &self,
+        command: CheckoutBasketCommand,
+    ) -> Result<CheckoutBasketResponse, OrderDomainError> {
+        let customer_id = command.customer_id;
+        let cancellation_policy = command.cancellation_policy.clone();
+        let customer = self.order_create_helper.check_customer(customer_id).await?;
+        let create_order_commands = command.into_create_order_commands();
+        self.order_create_helper
+            .check_customer_order_limits(customer_id, create_order_commands.len() as u64)
+            .await?;
+        let id_generator = &self.order_create_helper.id_generator;
+        let mut orders: Vec<(Order, Restaurant)> = vec![];
+        for create_order_command in create_order_commands {
+            let restaurant = self
+                .order_create_helper
+                .check_restaurant(create_order_command.clone())
+                .await?;
+            let mut order = create_order_command.into_order(id_generator);
+            order.dietary_preferences = customer.dietary_preferences.clone();
+            orders.push((order, restaurant));
+        }
+        let restaurants: Vec<Restaurant> = orders
+            .iter()
+            .map(|(_, restaurant)| restaurant.clone())
+            .collect();
+        let mut checkout_created_event = self
+            .order_create_helper
+            .order_domain_service
+            .validate_and_initiate_checkout(
+                id_generator.generate(),
+                customer_id,
+                cancellation_policy,
+                orders,
+                Causation::new(id_generator.generate(), None),
+            )?;
+        let mut risk_checks: Vec<RiskCheck> = vec![];
+        for (order, restaurant) in checkout_created_event
+            .checkout
+            .orders
+            .iter_mut()
+            .zip(restaurants.iter())
+        {
+            risk_checks.push(self.order_create_helper.check_risk(order).await?);
+            self.order_create_helper
+                .estimate_delivery(order, restaurant)
+                .await?;
+        }
+        self.order_create_helper
+            .order_repository
+            .save_all(checkout_created_event.checkout.orders.clone())
+            .await?;
+        for (order, risk_check) in checkout_created_event
+            .checkout
+            .orders
+            .iter()
+            .zip(risk_checks)
+        {
+            self.order_create_helper
+                .record_risk_check(
+                    order,
+                    risk_check,
+                    Causation::caused_by(&checkout_created_event.envelope),
+                )
+                .await?;
+        }
+        self.checkout_created_payment_request_message_publisher
+            .publish(checkout_created_event.clone())
+            .await;
+        Ok(checkout_created_event.checkout.into())
+    }
+}
+
+pub struct GroupOrderCommandHandler<
+    OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+    ORRRMP: OrderRiskReviewRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    GOR: GroupOrderRepository,
+    CR: CustomerRepository,
+    RR: RestaurantRepository,
+    RCR: RiskCheckRepository,
+    IG: IdGenerator,
+> {
+    order_create_helper: OrderCreateHelper<ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+    group_order_repository: GOR,
+    order_created_payment_request_message_publisher: OCPRMP,
+}
+
+impl<
+        OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+        ORRRMP: OrderRiskReviewRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        GOR: GroupOrderRepository,
+        CR: CustomerRepository,
+        RR: RestaurantRepository,
+        RCR: RiskCheckRepository,
+        IG: IdGenerator,
+    > GroupOrderCommandHandler<OCPRMP, ORRRMP, ODS, OR, GOR, CR, RR, RCR, IG>
+{
+    pub fn new(
+        order_create_helper: OrderCreateHelper<ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+        group_order_repository: GOR,
+        order_created_payment_request_message_publisher: OCPRMP,
+    ) -> Self {
+        Self {
+            order_create_helper,
+            group_order_repository,
+            order_created_payment_request_message_publisher,
+        }
+    }
+
+    pub async fn open_group_order(
+        &self,
+        command: OpenGroupOrderCommand,
+    ) -> Result<GroupOrderResponse, OrderDomainError> {
+        self.order_create_helper
+            .check_customer(command.host_id)
+            .await?;
+        for participant_id in command.participant_ids.iter() {
+            self.order_create_helper
+                .check_customer(*participant_id)
+                .await?;
+        }
+        let id_generator = &self.order_create_helper.id_generator;
+        let group_order = GroupOrder::open(
+            id_generator.generate(),
+            command.host_id,
+            command.restaurant_id,
+            command.order_address.into_street_address(id_generator),
+            command.participant_ids,
+        );
+        let group_order = self.group_order_repository.save(group_order).await?;
+        Ok(group_order.into())
+    }
+
+    pub async fn add_group_order_items(
+        &self,
+        command: AddGroupOrderItemsCommand,
+    ) -> Result<GroupOrderResponse, OrderDomainError> {
+        self.order_create_helper
+            .check_customer(command.customer_id)
+            .await?;
+        let mut group_order = self
+            .group_order_repository
+            .find_by_id(command.group_order_id)
+            .await?;
+        let items: Vec<OrderItem> = command.items.into_iter().map(|item| item.into()).collect();
+        group_order.add_items(command.customer_id, items)?;
+        let group_order = self.group_order_repository.update(group_order).await?;
+        Ok(group_order.into())
+    }
+
+    pub async fn lock_group_order(
+        &self,
+        command: LockGroupOrderCommand,
+    ) -> Result<GroupOrderResponse, OrderDomainError> {
+        let mut group_order = self
+            .group_order_repository
+            .find_by_id(command.group_order_id)
+            .await?;
+        group_order.lock(command.host_id)?;
+        let group_order = self.group_order_repository.update(group_order).await?;
+        Ok(group_order.into())
+    }
+
+    pub async fn submit_group_order(
+        &self,
+        command: SubmitGroupOrderCommand,
+    ) -> Result<CreateOrderResponse, OrderDomainError> {
+        let mut group_order = self
+            .group_order_repository
+            .find_by_id(command.group_order_id)
+            .await?;
+        group_order.ensure_host(command.host_id)?;
+        let id_generator = &self.order_create_helper.id_generator;
+        let order_id = match group_order.order_id {
+            Some(order_id) => {
+                match self
+                    .order_create_helper
+                    .order_repository
+                    .find_by_id(order_id)
+                    .await
+                {
+                    Ok(order) => return Ok(order.into()),
+                    Err(OrderDomainError::OrderNotFound) => order_id,
+                    Err(error) => return Err(error),
+                }
+            }
+            None => id_generator.generate(),
+        };
+        let claimed = group_order.order_id.is_some();
+        self.order_create_helper
+            .check_customer_order_limits(command.host_id, 1)
+            .await?;
+        let restaurant = Restaurant::new(
+            group_order.restaurant_id.clone().into(),
+            group_order_products(&group_order),
+            true,
+        );
+        let restaurant = self
+            .order_create_helper
+            .restaurant_repository
+            .find_restaurant_info(restaurant)
+            .await?;
+        for participant in group_order.participants.iter_mut() {
+            let customer = self
+                .order_create_helper
+                .check_customer(participant.customer_uuid())
+                .await?;
+            participant.dietary_preferences = customer.dietary_preferences;
+        }
+        let order = group_order.submit(command.host_id, order_id, id_generator.generate())?;
+        let mut order_created_event = self
+            .order_create_helper
+            .order_domain_service
+            .validate_and_initiate_order(
+                order,
+                restaurant.clone(),
+                Causation::new(id_generator.generate(), None),
+            )?;
+        let risk_check = self
+            .order_create_helper
+            .check_risk(&mut order_created_event.order)
+            .await?;
+        self.order_create_helper
+            .estimate_delivery(&mut order_created_event.order, &restaurant)
+            .await?;
+        if !claimed {
+            self.group_order_repository.update(group_order).await?;
+        }
+        self.order_create_helper
+            .save_order(order_created_event.order.clone())
+            .await?;
+        self.order_create_helper
+            .record_risk_check(
+                &order_created_event.order,
+                risk_check,
+                Causation::caused_by(&order_created_event.envelope),
+            )
+            .await?;
+        if order_created_event.order.order_status != OrderStatus::Scheduled {
+            self.order_created_payment_request_message_publisher
+                .publish(order_created_event.clone())
+                .await;
+        }
+        let create_order_response: CreateOrderResponse = order_created_event.order.into();
+        Ok(create_order_response)
+    }
+}
+
+pub struct OrderSubscriptionCommandHandler<
+    ODS: OrderDomainService,
+    OSR: OrderSubscriptionRepository,
+    CR: CustomerRepository,
+    IG: IdGenerator,
+> {
+    order_domain_service: ODS,
+    order_subscription_repository: OSR,
+    customer_repository: CR,
+    id_generator: IG,
+}
+
+impl<
+        ODS: OrderDomainService,
+        OSR: OrderSubscriptionRepository,
+        CR: CustomerRepository,
+        IG: IdGenerator,
+    > OrderSubscriptionCommandHandler<ODS, OSR, CR, IG>
+{
+    pub fn new(
+        order_domain_service: ODS,
+        order_subscription_repository: OSR,
+        customer_repository: CR,
+        id_generator: IG,
+    ) -> Self {
+        Self {
+            order_domain_service,
+            order_subscription_repository,
+            customer_repository,
+            id_generator,
+        }
+    }
+
+    pub async fn create_subscription(
+        &self,
+        command: CreateSubscriptionCommand,
+    ) -> Result<SubscriptionResponse, OrderDomainError> {
+        let _ = self
+            .customer_repository
+            .find_customer(command.template.customer_id)
+            .await?;
+        let subscription = command.into_subscription(&self.id_generator)?;
+        self.order_domain_service
+            .validate_subscription(&subscription)?;
+        let subscription = self
+            .order_subscription_repository
+            .save(subscription)
+            .await?;
+        Ok(subscription.into())
+    }
+
+    pub async fn pause_subscription(
+        &self,
+        command: ChangeSubscriptionStatusCommand,
+    ) -> Result<SubscriptionResponse, OrderDomainError> {
+        let mut subscription = self.find_owned_subscription(&command).await?;
+        subscription.pause()?;
+        let subscription = self
+            .order_subscription_repository
+            .update(subscription)
+            .await?;
+        Ok(subscription.into())
+    }
+
+    pub async fn resume_subscription(
+        &self,
+        command: ChangeSubscriptionStatusCommand,
+    ) -> Result<SubscriptionResponse, OrderDomainError> {
+        let mut subscription = self.find_owned_subscription(&command).await?;
+        subscription.resume()?;
+        let subscription = self
+            .order_subscription_repository
+            .update(subscription)
+            .await?;
+        Ok(subscription.into())
+    }
+
+    async fn find_owned_subscription(
+        &self,
+        command: &ChangeSubscriptionStatusCommand,
+    ) -> Result<OrderSubscription, OrderDomainError> {
+        let subscription = self
+            .order_subscription_repository
+            .find_by_id(command.subscription_id)
+            .await?;
+        if !subscription.is_owned_by(command.customer_id) {
+            return Err(OrderDomainError::SubscriptionNotFound);
+        }
+        Ok(subscription)
+    }
+}
+
+pub struct SubscriptionMaterializationHandler<
+    OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+    OSFNMP: OrderSubscriptionFailedNotificationMessagePublisher,
+    ORRRMP: OrderRiskReviewRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    OSR: OrderSubscriptionRepository,
+    CR: CustomerRepository,
+    RR: RestaurantRepository,
+    RCR: RiskCheckRepository,
+    IG: IdGenerator,
+> {
+    order_create_command_handler:
+        OrderCreateCommandHandler<OCPRMP, ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+    order_domain_service: ODS,
+    order_subscription_repository: OSR,
+    restaurant_repository: RR,
+    order_subscription_failed_notification_message_publisher: OSFNMP,
+    id_generator: IG,
+    subscription_policy: SubscriptionPolicy,
+}
+
+impl<
+        OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+        OSFNMP: OrderSubscriptionFailedNotificationMessagePublisher,
+        ORRRMP: OrderRiskReviewRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        OSR: OrderSubscriptionRepository,
+        CR: CustomerRepository,
+        RR: RestaurantRepository,
+        RCR: RiskCheckRepository,
+        IG: IdGenerator,
+    > SubscriptionMaterializationHandler<OCPRMP, OSFNMP, ORRRMP, ODS, OR, OSR, CR, RR, RCR, IG>
+{
+    pub fn new(
+        order_create_command_handler: OrderCreateCommandHandler<
+            OCPRMP,
+            ORRRMP,
+            ODS,
+            OR,
+            CR,
+            RR,
+            RCR,
+            IG,
+        >,
+        order_domain_service: ODS,
+        order_subscription_repository: OSR,
+        restaurant_repository: RR,
+        order_subscription_failed_notification_message_publisher: OSFNMP,
+        id_generator: IG,
+        subscription_policy: SubscriptionPolicy,
+    ) -> Self {
+        Self {
+            order_create_command_handler,
+            order_domain_service,
+            order_subscription_repository,
+            restaurant_repository,
+            order_subscription_failed_notification_message_publisher,
+            id_generator,
+            subscription_policy,
+        }
+    }
+
+    pub async fn materialize_due_subscriptions(&self) -> Result<BatchResult, OrderDomainError> {
+        let subscriptions = self.order_subscription_repository.find_active().await?;
+        let mut batch_result = BatchResult::default();
+        for mut subscription in subscriptions {
+            let occurrence = match self.order_domain_service.due_subscription_occurrence(
+                &subscription,
+                self.subscription_policy.materialize_lead_time,
+            ) {
+                Some(occurrence) => occurrence,
+                None => continue,
+            };
+            let subscription_id = subscription.id;
+            let order_id = self.id_generator.generate();
+            subscription.record_occurrence(&occurrence);
+            let subscription = match self
+                .order_subscription_repository
+                .claim_occurrence(subscription, occurrence.date, order_id)
+                .await
+            {
+                Ok(subscription) => subscription,
+                Err(OrderDomainError::SubscriptionOccurrenceClaimed(_)) => continue,
+                Err(error) => {
+                    batch_result.record(subscription_id, Err(error));
+                    continue;
+                }
+            };
+            let products: Vec<Product> = subscription
+                .items
+                .iter()
+                .map(|item| item.product.clone())
+                .collect();
+            let restaurant =
+                Restaurant::new(subscription.restaurant_id.clone().into(), products, true);
+            let restaurant_open = match self
+                .restaurant_repository
+                .find_restaurant_info(restaurant)
+                .await
+            {
+                Ok(restaurant) => restaurant.validate_open_at(occurrence.delivery_at),
+                Err(e) => Err(e),
+            };
+            let result = match restaurant_open {
+                Ok(()) => self
+                    .order_create_command_handler
+                    .create_order(occurrence_order_command(
+                        &subscription,
+                        occurrence.delivery_at,
+                        order_id,
+                    ))
+                    .await
+                    .map(|_| ()),
+                Err(OrderDomainError::RestaurantClosed(_))
+                | Err(OrderDomainError::RestaurantPaused(_)) => continue,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = &result {
+                let order_subscription_failed_event =
+                    self.order_domain_service.fail_subscription_occurrence(
+                        subscription,
+                        occurrence,
+                        vec![e.to_string()],
+                        Causation::new(self.id_generator.generate(), None),
+                    );
+                self.order_subscription_failed_notification_message_publisher
+                    .publish(order_subscription_failed_event)
+                    .await;
+            }
+            batch_result.record(subscription_id, result);
+        }
+        Ok(batch_result)
+    }
+}
+
+pub struct ScheduledOrderReleaseHandler<
+    OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    RR: RestaurantRepository,
+    IG: IdGenerator,
+> {
+    order_domain_service: ODS,
+    order_repository: OR,
+    restaurant_repository: RR,
+    order_created_payment_request_message_publisher: OCPRMP,
+    id_generator: IG,
+}
+
+impl<
+        OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        RR: RestaurantRepository,
+        IG: IdGenerator,
+    > ScheduledOrderReleaseHandler<OCPRMP, ODS, OR, RR, IG>
+{
+    pub fn new(
+        order_domain_service: ODS,
+        order_repository: OR,
+        restaurant_repository: RR,
+        order_created_payment_request_message_publisher: OCPRMP,
+        id_generator: IG,
+    ) -> Self {
+        Self {
+            order_domain_service,
+            order_repository,
+            restaurant_repository,
+            order_created_payment_request_message_publisher,
+            id_generator,
+        }
+    }
+
+    pub async fn release_due_orders(&self) -> Result<BatchResult, OrderDomainError> {
+        let release_cutoff = self.order_domain_service.scheduled_release_cutoff();
+        let due_orders = self
+            .order_repository
+            .find_scheduled_due(release_cutoff)
+            .await?;
+        let mut batch_result = BatchResult::default();
+        for order in due_orders {
+            let order_id: uuid::Uuid = order.clone().into();
+            batch_result.record(order_id, self.release_order(order).await);
+        }
+        Ok(batch_result)
+    }
+
+    async fn release_order(&self, order: Order) -> Result<(), OrderDomainError> {
+        let restaurant = eta::find_order_restaurant(&self.restaurant_repository, &order).await?;
+        let causation = Causation::new(self.id_generator.generate(), None);
+        match self.order_domain_service.release_scheduled_order(
+            order.clone(),
+            &restaurant,
+            causation.clone(),
+        ) {
+            Ok(mut order_created_event) => {
+                let estimated_delivery_at = eta::estimate_delivery(
+                    &self.order_domain_service,
+                    &self.order_repository,
+                    &order_created_event.order,
+                    &restaurant,
+                )
+                .await?;
+                order_created_event
+                    .order
+                    .update_delivery_estimate(estimated_delivery_at);
+                self.order_repository
+                    .update(order_created_event.order.clone())
+                    .await?;
+                self.order_created_payment_request_message_publisher
+                    .publish(order_created_event)
+                    .await;
+                Ok(())
+            }
+            Err(
+                error @ (OrderDomainError::InactiveRestaurant
+                | OrderDomainError::RestaurantClosed(_)
+                | OrderDomainError::RestaurantPaused(_)),
+            ) => {
+                let order_cancelled_event = self.order_domain_service.cancel_order(
+                    order,
+                    vec![error.to_string()],
+                    causation,
+                )?;
+                self.order_repository
+                    .update(order_cancelled_event.order)
+                    .await?;
+                Err(error)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+pub struct SagaTimeoutHandler<
+    OCPRMP: OrderCancelledPaymentRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    IG: IdGenerator,
+> {
+    order_domain_service: ODS,
+    order_repository: OR,
+    order_cancelled_payment_request_message_publisher: OCPRMP,
+    id_generator: IG,
+    saga_timeouts: SagaTimeouts,
+}
+
+impl<
+        OCPRMP: OrderCancelledPaymentRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        IG: IdGenerator,
+    > SagaTimeoutHandler<OCPRMP, ODS, OR, IG>
+{
+    pub fn new(
+        order_domain_service: ODS,
+        order_repository: OR,
+        order_cancelled_payment_request_message_publisher: OCPRMP,
+        id_generator: IG,
+        saga_timeouts: SagaTimeouts,
+    ) -> Self {
+        Self {
+            order_domain_service,
+            order_repository,
+            order_cancelled_payment_request_message_publisher,
+            id_generator,
+            saga_timeouts,
+        }
+    }
+
+    pub async fn compensate_timed_out_orders(&self) -> Result<BatchResult, OrderDomainError> {
+        let cancelled = self.cancel_timed_out_pending_orders().await?;
+        let cancelling = self.cancel_timed_out_paid_orders().await?;
+        Ok(cancelled.merge(cancelling))
+    }
+
+    async fn cancel_timed_out_pending_orders(&self) -> Result<BatchResult, OrderDomainError> {
+        let cutoff = self
+            .order_domain_service
+            .saga_step_cutoff(self.saga_timeouts.payment_response);
+        let stuck_orders = self
+            .order_repository
+            .find_stuck(OrderStatus::Pending, cutoff)
+            .await?;
+        let mut batch_result = BatchResult::default();
+        for order in stuck_orders {
+            let order_id: uuid::Uuid = order.clone().into();
+            batch_result.record(order_id, self.cancel_timed_out_pending_order(order).await);
+        }
+        Ok(batch_result)
+    }
+
+    async fn cancel_timed_out_pending_order(&self, order: Order) -> Result<(), OrderDomainError> {
+        let order_cancelled_event = self.order_domain_service.cancel_pending_order(
+            order,
+            vec![String::from("payment response timed out")],
+            Causation::new(self.id_generator.generate(), None),
+        )?;
+        self.order_repository
+            .update(order_cancelled_event.order.clone())
+            .await?;
+        self.order_cancelled_payment_request_message_publisher
+            .publish(order_cancelled_event)
+            .await;
+        Ok(())
+    }
+
+    async fn cancel_timed_out_paid_orders(&self) -> Result<BatchResult, OrderDomainError> {
+        let cutoff = self
+            .order_domain_service
+            .saga_step_cutoff(self.saga_timeouts.restaurant_approval_response);
+        let stuck_orders = self
+            .order_repository
+            .find_stuck(OrderStatus::Paid, cutoff)
+            .await?;
+        let mut batch_result = BatchResult::default();
+        for order in stuck_orders {
+            let order_id: uuid::Uuid = order.clone().into();
+            batch_result.record(order_id, self.cancel_timed_out_paid_order(order).await);
+        }
+        Ok(batch_result)
+    }
+
+    async fn cancel_timed_out_paid_order(&self, order: Order) -> Result<(), OrderDomainError> {
+        let order_cancelled_event = self.order_domain_service.cancel_order_payment(
+            order,
+            vec![String::from("restaurant approval response timed out")],
+            Causation::new(self.id_generator.generate(), None),
+        )?;
+        self.order_repository
+            .update(order_cancelled_event.order.clone())
+            .await?;
+        self.order_cancelled_payment_request_message_publisher
+            .publish(order_cancelled_event)
+            .await;
+        Ok(())
+    }
+}
+
+pub struct OrderTrackCommandHandler<OTVR: OrderTrackingViewRepository> {
+    order_tracking_view_repository: OTVR,
+}
+
+impl<OTVR: OrderTrackingViewRepository> OrderTrackCommandHandler<OTVR> {
+    pub fn new(order_tracking_view_repository: OTVR) -> Self {
+        Self {
+            order_tracking_view_repository,
+        }
+    }
+
+    pub async fn track_order(
+        &self,
+        query: TrackOrderQuery,
+    ) -> Result<TrackOrderResponse, OrderDomainError> {
+        let tracking_id: TrackingId = query.order_tracking_id.into();
+        self.order_tracking_view_repository
+            .find_by_tracking_id(tracking_id)
+            .await
+    }
+
+    pub async fn rebuild_tracking_view(&self) -> Result<u64, OrderDomainError> {
+        self.order_tracking_view_repository.rebuild().await
+    }
+}
+
+pub struct OrderReorderQueryHandler<OR: OrderRepository, RR: RestaurantRepository> {
+    order_repository: OR,
+    restaurant_repository: RR,
+}
+
+impl<OR: OrderRepository, RR: RestaurantRepository> OrderReorderQueryHandler<OR, RR> {
+    pub fn new(order_repository: OR, restaurant_repository: RR) -> Self {
+        Self {
+            order_repository,
+            restaurant_repository,
+        }
+    }
+
+    pub async fn quote(
+        &self,
+        command: &ReorderCommand,
+    ) -> Result<(ReorderQuote, CreateOrderCommand), OrderDomainError> {
+        let tracking_id: TrackingId = command.order_tracking_id.into();
+        let order = self
+            .order_repository
+            .find_by_tracking_id(tracking_id)
+            .await?;
+        let customer_id: uuid::Uuid = order.customer_id.clone().into();
+        if customer_id != command.customer_id {
+            return Err(OrderDomainError::OrderAccessDenied);
+        }
+        let products: Vec<Product> = order
+            .items
+            .iter()
+            .map(|item| item.product.clone())
+            .collect();
+        let restaurant = self
+            .restaurant_repository
+            .find_restaurant_info(Restaurant::new(
+                order.restaurant_id.clone().into(),
+                products,
+                true,
+            ))
+            .await?;
+        Ok(ReorderQuote::for_order(&order, &restaurant))
+    }
+}
+
+pub struct OrderStatusStreamHandler<OR: OrderRepository, C: Clock> {
+    order_repository: OR,
+    order_status_broadcaster: OrderStatusBroadcaster<C>,
+    live_status_config: LiveStatusConfig,
+}
+
+impl<OR: OrderRepository, C: Clock> OrderStatusStreamHandler<OR, C> {
+    pub fn new(
+        order_repository: OR,
+        order_status_broadcaster: OrderStatusBroadcaster<C>,
+        live_status_config: LiveStatusConfig,
+    ) -> Self {
+        Self {
+            order_repository,
+            order_status_broadcaster,
+            live_status_config,
+        }
+    }
+
+    pub async fn subscribe(
+        &self,
+        query: SubscribeOrderStatusQuery,
+    ) -> Result<OrderStatusSubscription, OrderDomainError> {
+        let tracking_id: TrackingId = query.order_tracking_id.into();
+        let order = self
+            .order_repository
+            .find_by_tracking_id(tracking_id)
+            .await?;
+        let customer_id: uuid::Uuid = order.customer_id.into();
+        if customer_id != query.customer_id {
+            return Err(OrderDomainError::OrderAccessDenied);
+        }
+        Ok(self.order_status_broadcaster.subscribe(
+            query.order_tracking_id,
+            query.last_event_id,
+            self.live_status_config.heartbeat_interval,
+        ))
+    }
+}
+
+pub struct OrderListQueryHandler<OQR: OrderQueryRepository> {
+    order_query_repository: OQR,
+}
+
+impl<OQR: OrderQueryRepository> OrderListQueryHandler<OQR> {
+    pub fn new(order_query_repository: OQR) -> Self {
+        Self {
+            order_query_repository,
+        }
+    }
+
+    pub async fn list_customer_orders(
+        &self,
+        query: ListCustomerOrdersQuery,
+    ) -> Result<OrderPageResponse, OrderDomainError> {
+        self.search_orders(query.into()).await
+    }
+
+    pub async fn search_orders(
+        &self,
+        query: SearchOrdersQuery,
+    ) -> Result<OrderPageResponse, OrderDomainError> {
+        let mut criteria: OrderSearchCriteria = query.try_into()?;
+        let limit = criteria.limit as usize;
+        criteria.limit += 1;
+        let mut orders = self.order_query_repository.search(criteria).await?;
+        let next_cursor = if orders.len() > limit {
+            orders.truncate(limit);
+            orders.last().map(|order| {
+                OrderCursor {
+                    created_at: order.created_at,
+                    order_id: order.clone().into(),
+                }
+                .to_string()
+            })
+        } else {
+            None
+        };
+        Ok(OrderPageResponse {
+            orders: orders.into_iter().map(|order| order.into()).collect(),
+            next_cursor,
+        })
+    }
+}
+
+pub struct OrderAdjustCommandHandler<
+    ORRMP: OrderRefundRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    IG: IdGenerator,
+> {
+    order_domain_service: ODS,
+    order_repository: OR,
+    order_refund_request_message_publisher: ORRMP,
+    id_generator: IG,
+}
+
+impl<
+        ORRMP: OrderRefundRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        IG: IdGenerator,
+    > OrderAdjustCommandHandler<ORRMP, ODS, OR, IG>
+{
+    pub fn new(
+        order_domain_service: ODS,
+        order_repository: OR,
+        order_refund_request_message_publisher: ORRMP,
+        id_generator: IG,
+    ) -> Self {
+        Self {
+            order_domain_service,
+            order_repository,
+            order_refund_request_message_publisher,
+            id_generator,
+        }
+    }
+
+    pub async fn adjust_order_item(
+        &self,
+        command: AdjustOrderItemCommand,
+    ) -> Result<AdjustOrderItemResponse, OrderDomainError> {
+        let tracking_id: TrackingId = command.order_tracking_id.into();
+        let order = self
+            .order_repository
+            .find_by_tracking_id(tracking_id)
+            .await?;
+        let order_item_adjusted_event = self.order_domain_service.adjust_order_item(
+            order,
+            command.order_item_id,
+            command.quantity,
+            command.reason,
+            Causation::new(self.id_generator.generate(), None),
+        )?;
+        self.order_repository
+            .update(order_item_adjusted_event.order.clone())
+            .await?;
+        self.order_refund_request_message_publisher
+            .publish(order_item_adjusted_event.clone())
+            .await;
+        Ok(order_item_adjusted_event.into())
+    }
+}
+
+pub struct OrderApplicationServiceImpl<
+    OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+    CCPRMP: CheckoutCreatedPaymentRequestMessagePublisher,
+    ORRMP: OrderRefundRequestMessagePublisher,
+    ORRRMP: OrderRiskReviewRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    CR: CustomerRepository,
+    RR: RestaurantRepository,
+    RCR: RiskCheckRepository,
+    OQR: OrderQueryRepository,
+    OTVR: OrderTrackingViewRepository,
+    GOR: GroupOrderRepository,
+    OSR: OrderSubscriptionRepository,
+    IG: IdGenerator,
+> {
+    order_create_command_helper:
+        OrderCreateCommandHandler<OCPRMP, ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+    basket_checkout_command_handler:
+        BasketCheckoutCommandHandler<CCPRMP, ORRRMP, ODS, OR, CR, RR, RCR, IG>,
+    group_order_command_handler:
+        GroupOrderCommandHandler<OCPRMP, ORRRMP, ODS, OR, GOR, CR, RR, RCR, IG>,
+    order_subscription_command_handler: OrderSubscriptionCommandHandler<ODS, OSR, CR, IG>,
+    order_track_comman_helper: OrderTrackCommandHandler<OTVR>,
+    order_adjust_command_handler: OrderAdjustCommandHandler<ORRMP, ODS, OR, IG>,
+    order_list_query_handler: OrderListQueryHandler<OQR>,
+    order_reorder_query_handler: OrderReorderQueryHandler<OR, RR>,
+}
+
+#[async_trait::async_trait]
+impl<
+        OCPRMP: OrderCreatedPaymentRequestMessagePublisher,
+        CCPRMP: CheckoutCreatedPaymentRequestMessagePublisher,
+        ORRMP: OrderRefundRequestMessagePublisher,
+        ORRRMP: OrderRiskReviewRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        CR: CustomerRepository,
+        RR: RestaurantRepository,
+        RCR: RiskCheckRepository,
+        OQR: OrderQueryRepository,
+        OTVR: OrderTrackingViewRepository,
+        GOR: GroupOrderRepository,
+        OSR: OrderSubscriptionRepository,
+        IG: IdGenerator,
+    > OrderApplicationService
+    for OrderApplicationServiceImpl<
+        OCPRMP,
+        CCPRMP,
+        ORRMP,
+        ORRRMP,
+        ODS,
+        OR,
+        CR,
+        RR,
+        RCR,
+        OQR,
+        OTVR,
+        GOR,
+        OSR,
+        IG,
+    >
+{
+    async fn create_order(
+        &self,
+        command: dto::create::CreateOrderCommand,
+    ) -> Result<dto::create::CreateOrderResponse, ErrorResponse> {
+        self.order_create_command_helper
+            .create_order(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn checkout_basket(
+        &self,
+        command: dto::checkout::CheckoutBasketCommand,
+    ) -> Result<dto::checkout::CheckoutBasketResponse, ErrorResponse> {
+        self.basket_checkout_command_handler
+            .checkout_basket(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn track_order(
+        &self,
+        query: dto::track::TrackOrderQuery,
+    ) -> Result<dto::track::TrackOrderResponse, ErrorResponse> {
+        self.order_track_comman_helper
+            .track_order(query)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn rebuild_order_tracking_view(&self) -> Result<u64, ErrorResponse> {
+        self.order_track_comman_helper
+            .rebuild_tracking_view()
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn adjust_order_item(
+        &self,
+        command: dto::adjust::AdjustOrderItemCommand,
+    ) -> Result<dto::adjust::AdjustOrderItemResponse, ErrorResponse> {
+        self.order_adjust_command_handler
+            .adjust_order_item(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn reorder(
+        &self,
+        command: dto::reorder::ReorderCommand,
+    ) -> Result<dto::reorder::ReorderResponse, ErrorResponse> {
+        let (reorder_quote, create_order_command) =
+            self.order_reorder_query_handler.quote(&command).await?;
+        if !command.confirm {
+            return Ok(ReorderResponse::Quote(reorder_quote));
+        }
+        if create_order_command.items.is_empty() {
+            return Err(OrderDomainError::NothingToReorder.into());
+        }
+        reorder_quote.confirm(command.expected_price)?;
+        let create_order_response = self
+            .order_create_command_helper
+            .create_order(create_order_command)
+            .await?;
+        Ok(ReorderResponse::Created(create_order_response))
+    }
+
+    async fn open_group_order(
+        &self,
+        command: dto::group::OpenGroupOrderCommand,
+    ) -> Result<dto::group::GroupOrderResponse, ErrorResponse> {
+        self.group_order_command_handler
+            .open_group_order(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn add_group_order_items(
+        &self,
+        command: dto::group::AddGroupOrderItemsCommand,
+    ) -> Result<dto::group::GroupOrderResponse, ErrorResponse> {
+        self.group_order_command_handler
+            .add_group_order_items(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn lock_group_order(
+        &self,
+        command: dto::group::LockGroupOrderCommand,
+    ) -> Result<dto::group::GroupOrderResponse, ErrorResponse> {
+        self.group_order_command_handler
+            .lock_group_order(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn submit_group_order(
+        &self,
+        command: dto::group::SubmitGroupOrderCommand,
+    ) -> Result<dto::create::CreateOrderResponse, ErrorResponse> {
+        self.group_order_command_handler
+            .submit_group_order(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn create_subscription(
+        &self,
+        command: dto::subscription::CreateSubscriptionCommand,
+    ) -> Result<dto::subscription::SubscriptionResponse, ErrorResponse> {
+        self.order_subscription_command_handler
+            .create_subscription(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn pause_subscription(
+        &self,
+        command: dto::subscription::ChangeSubscriptionStatusCommand,
+    ) -> Result<dto::subscription::SubscriptionResponse, ErrorResponse> {
+        self.order_subscription_command_handler
+            .pause_subscription(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn resume_subscription(
+        &self,
+        command: dto::subscription::ChangeSubscriptionStatusCommand,
+    ) -> Result<dto::subscription::SubscriptionResponse, ErrorResponse> {
+        self.order_subscription_command_handler
+            .resume_subscription(command)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn list_customer_orders(
+        &self,
+        query: dto::list::ListCustomerOrdersQuery,
+    ) -> Result<dto::list::OrderPageResponse, ErrorResponse> {
+        self.order_list_query_handler
+            .list_customer_orders(query)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+
+    async fn search_orders(
+        &self,
+        query: dto::list::SearchOrdersQuery,
+    ) -> Result<dto::list::OrderPageResponse, ErrorResponse> {
+        self.order_list_query_handler
+            .search_orders(query)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+}
+
+pub struct PaymentResponseMessageListenerImpl<
+    OPRRMP: OrderPaidRestaurantRequestMessagePublisher,
+    OCPRMP: OrderCancelledPaymentRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    RR: RestaurantRepository,
+> {
+    order_domain_service: ODS,
+    order_repository: OR,
+    restaurant_repository: RR,
+    order_paid_restaurant_request_message_publisher: OPRRMP,
+    order_cancelled_payment_request_message_publisher: OCPRMP,
+    retry_policy: RetryPolicy,
+}
+
+impl<
+        OPRRMP: OrderPaidRestaurantRequestMessagePublisher,
+        OCPRMP: OrderCancelledPaymentRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        RR: RestaurantRepository,
+    > PaymentResponseMessageListenerImpl<OPRRMP, OCPRMP, ODS, OR, RR>
+{
+    pub fn new(
+        order_domain_service: ODS,
+        order_repository: OR,
+        restaurant_repository: RR,
+        order_paid_restaurant_request_message_publisher: OPRRMP,
+        order_cancelled_payment_request_message_publisher: OCPRMP,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        Self {
+            order_domain_service,
+            order_repository,
+            restaurant_repository,
+            order_paid_restaurant_request_message_publisher,
+            order_cancelled_payment_request_message_publisher,
+            retry_policy,
+        }
+    }
+
+    async fn pay_checkout(&self, checkout_orders: Vec<Order>, causation: &Causation) {
+        for checkout_order in checkout_orders {
+            let order_id: uuid::Uuid = checkout_order.into();
+            let order_paid_event =
+                retry_on_concurrent_modification(&self.retry_policy, || async move {
+                    let order = self.order_repository.find_by_id(order_id).await?;
+                    let mut order_paid_event = self
+                        .order_domain_service
+                        .pay_order(order, causation.clone())?;
+                    eta::reestimate_delivery(
+                        &self.order_domain_service,
+                        &self.order_repository,
+                        &self.restaurant_repository,
+                        &mut order_paid_event.order,
+                    )
+                    .await?;
+                    self.order_repository
+                        .update(order_paid_event.order.clone())
+                        .await?;
+                    Ok(order_paid_event)
+                })
+                .await;
+            if let Ok(order_paid_event) = order_paid_event {
+                self.order_paid_restaurant_request_message_publisher
+                    .publish(order_paid_event)
+                    .await;
+            }
+        }
+    }
+
+    async fn cancel_checkout(
+        &self,
+        checkout_orders: Vec<Order>,
+        failure_messages: &[String],
+        causation: &Causation,
+    ) {
+        for checkout_order in checkout_orders {
+            let order_id: uuid::Uuid = checkout_order.into();
+            let _order_cancelled_final_event =
+                retry_on_concurrent_modification(&self.retry_policy, || async move {
+                    let order = self.order_repository.find_by_id(order_id).await?;
+                    let order_cancelled_final_event = self.order_domain_service.cancel_order(
+                        order,
+                        failure_messages.to_vec(),
+                        causation.clone(),
+                    )?;
+                    self.order_repository
+                        .update(order_cancelled_final_event.order.clone())
+                        .await?;
+                    Ok(order_cancelled_final_event)
+                })
+                .await;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<
+        OPRRMP: OrderPaidRestaurantRequestMessagePublisher,
+        OCPRMP: OrderCancelledPaymentRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        RR: RestaurantRepository,
+    > PaymentResponseListener for PaymentResponseMessageListenerImpl<OPRRMP, OCPRMP, ODS, OR, RR>
+{
+    async fn payment_completed(&self, response: dto::message::PaymentResponse) {
+        // TODO: Handle error
+        let (order_id, causation) = match response.order_and_causation() {
+            Ok(order_and_causation) => order_and_causation,
+            Err(_) => return,
+        };
+        let causation = &causation;
+        if let Ok(checkout_orders) = self.order_repository.find_by_checkout_id(order_id).await {
+            if !checkout_orders.is_empty() {
+                self.pay_checkout(checkout_orders, causation).await;
+                return;
+            }
+        }
+        let response = &response;
+        let order_paid_event =
+            retry_on_concurrent_modification(&self.retry_policy, || async move {
+                let mut order = self.order_repository.find_by_id(order_id).await?;
+                if order.is_split_payment() {
+                    let customer_id = response.customer_uuid()?;
+                    let order_payment_share_paid_event = self
+                        .order_domain_service
+                        .pay_order_share(order, customer_id, causation.clone())?;
+                    order = order_payment_share_paid_event.order;
+                    if !order.all_shares_paid() {
+                        self.order_repository.update(order).await?;
+                        return Ok(None);
+                    }
+                }
+                let mut order_paid_event = self
+                    .order_domain_service
+                    .pay_order(order, causation.clone())?;
+                eta::reestimate_delivery(
+                    &self.order_domain_service,
+                    &self.order_repository,
+                    &self.restaurant_repository,
+                    &mut order_paid_event.order,
+                )
+                .await?;
+                self.order_repository
+                    .update(order_paid_event.order.clone())
+                    .await?;
+                Ok(Some(order_paid_event))
+            })
+            .await;
+        if let Ok(Some(order_paid_event)) = order_paid_event {
+            self.order_paid_restaurant_request_message_publisher
+                .publish(order_paid_event)
+                .await;
+        }
+    }
+
+    async fn payment_cancelled(&self, response: dto::message::PaymentResponse) {
+        // TODO: Handle error
+        let (order_id, causation) = match response.order_and_causation() {
+            Ok(order_and_causation) => order_and_causation,
+            Err(_) => return,
+        };
+        let causation = &causation;
+        let failure_messages = &response.failure_messages;
+        if let Ok(checkout_orders) = self.order_repository.find_by_checkout_id(order_id).await {
+            if !checkout_orders.is_empty() {
+                self.cancel_checkout(checkout_orders, failure_messages, causation)
+                    .await;
+                return;
+            }
+        }
+        let response = &response;
+        let order_cancelled_event =
+            retry_on_concurrent_modification(&self.retry_policy, || async move {
+                let order = self.order_repository.find_by_id(order_id).await?;
+                if order.is_split_payment() && order.order_status == OrderStatus::Pending {
+                    let customer_id = response.customer_uuid()?;
+                    let order_cancelled_event = self.order_domain_service.fail_order_share(
+                        order,
+                        customer_id,
+                        failure_messages.clone(),
+                        causation.clone(),
+                    )?;
+                    self.order_repository
+                        .update(order_cancelled_event.order.clone())
+                        .await?;
+                    return Ok(Some(order_cancelled_event));
+                }
+                let order_cancelled_final_event = self.order_domain_service.cancel_order(
+                    order,
+                    failure_messages.clone(),
+                    causation.clone(),
+                )?;
+                self.order_repository
+                    .update(order_cancelled_final_event.order.clone())
+                    .await?;
+                Ok(None)
+            })
+            .await;
+        if let Ok(Some(order_cancelled_event)) = order_cancelled_event {
+            self.order_cancelled_payment_request_message_publisher
+                .publish(order_cancelled_event)
+                .await;
+        }
+    }
+}
+
+pub struct RestaurantApprovalResponseMessageListenerImpl<
+    OCPRMP: OrderCancelledPaymentRequestMessagePublisher,
+    ODS: OrderDomainService,
+    OR: OrderRepository,
+    RR: RestaurantRepository,
+> {
+    order_domain_service: ODS,
+    order_repository: OR,
+    restaurant_repository: RR,
+    order_cancelled_payment_request_message_publisher: OCPRMP,
+    retry_policy: RetryPolicy,
+}
+
+impl<
+        OCPRMP: OrderCancelledPaymentRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        RR: RestaurantRepository,
+    > RestaurantApprovalResponseMessageListenerImpl<OCPRMP, ODS, OR, RR>
+{
+    pub fn new(
+        order_domain_service: ODS,
+        order_repository: OR,
+        restaurant_repository: RR,
+        order_cancelled_payment_request_message_publisher: OCPRMP,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        Self {
+            order_domain_service,
+            order_repository,
+            restaurant_repository,
+            order_cancelled_payment_request_message_publisher,
+            retry_policy,
+        }
+    }
+
+    async fn cancel_checkout_siblings(
+        &self,
+        checkout_id: uuid::Uuid,
+        rejected_order_id: uuid::Uuid,
+        failure_messages: &[String],
+        causation: &Causation,
+    ) {
+        let checkout_orders = match self.order_repository.find_by_checkout_id(checkout_id).await {
+            Ok(checkout_orders) => checkout_orders,
+            Err(_) => return,
+        };
+        for checkout_order in checkout_orders {
+            let order_id: uuid::Uuid = checkout_order.into();
+            if order_id == rejected_order_id {
+                continue;
+            }
+            let order_cancelled_event =
+                retry_on_concurrent_modification(&self.retry_policy, || async move {
+                    let order = self.order_repository.find_by_id(order_id).await?;
+                    let order_cancelled_event = self.order_domain_service.cancel_checkout_order(
+                        order,
+                        failure_messages.to_vec(),
+                        causation.clone(),
+                    )?;
+                    self.order_repository
+                        .update(order_cancelled_event.order.clone())
+                        .await?;
+                    Ok(order_cancelled_event)
+                })
+                .await;
+            if let Ok(order_cancelled_event) = order_cancelled_event {
+                self.order_cancelled_payment_request_message_publisher
+                    .publish(order_cancelled_event)
+                    .await;
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<
+        OCPRMP: OrderCancelledPaymentRequestMessagePublisher,
+        ODS: OrderDomainService,
+        OR: OrderRepository,
+        RR: RestaurantRepository,
+    > RestaurantApprovalResponseMessageListener
+    for RestaurantApprovalResponseMessageListenerImpl<OCPRMP, ODS, OR, RR>
+{
+    async fn order_approved(&self, response: RestaurantApprovalResponse) {
+        // TODO: Handle error
+        let (order_id, causation) = match response.order_and_causation() {
+            Ok(order_and_causation) => order_and_causation,
+            Err(_) => return,
+        };
+        let causation = &causation;
+        let _order_approved_event =
+            retry_on_concurrent_modification(&self.retry_policy, || async move {
+                let order = self.order_repository.find_by_id(order_id).await?;
+                let mut order_approved_event = self
+                    .order_domain_service
+                    .approve_order(order, causation.clone())?;
+                eta::reestimate_delivery(
+                    &self.order_domain_service,
+                    &self.order_repository,
+                    &self.restaurant_repository,
+                    &mut order_approved_event.order,
+                )
+                .await?;
+                self.order_repository
+                    .update(order_approved_event.order.clone())
+                    .await?;
+                Ok(order_approved_event)
+            })
+            .await;
+    }
+
+    async fn order_rejected(&self, response: RestaurantApprovalResponse) {
+        // TODO: Handle error
+        let (order_id, causation) = match response.order_and_causation() {
+            Ok(order_and_causation) => order_and_causation,
+            Err(_) => return,
+        };
+        let causation = &causation;
+        let failure_messages = &response.failure_messages;
+        let order_cancelled_event =
+            retry_on_concurrent_modification(&self.retry_policy, || async move {
+                let order = self.order_repository.find_by_id(order_id).await?;
+                let order_cancelled_event = self.order_domain_service.cancel_order_payment(
+                    order,
+                    failure_messages.clone(),
+                    causation.clone(),
+                )?;
+                self.order_repository
+                    .update(order_cancelled_event.order.clone())
+                    .await?;
+                Ok(order_cancelled_event)
+            })
+            .await;
+        if let Ok(order_cancelled_event) = order_cancelled_event {
+            let checkout_id = match order_cancelled_event.order.cancels_whole_checkout() {
+                true => order_cancelled_event
+                    .order
+                    .checkout
+                    .as_ref()
+                    .map(|checkout| checkout.id),
+                false => None,
+            };
+            let sibling_causation = Causation::caused_by(&order_cancelled_event.envelope);
+            self.order_cancelled_payment_request_message_publisher
+                .publish(order_cancelled_event)
+                .await;
+            if let Some(checkout_id) = checkout_id {
+                self.cancel_checkout_siblings(
+                    checkout_id,
+                    order_id,
+                    failure_messages,
+                    &sibling_causation,
+                )
+                .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod basket_checkout {
+        use common::{error::OrderDomainError, id::SequentialIdGenerator};
+        use domain_core::checkout::CheckoutCancellationPolicy;
+
+        use crate::config::OrderLimits;
+        use crate::dto::checkout::{BasketItem, CheckoutBasketCommand};
+        use crate::dto::create::{OrderAddress, OrderItem};
+        use crate::fixtures::{
+            self, InMemoryCustomerRepository, InMemoryOrderRepository,
+            InMemoryRestaurantRepository, RecordingPublisher,
+        };
+        use crate::BasketCheckoutCommandHandler;
+
+        #[tokio::test]
+        async fn counts_every_basket_order_against_the_open_order_limit() {
+            let product = fixtures::product(10, 1000.0);
+            let restaurant = fixtures::restaurant(vec![product.clone()]);
+            let open_order = fixtures::placed_order(
+                &SequentialIdGenerator::new(10_000),
+                fixtures::create_order_command(fixtures::id(2), std::slice::from_ref(&product)),
+                &restaurant,
+            );
+            let order_repository = InMemoryOrderRepository::with_orders(vec![open_order]);
+            let publisher = RecordingPublisher::default();
+            let basket_checkout_command_handler = BasketCheckoutCommandHandler::new(
+                fixtures::order_create_helper(
+                    order_repository.clone(),
+                    InMemoryCustomerRepository::with_customers(vec![fixtures::customer(
+                        fixtures::id(2),
+                    )]),
+                    InMemoryRestaurantRepository::new(restaurant),
+                    OrderLimits {
+                        max_open_orders_per_customer: 2,
+                    },
+                ),
+                publisher.clone(),
+            );
+            let basket_item = |restaurant_id: uuid::Uuid| BasketItem {
+                restaurant_id,
+                item: OrderItem::new(product.clone().into(), 1, product.price.clone(), vec![]),
+            };
+            let result = basket_checkout_command_handler
+                .checkout_basket(CheckoutBasketCommand {
+                    customer_id: fixtures::id(2),
+                    order_address: OrderAddress::from(fixtures::street_address()),
+                    items: vec![basket_item(fixtures::id(3)), basket_item(fixtures::id(30))],
+                    cancellation_policy: CheckoutCancellationPolicy::CancelAll,
+                })
+                .await;
+            assert!(matches!(
+                result,
+                Err(OrderDomainError::TooManyOpenOrders(1))
+            ));
+            assert_eq!(order_repository.all().len(), 1);
+            assert!(publisher.published().is_empty());
+        }
+    }
+
+    mod group_order {
+        use common::error::OrderDomainError;
+
+        use crate::config::OrderLimits;
+        use crate::dto::create::{OrderAddress, OrderItem};
+        use crate::dto::group::{
+            AddGroupOrderItemsCommand, LockGroupOrderCommand, OpenGroupOrderCommand,
+            SubmitGroupOrderCommand,
+        };
+        use crate::fixtures::{
+            self, InMemoryCustomerRepository, InMemoryGroupOrderRepository,
+            InMemoryOrderRepository, InMemoryRestaurantRepository, RecordingPublisher,
+        };
+        use crate::GroupOrderCommandHandler;
+
+        #[tokio::test]
+        async fn submitting_twice_returns_the_claimed_order() {
+            let product = fixtures::product(10, 1000.0);
+            let order_repository = InMemoryOrderRepository::default();
+            let publisher = RecordingPublisher::default();
+            let group_order_command_handler = GroupOrderCommandHandler::new(
+                fixtures::order_create_helper(
+                    order_repository.clone(),
+                    InMemoryCustomerRepository::with_customers(vec![
+                        fixtures::customer(fixtures::id(2)),
+                        fixtures::customer(fixtures::id(21)),
+                        fixtures::customer(fixtures::id(22)),
+                    ]),
+                    InMemoryRestaurantRepository::new(fixtures::restaurant(vec![product.clone()])),
+                    OrderLimits::default(),
+                ),
+                InMemoryGroupOrderRepository::default(),
+                publisher.clone(),
+            );
+            let group_order = group_order_command_handler
+                .open_group_order(OpenGroupOrderCommand {
+                    host_id: fixtures::id(2),
+                    restaurant_id: fixtures::id(3),
+                    order_address: OrderAddress::from(fixtures::street_address()),
+                    participant_ids: vec![fixtures::id(21)],
+                })
+                .await
+                .unwrap();
+            let add_items = |customer_id: uuid::Uuid| AddGroupOrderItemsCommand {
+                group_order_id: group_order.group_order_id,
+                customer_id,
+                items: vec![OrderItem::new(
+                    product.clone().into(),
+                    1,
+                    product.price.clone(),
+                    vec![],
+                )],
+            };
+            assert!(matches!(
+                group_order_command_handler
+                    .add_group_order_items(add_items(fixtures::id(22)))
+                    .await,
+                Err(OrderDomainError::NotGroupOrderParticipant)
+            ));
+            group_order_command_handler
+                .add_group_order_items(add_items(fixtures::id(21)))
+                .await
+                .unwrap();
+            group_order_command_handler
+                .lock_group_order(LockGroupOrderCommand {
+                    group_order_id: group_order.group_order_id,
+                    host_id: fixtures::id(2),
+                })
+                .await
+                .unwrap();
+            let submit = SubmitGroupOrderCommand {
+                group_order_id: group_order.group_order_id,
+                host_id: fixtures::id(2),
+            };
+            assert!(matches!(
+                group_order_command_handler
+                    .submit_group_order(SubmitGroupOrderCommand {
+                        group_order_id: group_order.group_order_id,
+                        host_id: fixtures::id(21),
+                    })
+                    .await,
+                Err(OrderDomainError::NotGroupOrderHost)
+            ));
+            let first_response = group_order_command_handler
+                .submit_group_order(submit.clone())
+                .await
+                .unwrap();
+            let second_response = group_order_command_handler
+                .submit_group_order(submit)
+                .await
+                .unwrap();
+            assert_eq!(
+                first_response.order_tracking_id,
+                second_response.order_tracking_id
+            );
+            assert_eq!(order_repository.all().len(), 1);
+            assert_eq!(publisher.published().len(), 1);
+        }
+    }
+
+    mod order_create {
+        use common::{
+            error::OrderDomainError,
+            id::SequentialIdGenerator,
+            value_object::{money::Money, OrderStatus},
+        };
+        use domain_core::risk::{RiskDecision, RiskRule, RiskRulesEngine};
+
+        use crate::config::OrderLimits;
+        use crate::fixtures::{
+            self, InMemoryCustomerRepository, InMemoryOrderRepository,
+            InMemoryRestaurantRepository, InMemoryRiskCheckRepository, RecordingPublisher,
+        };
+        use crate::OrderCreateHelper;
+
+        #[tokio::test]
+        async fn rejects_customers_at_the_open_order_limit() {
+            let product = fixtures::product(10, 1000.0);
+            let restaurant = fixtures::restaurant(vec![product.clone()]);
+            let open_order = fixtures::placed_order(
+                &SequentialIdGenerator::new(10_000),
+                fixtures::create_order_command(fixtures::id(2), std::slice::from_ref(&product)),
+                &restaurant,
+            );
+            let order_create_helper = fixtures::order_create_helper(
+                InMemoryOrderRepository::with_orders(vec![open_order]),
+                InMemoryCustomerRepository::with_customers(vec![fixtures::customer(fixtures::id(
+                    2,
+                ))]),
+                InMemoryRestaurantRepository::new(restaurant),
+                OrderLimits {
+                    max_open_orders_per_customer: 1,
+                },
+            );
+            let result = order_create_helper
+                .persist_order(fixtures::create_order_command(fixtures::id(2), &[product]))
+                .await;
+            assert!(matches!(
+                result,
+                Err(OrderDomainError::TooManyOpenOrders(1))
+            ));
+        }
+
+        #[tokio::test]
+        async fn rejects_orders_below_the_restaurant_minimum() {
+            let product = fixtures::product(10, 1000.0);
+            let mut restaurant = fixtures::restaurant(vec![product.clone()]);
+            restaurant.minimum_order_amount = Money::new(1500.0);
+            let order_repository = InMemoryOrderRepository::default();
+            let order_create_helper = fixtures::order_create_helper(
+                order_repository.clone(),
+                InMemoryCustomerRepository::with_customers(vec![fixtures::customer(fixtures::id(
+                    2,
+                ))]),
+                InMemoryRestaurantRepository::new(restaurant),
+                OrderLimits::default(),
+            );
+            let result = order_create_helper
+                .persist_order(fixtures::create_order_command(fixtures::id(2), &[product]))
+                .await;
+            assert!(matches!(
+                result,
+                Err(OrderDomainError::BelowMinimumOrderAmount(minimum)) if minimum == 1500.0
+            ));
+            assert!(order_repository.all().is_empty());
+        }
+
+        #[tokio::test]
+        async fn estimates_delivery_behind_the_earlier_kitchen_orders() {
+            let product = fixtures::product(10, 1000.0);
+            let restaurant = fixtures::restaurant(vec![product.clone()]);
+            let mut kitchen_order = fixtures::placed_order(
+                &SequentialIdGenerator::new(10_000),
+                fixtures::create_order_command(fixtures::id(8), std::slice::from_ref(&product)),
+                &restaurant,
+            );
+            kitchen_order.order_status = OrderStatus::Paid;
+            kitchen_order.created_at = Some(fixtures::now() - chrono::Duration::minutes(10));
+            let order_create_helper = fixtures::order_create_helper(
+                InMemoryOrderRepository::with_orders(vec![kitchen_order]),
+                InMemoryCustomerRepository::with_customers(vec![fixtures::customer(fixtures::id(
+                    2,
+                ))]),
+                InMemoryRestaurantRepository::new(restaurant),
+                OrderLimits::default(),
+            );
+            let order_created_event = order_create_helper
+                .persist_order(fixtures::create_order_command(fixtures::id(2), &[product]))
+                .await
+                .unwrap();
+            assert_eq!(
+                order_created_event.order.estimated_delivery_at,
+                Some(fixtures::now() + chrono::Duration::minutes(5 + 2 + 15 + 5))
+            );
+        }
+
+        #[test]
+        fn into_order_keeps_a_claimed_order_id() {
+            let mut create_order_command =
+                fixtures::create_order_command(fixtures::id(2), &[fixtures::product(10, 1000.0)]);
+            create_order_command.order_id = Some(fixtures::id(71));
+            let order = create_order_command.into_order(&SequentialIdGenerator::new(100));
+            assert_eq!(
+                order.aggregate_root.base_entity.id.base_id.value,
+                fixtures::id(71)
+            );
+            assert!(order
+                .items
+                .iter()
+                .all(|item| item.order_id.base_id.value == fixtures::id(71)));
+        }
+
+        #[tokio::test]
+        async fn publishes_review_orders_once_they_are_placed() {
+            let product = fixtures::product(10, 1000.0);
+            let risk_check_repository = InMemoryRiskCheckRepository::default();
+            let publisher = RecordingPublisher::default();
+            let order_create_helper = OrderCreateHelper::new(
+                fixtures::domain_service_with_risk_rules(RiskRulesEngine {
+                    rules: vec![RiskRule::HighTotal {
+                        threshold: Money::new(500.0),
+                        decision: RiskDecision::Review,
+                    }],
+                }),
+                InMemoryOrderRepository::default(),
+                InMemoryCustomerRepository::with_customers(vec![fixtures::customer(fixtures::id(
+                    2,
+                ))]),
+                InMemoryRestaurantRepository::new(fixtures::restaurant(vec![product.clone()])),
+                risk_check_repository.clone(),
+                publisher.clone(),
+                SequentialIdGenerator::new(100),
+                OrderLimits::default(),
+            );
+            let order_created_event = order_create_helper
+                .persist_order(fixtures::create_order_command(fixtures::id(2), &[product]))
+                .await
+                .unwrap();
+            assert!(order_created_event.order.risk_decision == RiskDecision::Review);
+            assert_eq!(risk_check_repository.risk_checks.lock().unwrap().len(), 1);
+            let published = publisher.published();
+            assert_eq!(published.len(), 1);
+            assert_eq!(
+                published[0]
+                    .order
+                    .aggregate_root
+                    .base_entity
+                    .id
+                    .base_id
+                    .value,
+                order_created_event
+                    .order
+                    .aggregate_root
+                    .base_entity
+                    .id
+                    .base_id
+                    .value
+            );
+        }
+
+        #[tokio::test]
+        async fn does_not_record_risk_checks_for_rejected_orders() {
+            let product = fixtures::product(10, 1000.0);
+            let mut restaurant = fixtures::restaurant(vec![product.clone()]);
+            restaurant.minimum_order_amount = Money::new(1500.0);
+            let risk_check_repository = InMemoryRiskCheckRepository::default();
+            let publisher = RecordingPublisher::default();
+            let order_create_helper = OrderCreateHelper::new(
+                fixtures::domain_service(),
+                InMemoryOrderRepository::default(),
+                InMemoryCustomerRepository::with_customers(vec![fixtures::customer(fixtures::id(
+                    2,
+                ))]),
+                InMemoryRestaurantRepository::new(restaurant),
+                risk_check_repository.clone(),
+                publisher.clone(),
+                SequentialIdGenerator::new(100),
+                OrderLimits::default(),
+            );
+            let result = order_create_helper
+                .persist_order(fixtures::create_order_command(fixtures::id(2), &[product]))
+                .await;
+            assert!(result.is_err());
+            assert!(risk_check_repository.risk_checks.lock().unwrap().is_empty());
+            assert!(publisher.published().is_empty());
+        }
+
+        #[tokio::test]
+        async fn persists_orders_within_the_limits() {
+            let product = fixtures::product(10, 1000.0);
+            let order_repository = InMemoryOrderRepository::default();
+            let order_create_helper = fixtures::order_create_helper(
+                order_repository.clone(),
+                InMemoryCustomerRepository::with_customers(vec![fixtures::customer(fixtures::id(
+                    2,
+                ))]),
+                InMemoryRestaurantRepository::new(fixtures::restaurant(vec![product.clone()])),
+                OrderLimits::default(),
+            );
+            let order_created_event = order_create_helper
+                .persist_order(fixtures::create_order_command(fixtures::id(2), &[product]))
+                .await
+                .unwrap();
+            assert_eq!(order_repository.all().len(), 1);
+            assert!(order_created_event.order.risk_decision == RiskDecision::Allow);
+        }
+    }
+
+    mod scheduled_order_release {
+        use common::{
+            error::OrderDomainError, id::SequentialIdGenerator, value_object::OrderStatus,
+        };
+        use domain_core::event::OrderCreated;
+
+        use crate::fixtures::{
+            self, InMemoryOrderRepository, InMemoryRestaurantRepository, RecordingPublisher,
+        };
+        use crate::ScheduledOrderReleaseHandler;
+
+        fn scheduled_order_repository(
+            id_generator: &SequentialIdGenerator,
+        ) -> InMemoryOrderRepository {
+            let product = fixtures::product(10, 1000.0);
+            let mut command =
+                fixtures::create_order_command(fixtures::id(2), std::slice::from_ref(&product));
+            command.requested_delivery_time = Some(fixtures::now() + chrono::Duration::hours(2));
+            let order =
+                fixtures::placed_order(id_generator, command, &fixtures::restaurant(vec![product]));
+            assert!(order.order_status == OrderStatus::Scheduled);
+            InMemoryOrderRepository::with_orders(vec![order])
+        }
+
+        fn handler(
+            order_repository: InMemoryOrderRepository,
+            restaurant_repository: InMemoryRestaurantRepository,
+            publisher: RecordingPublisher<OrderCreated>,
+        ) -> ScheduledOrderReleaseHandler<
+            RecordingPublisher<OrderCreated>,
+            fixtures::TestOrderDomainService,
+            InMemoryOrderRepository,
+            InMemoryRestaurantRepository,
+            SequentialIdGenerator,
+        > {
+            ScheduledOrderReleaseHandler::new(
+                fixtures::domain_service_at(fixtures::now() + chrono::Duration::minutes(90)),
+                order_repository,
+                restaurant_repository,
+                publisher,
+                SequentialIdGenerator::new(5000),
+            )
+        }
+
+        #[tokio::test]
+        async fn releases_due_orders_of_open_restaurants() {
+            let order_repository = scheduled_order_repository(&SequentialIdGenerator::new(100));
+            let publisher = RecordingPublisher::default();
+            let batch_result = handler(
+                order_repository.clone(),
+                InMemoryRestaurantRepository::new(fixtures::restaurant(vec![fixtures::product(
+                    10, 1000.0,
+                )])),
+                publisher.clone(),
+            )
+            .release_due_orders()
+            .await
+            .unwrap();
+            assert_eq!(batch_result.succeeded, 1);
+            assert!(batch_result.failures.is_empty());
+            assert!(order_repository.all()[0].order_status == OrderStatus::Pending);
+            assert_eq!(publisher.published().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn cancels_orders_of_restaurants_that_are_no_longer_active() {
+            let order_repository = scheduled_order_repository(&SequentialIdGenerator::new(100));
+            let publisher = RecordingPublisher::default();
+            let mut restaurant = fixtures::restaurant(vec![fixtures::product(10, 1000.0)]);
+            restaurant.active = false;
+            let batch_result = handler(
+                order_repository.clone(),
+                InMemoryRestaurantRepository::new(restaurant),
+                publisher.clone(),
+            )
+            .release_due_orders()
+            .await
+            .unwrap();
+            assert_eq!(batch_result.succeeded, 0);
+            assert!(matches!(
+                batch_result.failures[0].error,
+                OrderDomainError::InactiveRestaurant
+            ));
+            assert!(order_repository.all()[0].order_status == OrderStatus::Cancelled);
+            assert!(publisher.published().is_empty());
+        }
+
+        #[tokio::test]
+        async fn reports_failures_and_keeps_the_order_scheduled() {
+            let order_repository = scheduled_order_repository(&SequentialIdGenerator::new(100));
+            let mut other_restaurant = fixtures::restaurant(vec![]);
+            other_restaurant.base_entity.id.base_id.value = fixtures::id(99);
+            let batch_result = handler(
+                order_repository.clone(),
+                InMemoryRestaurantRepository::new(other_restaurant),
+                RecordingPublisher::default(),
+            )
+            .release_due_orders()
+            .await
+            .unwrap();
+            assert_eq!(batch_result.failures.len(), 1);
+            assert!(matches!(
+                batch_result.failures[0].error,
+                OrderDomainError::RestaurantNotFound
+            ));
+            assert!(order_repository.all()[0].order_status == OrderStatus::Scheduled);
+        }
+    }
+
+    mod order_list {
+        use crate::dto::list::OrderCursor;
+        use crate::fixtures;
+
+        #[test]
+        fn cursors_round_trip_with_and_without_a_creation_time() {
+            for created_at in [Some(fixtures::now()), None] {
+                let cursor = OrderCursor {
+                    created_at,
+                    order_id: fixtures::id(11),
+                };
+                let parsed: OrderCursor = cursor.to_string().parse().unwrap();
+                assert!(parsed == cursor);
+            }
+            assert!("not-a-date|".parse::<OrderCursor>().is_err());
+        }
+    }
+
+    mod reorder {
+        use common::{error::OrderDomainError, id::SequentialIdGenerator};
+
+        use crate::dto::reorder::ReorderQuote;
+        use crate::fixtures;
+
+        fn quote(current_price: f64) -> ReorderQuote {
+            let order = fixtures::placed_order(
+                &SequentialIdGenerator::new(100),
+                fixtures::create_order_command(fixtures::id(2), &[fixtures::product(10, 1000.0)]),
+                &fixtures::restaurant(vec![fixtures::product(10, 1000.0)]),
+            );
+            let (reorder_quote, _create_order_command) = ReorderQuote::for_order(
+                &order,
+                &fixtures::restaurant(vec![fixtures::product(10, current_price)]),
+            );
+            reorder_quote
+        }
+
+        #[test]
+        fn confirms_reorders_at_the_quoted_total() {
+            assert!(quote(1000.0).confirm(Some(1000.0)).is_ok());
+        }
+
+        #[test]
+        fn rejects_reorders_whose_total_changed_since_the_quote() {
+            let reorder_quote = quote(1200.0);
+            assert_eq!(reorder_quote.repriced_items.len(), 1);
+            assert!(matches!(
+                reorder_quote.confirm(Some(1000.0)),
+                Err(OrderDomainError::ReorderQuoteChanged(current_price)) if current_price == 1200.0
+            ));
+            assert!(matches!(
+                reorder_quote.confirm(None),
+                Err(OrderDomainError::ReorderQuoteChanged(_))
+            ));
+        }
+    }
+
+    mod live_status {
+        use std::time::Duration;
+
+        use common::{clock::FixedClock, id::SequentialIdGenerator};
+
+        use crate::config::LiveStatusConfig;
+        use crate::fixtures;
+        use crate::live::{OrderStatusBroadcaster, OrderStatusStreamItem};
+
+        fn broadcaster(replay_capacity: usize) -> OrderStatusBroadcaster<FixedClock> {
+            OrderStatusBroadcaster::new(
+                &LiveStatusConfig {
+                    heartbeat_interval: Duration::from_secs(60),
+                    replay_capacity,
+                },
+                FixedClock::new(fixtures::now()),
+            )
+        }
+
+        fn order(version: i64) -> domain_core::entity::Order {
+            let product = fixtures::product(10, 1000.0);
+            let mut order = fixtures::placed_order(
+                &SequentialIdGenerator::new(100),
+                fixtures::create_order_command(fixtures::id(2), std::slice::from_ref(&product)),
+                &fixtures::restaurant(vec![product]),
+            );
+            order.aggregate_root.version = version;
+            order
+        }
+
+        #[tokio::test]
+        async fn event_ids_follow_the_persisted_order_version() {
+            let order = order(3);
+            let order_tracking_id: uuid::Uuid = order.tracking_id.clone().into();
+            for order_status_broadcaster in [broadcaster(8), broadcaster(8)] {
+                order_status_broadcaster.publish(&order);
+                let mut subscription = order_status_broadcaster.subscribe(
+                    order_tracking_id,
+                    Some(2),
+                    Duration::from_secs(60),
+                );
+                match subscription.next().await {
+                    Some(OrderStatusStreamItem::Update(update)) => assert_eq!(update.event_id, 3),
+                    _ => panic!("expected the replayed update"),
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn updates_without_a_status_change_time_occur_at_the_clock() {
+            let mut order = order(1);
+            order.status_changed_at = None;
+            let order_tracking_id: uuid::Uuid = order.tracking_id.clone().into();
+            let order_status_broadcaster = broadcaster(8);
+            order_status_broadcaster.publish(&order);
+            let mut subscription = order_status_broadcaster.subscribe(
+                order_tracking_id,
+                Some(0),
+                Duration::from_secs(60),
+            );
+            match subscription.next().await {
+                Some(OrderStatusStreamItem::Update(update)) => {
+                    assert_eq!(update.occurred_at, fixtures::now())
+                }
+                _ => panic!("expected the replayed update"),
+            }
+        }
+
+        #[tokio::test]
+        async fn lagging_subscribers_are_asked_to_resync() {
+            let order_status_broadcaster = broadcaster(1);
+            let first_order = order(1);
+            let mut subscription = order_status_broadcaster.subscribe(
+                first_order.tracking_id.clone().into(),
+                None,
+                Duration::from_secs(60),
+            );
+            for version in 1..=3 {
+                order_status_broadcaster.publish(&order(version));
+            }
+            assert!(matches!(
+                subscription.next().await,
+                Some(OrderStatusStreamItem::Resync)
+            ));
+            assert_eq!(
+                OrderStatusStreamItem::Resync.to_sse(),
+                "event: resync\ndata: {}\n\n"
+            );
+        }
+    }
+
+    mod saga_timeout {
+        use common::{id::SequentialIdGenerator, value_object::OrderStatus};
+        use domain_core::event::OrderCancelled;
+
+        use crate::config::SagaTimeouts;
+        use crate::fixtures::{self, InMemoryOrderRepository, RecordingPublisher};
+        use crate::SagaTimeoutHandler;
+
+        fn handler(
+            order_repository: InMemoryOrderRepository,
+            publisher: RecordingPublisher<OrderCancelled>,
+        ) -> SagaTimeoutHandler<
+            RecordingPublisher<OrderCancelled>,
+            fixtures::TestOrderDomainService,
+            InMemoryOrderRepository,
+            SequentialIdGenerator,
+        > {
+            SagaTimeoutHandler::new(
+                fixtures::domain_service_at(fixtures::now() + chrono::Duration::minutes(30)),
+                order_repository,
+                publisher,
+                SequentialIdGenerator::new(5000),
+                SagaTimeouts {
+                    payment_response: chrono::Duration::minutes(10),
+                    restaurant_approval_response: chrono::Duration::minutes(10),
+                },
+            )
+        }
+
+        fn pending_order() -> domain_core::entity::Order {
+            let product = fixtures::product(10, 1000.0);
+            fixtures::placed_order(
+                &SequentialIdGenerator::new(100),
+                fixtures::create_order_command(fixtures::id(2), std::slice::from_ref(&product)),
+                &fixtures::restaurant(vec![product]),
+            )
+        }
+
+        #[tokio::test]
+        async fn cancels_timed_out_pending_orders_and_their_payment() {
+            let order_repository = InMemoryOrderRepository::with_orders(vec![pending_order()]);
+            let publisher = RecordingPublisher::default();
+            let batch_result = handler(order_repository.clone(), publisher.clone())
+                .compensate_timed_out_orders()
+                .await
+                .unwrap();
+            assert_eq!(batch_result.succeeded, 1);
+            assert!(batch_result.failures.is_empty());
+            assert!(order_repository.all()[0].order_status == OrderStatus::Cancelled);
+            let published = publisher.published();
+            assert_eq!(published.len(), 1);
+            assert!(published[0].order.order_status == OrderStatus::Cancelled);
+        }
+
+        #[tokio::test]
+        async fn starts_cancelling_timed_out_paid_orders() {
+            let mut order = pending_order();
+            order.pay().unwrap();
+            order.status_changed_at = None;
+            let order_repository = InMemoryOrderRepository::with_orders(vec![order]);
+            let publisher = RecordingPublisher::default();
+            let batch_result = handler(order_repository.clone(), publisher.clone())
+                .compensate_timed_out_orders()
+                .await
+                .unwrap();
+            assert_eq!(batch_result.succeeded, 1);
+            assert!(order_repository.all()[0].order_status == OrderStatus::Cancelling);
+            assert_eq!(publisher.published().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn leaves_orders_within_the_timeout_alone() {
+            let order_repository = InMemoryOrderRepository::with_orders(vec![pending_order()]);
+            let publisher = RecordingPublisher::default();
+            let mut saga_timeout_handler = handler(order_repository.clone(), publisher.clone());
+            saga_timeout_handler.saga_timeouts.payment_response = chrono::Duration::hours(1);
+            let batch_result = saga_timeout_handler
+                .compensate_timed_out_orders()
+                .await
+                .unwrap();
+            assert_eq!(batch_result.succeeded, 0);
+            assert!(order_repository.all()[0].order_status == OrderStatus::Pending);
+            assert!(publisher.published().is_empty());
+        }
+    }
+
+    mod subscription_materialization {
+        use chrono::{NaiveTime, Weekday};
+        use common::id::SequentialIdGenerator;
+        use domain_core::{
+            event::{OrderCreated, OrderRiskReviewRequested, OrderSubscriptionFailed},
+            subscription::{OrderSubscription, OrderSubscriptionBuilder, SubscriptionSchedule},
+        };
+
+        use crate::config::{OrderLimits, SubscriptionPolicy};
+        use crate::fixtures::{
+            self, InMemoryCustomerRepository, InMemoryOrderRepository,
+            InMemoryOrderSubscriptionRepository, InMemoryRestaurantRepository,
+            InMemoryRiskCheckRepository, RecordingPublisher,
+        };
+        use crate::{OrderCreateCommandHandler, SubscriptionMaterializationHandler};
+
+        fn subscription() -> OrderSubscription {
+            let product = fixtures::product(10, 1000.0);
+            let template = fixtures::placed_order(
+                &SequentialIdGenerator::new(100),
+                fixtures::create_order_command(fixtures::id(2), std::slice::from_ref(&product)),
+                &fixtures::restaurant(vec![product]),
+            );
+            OrderSubscriptionBuilder::default()
+                .id(fixtures::id(60))
+                .customer_id(template.customer_id.clone())
+                .restaurant_id(template.restaurant_id.clone())
+                .street_address(template.street_address.clone())
+                .items(template.items.clone())
+                .schedule(
+                    SubscriptionSchedule::new(
+                        vec![Weekday::Mon],
+                        NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+                        "UTC",
+                    )
+                    .unwrap(),
+                )
+                .build()
+                .unwrap()
+        }
+
+        type TestSubscriptionMaterializationHandler = SubscriptionMaterializationHandler<
+            RecordingPublisher<OrderCreated>,
+            RecordingPublisher<OrderSubscriptionFailed>,
+            RecordingPublisher<OrderRiskReviewRequested>,
+            fixtures::TestOrderDomainService,
+            InMemoryOrderRepository,
+            InMemoryOrderSubscriptionRepository,
+            InMemoryCustomerRepository,
+            InMemoryRestaurantRepository,
+            InMemoryRiskCheckRepository,
+            SequentialIdGenerator,
+        >;
+
+        fn handler(
+            order_repository: InMemoryOrderRepository,
+            order_subscription_repository: InMemoryOrderSubscriptionRepository,
+            failure_publisher: RecordingPublisher<OrderSubscriptionFailed>,
+        ) -> TestSubscriptionMaterializationHandler {
+            let restaurant_repository = InMemoryRestaurantRepository::new(fixtures::restaurant(
+                vec![fixtures::product(10, 1000.0)],
+            ));
+            SubscriptionMaterializationHandler::new(
+                OrderCreateCommandHandler::new(
+                    fixtures::order_create_helper(
+                        order_repository,
+                        InMemoryCustomerRepository::with_customers(vec![fixtures::customer(
+                            fixtures::id(2),
+                        )]),
+                        restaurant_repository.clone(),
+                        OrderLimits::default(),
+                    ),
+                    RecordingPublisher::default(),
+                ),
+                fixtures::domain_service(),
+                order_subscription_repository,
+                restaurant_repository,
+                failure_publisher,
+                SequentialIdGenerator::new(7000),
+                SubscriptionPolicy::default(),
+            )
+        }
+
+        #[tokio::test]
+        async fn creates_orders_for_due_occurrences() {
+            let order_repository = InMemoryOrderRepository::default();
+            let failure_publisher = RecordingPublisher::default();
+            let batch_result = handler(
+                order_repository.clone(),
+                InMemoryOrderSubscriptionRepository::with_subscriptions(vec![subscription()]),
+                failure_publisher.clone(),
+            )
+            .materialize_due_subscriptions()
+            .await
+            .unwrap();
+            assert_eq!(batch_result.succeeded, 1);
+            assert!(batch_result.failures.is_empty());
+            assert_eq!(order_repository.all().len(), 1);
+            assert!(failure_publisher.published().is_empty());
+        }
+
+        #[tokio::test]
+        async fn skips_occurrences_claimed_by_another_run() {
+            let order_repository = InMemoryOrderRepository::default();
+            let order_subscription_repository =
+                InMemoryOrderSubscriptionRepository::with_subscriptions(vec![subscription()]);
+            order_subscription_repository
+                .claimed_occurrences
+                .lock()
+                .unwrap()
+                .push((fixtures::id(60), fixtures::now().date_naive()));
+            let failure_publisher = RecordingPublisher::default();
+            let batch_result = handler(
+                order_repository.clone(),
+                order_subscription_repository,
+                failure_publisher.clone(),
+            )
+            .materialize_due_subscriptions()
+            .await
+            .unwrap();
+            assert_eq!(batch_result.succeeded, 0);
+            assert!(batch_result.failures.is_empty());
+            assert!(order_repository.all().is_empty());
+            assert!(failure_publisher.published().is_empty());
+        }
+    }
+
+    mod order_adjust {
+        use common::{id::SequentialIdGenerator, value_object::OrderStatus};
+        use domain_core::{event::OrderItemAdjusted, value_object::AdjustmentReason};
+
+        use crate::dto::adjust::AdjustOrderItemCommand;
+        use crate::fixtures::{self, InMemoryOrderRepository, RecordingPublisher};
+        use crate::OrderAdjustCommandHandler;
+
+        #[tokio::test]
+        async fn adjustments_publish_the_refund_request() {
+            let product = fixtures::product(10, 1000.0);
+            let mut order = fixtures::placed_order(
+                &SequentialIdGenerator::new(100),
+                fixtures::create_order_command(fixtures::id(2), std::slice::from_ref(&product)),
+                &fixtures::restaurant(vec![product]),
+            );
+            order.pay().unwrap();
+            order.approve().unwrap();
+            let order_tracking_id: uuid::Uuid = order.tracking_id.clone().into();
+            let order_repository = InMemoryOrderRepository::with_orders(vec![order]);
+            let publisher: RecordingPublisher<OrderItemAdjusted> = RecordingPublisher::default();
+            let response = OrderAdjustCommandHandler::new(
+                fixtures::domain_service(),
+                order_repository.clone(),
+                publisher.clone(),
+                SequentialIdGenerator::new(5000),
+            )
+            .adjust_order_item(AdjustOrderItemCommand {
+                order_tracking_id,
+                order_item_id: 0,
+                quantity: 1,
+                reason: AdjustmentReason::Damaged,
+            })
+            .await
+            .unwrap();
+            let published = publisher.published();
+            assert_eq!(published.len(), 1);
+            assert_eq!(published[0].adjustment.id, response.adjustment_id);
+            assert_eq!(
+                published[0].adjustment.refund_amount.amount,
+                response.refund_amount.amount
+            );
+            assert!(order_repository.all()[0].order_status == OrderStatus::Approved);
+            assert_eq!(order_repository.all()[0].items[0].quantity, 0);
+        }
+    }
+
+    mod response_message {
+        use common::{error::OrderDomainError, value_object::PaymentStatus};
+
+        use crate::dto::message::PaymentResponse;
+        use crate::fixtures;
+
+        fn payment_response() -> PaymentResponse {
+            PaymentResponse {
+                id: fixtures::id(1).to_string(),
+                saga_id: fixtures::id(2).to_string(),
+                order_id: fixtures::id(3).to_string(),
+                payment_id: fixtures::id(4).to_string(),
+                customer_id: fixtures::id(5).to_string(),
+                price: 1000.0,
+                created_at: fixtures::now(),
+                payment_status: PaymentStatus::Completed,
+                failure_messages: vec![],
+            }
+        }
+
+        fn invalid_field<T>(result: Result<T, OrderDomainError>) -> Option<String> {
+            match result {
+                Err(OrderDomainError::ValidationFailed(report)) => report
+                    .errors
+                    .first()
+                    .map(|field_error| field_error.field.clone()),
+                _ => None,
+            }
+        }
+
+        #[test]
+        fn malformed_ids_are_validation_errors() {
+            let mut response = payment_response();
+            response.order_id = String::from("not-a-uuid");
+            assert_eq!(
+                invalid_field(response.order_and_causation()),
+                Some(String::from("order_id"))
+            );
+            let mut response = payment_response();
+            response.saga_id = String::from("not-a-uuid");
+            assert_eq!(
+                invalid_field(response.order_and_causation()),
+                Some(String::from("saga_id"))
+            );
+            let mut response = payment_response();
+            response.customer_id = String::from("not-a-uuid");
+            assert_eq!(
+                invalid_field(response.customer_uuid()),
+                Some(String::from("customer_id"))
+            );
+        }
+    }
+
+    mod error_response {
+        use common::{error::OrderDomainError, validation::ValidationReport};
+
+        use crate::dto::error::ErrorResponse;
+        use crate::fixtures;
+
+        #[test]
+        fn validation_reports_become_field_errors_with_unavailable_products() {
+            let mut report = ValidationReport::new();
+            report.add("restaurant_id", "inactive restaurant");
+            report.add_unavailable_product("items[0].quantity", fixtures::id(10), "sold out");
+            let error_response = ErrorResponse::from(OrderDomainError::ValidationFailed(report));
+            assert_eq!(error_response.message, "the order is invalid");
+            let fields: Vec<String> = error_response
+                .field_errors
+                .into_iter()
+                .map(|field_error| field_error.field)
+                .collect();
+            assert_eq!(fields, vec!["restaurant_id", "items[0].quantity"]);
+            assert_eq!(error_response.unavailable_products, vec![fixtures::id(10)]);
+        }
+
+        #[test]
+        fn unavailable_products_are_listed_in_the_response() {
+            let error_response = ErrorResponse::from(OrderDomainError::ProductsUnavailable(vec![
+                fixtures::id(10),
+                fixtures::id(11),
+            ]));
+            assert!(error_response.field_errors.is_empty());
+            assert_eq!(
+                error_response.unavailable_products,
+                vec![fixtures::id(10), fixtures::id(11)]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod fixtures {
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+
+    use chrono::{DateTime, NaiveTime, TimeZone, Utc, Weekday};
+    use common::{
+        clock::FixedClock,
+        entity::{AggregateRootBuilder, BaseEntityBuilder},
+        error::OrderDomainError,
+        event::{publisher::DomainEventPublisher, Causation, DomainEvent},
+        id::SequentialIdGenerator,
+        value_object::{money::Money, OrderStatus},
+    };
+    use domain_core::{
+        delivery::{DeliveryArea, DeliveryFeeSchedule, DeliveryZone},
+        entity::{Customer, CustomerBuilder, Order, Product, Restaurant},
+        eta::HeuristicEtaEstimator,
+        event::{
+            CheckoutCreated, OrderCancelled, OrderCreated, OrderItemAdjusted,
+            OrderRiskReviewRequested, OrderSubscriptionFailed,
+        },
+        group_order::GroupOrder,
+        opening_hours::{OpeningHours, OpeningPeriod, WeeklyOpeningPeriod},
+        risk::{RiskCheck, RiskRulesEngine},
+        scheduling::SchedulingPolicy,
+        subscription::OrderSubscription,
+        tax::TableTaxCalculator,
+        value_object::{StreetAddress, StreetAddressBuilder, TrackingId},
+        OrderDomainService, OrderDomainServiceImpl,
+    };
+
+    use crate::config::OrderLimits;
+    use crate::dto::create::{CreateOrderCommand, OrderAddress, OrderItem};
+    use crate::ports::output::{
+        message::publisher::{
+            notification::OrderSubscriptionFailedNotificationMessagePublisher,
+            payment::{
+                CheckoutCreatedPaymentRequestMessagePublisher,
+                OrderCancelledPaymentRequestMessagePublisher,
+                OrderCreatedPaymentRequestMessagePublisher, OrderRefundRequestMessagePublisher,
+            },
+            risk_review::OrderRiskReviewRequestMessagePublisher,
+        },
+        repository::{
+            CustomerRepository, GroupOrderRepository, OrderRepository, OrderSubscriptionRepository,
+            RestaurantRepository, RiskCheckRepository,
+        },
+    };
+    use crate::OrderCreateHelper;
+
+    pub type TestOrderDomainService = OrderDomainServiceImpl<
+        TableTaxCalculator,
+        FixedClock,
+        SequentialIdGenerator,
+        HeuristicEtaEstimator,
+    >;
+
+    pub fn id(n: u128) -> uuid::Uuid {
+        uuid::Uuid::from_u128(n)
+    }
+
+    pub fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap()
+    }
+
+    pub fn causation() -> Causation {
+        Causation::new(id(800), None)
+    }
+
+    pub fn domain_service() -> TestOrderDomainService {
+        domain_service_at(now())
+    }
+
+    pub fn domain_service_at(now: DateTime<Utc>) -> TestOrderDomainService {
+        OrderDomainServiceImpl::new(
+            TableTaxCalculator {
+                rules: vec![],
+                default_rate: 0.0,
+            },
+            FixedClock::new(now),
+            SequentialIdGenerator::new(1000),
+            SchedulingPolicy::default(),
+            RiskRulesEngine::default(),
+            HeuristicEtaEstimator::default(),
+        )
+    }
+
+    pub fn domain_service_with_risk_rules(
+        risk_rules_engine: RiskRulesEngine,
+    ) -> TestOrderDomainService {
+        OrderDomainServiceImpl::new(
+            TableTaxCalculator {
+                rules: vec![],
+                default_rate: 0.0,
+            },
+            FixedClock::new(now()),
+            SequentialIdGenerator::new(1000),
+            SchedulingPolicy::default(),
+            risk_rules_engine,
+            HeuristicEtaEstimator::default(),
+        )
+    }
+
+    pub fn street_address() -> StreetAddress {
+        StreetAddressBuilder::default()
+            .id(id(900))
+            .street(String::from("1 Main Street"))
+            .postal_code(String::from("1011AB"))
+            .city(String::from("Amsterdam"))
+            .build()
+            .unwrap()
+    }
+
+    pub fn product(n: u128, price: f64) -> Product {
+        Product::new(id(n), format!("product {}", n), Money::new(price))
+    }
+
+    pub fn always_open() -> OpeningHours {
+        let midnight = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+        OpeningHours {
+            time_zone: chrono_tz::UTC,
+            weekly_periods: [
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Sat,
+                Weekday::Sun,
+            ]
+            .into_iter()
+            .map(|weekday| WeeklyOpeningPeriod {
+                weekday,
+                period: OpeningPeriod {
+                    opens_at: midnight,
+                    closes_at: midnight,
+                },
+            })
+            .collect(),
+            exceptions: vec![],
+            pauses: vec![],
+        }
+    }
+
+    pub fn restaurant(products: Vec<Product>) -> Restaurant {
+        let mut restaurant = Restaurant::new(id(3), products, true);
+        restaurant.delivery_zones = vec![DeliveryZone {
+            id: id(51),
+            area: DeliveryArea::PostalCodes(HashSet::from([String::from("1011AB")])),
+            fee_schedule: DeliveryFeeSchedule {
+                base_fee: Money::default(),
+                fee_per_km: Money::default(),
+                free_delivery_threshold: None,
+            },
+            minimum_order_amount: Money::default(),
+        }];
+        restaurant.opening_hours = Some(always_open());
+        restaurant
+    }
+
+    pub fn create_order_command(
+        customer_id: uuid::Uuid,
+        products: &[Product],
+    ) -> CreateOrderCommand {
+        let items: Vec<OrderItem> = products
+            .iter()
+            .map(|product| OrderItem::new(product.clone().into(), 1, product.price.clone(), vec![]))
+            .collect();
+        CreateOrderCommand {
+            customer_id,
+            restaurant_id: id(3),
+            price: items.iter().map(|item| item.sub_total().amount).sum(),
+            order_address: OrderAddress::from(street_address()),
+            items,
+            requested_delivery_time: None,
+            tip: None,
+            order_id: None,
+        }
+    }
+
+    pub fn placed_order(
+        id_generator: &SequentialIdGenerator,
+        command: CreateOrderCommand,
+        restaurant: &Restaurant,
+    ) -> Order {
+        domain_service()
+            .validate_and_initiate_order(
+                command.into_order(id_generator),
+                restaurant.clone(),
+                causation(),
+            )
+            .unwrap()
+            .order
+    }
+
+    #[derive(Clone, Default)]
+    pub struct InMemoryOrderRepository {
+        pub orders: Arc<Mutex<Vec<Order>>>,
+    }
+
+    impl InMemoryOrderRepository {
+        pub fn with_orders(orders: Vec<Order>) -> Self {
+            Self {
+                orders: Arc::new(Mutex::new(orders)),
+            }
+        }
+
+        pub fn all(&self) -> Vec<Order> {
+            self.orders.lock().unwrap().clone()
+        }
+
+        fn find(&self, predicate: impl Fn(&Order) -> bool) -> Vec<Order> {
+            self.orders
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|order| predicate(order))
+                .cloned()
+                .collect()
+        }
+    }
+
+    fn order_uuid(order: &Order) -> uuid::Uuid {
+        order.clone().into()
+    }
+
+    #[async_trait::async_trait]
+    impl OrderRepository for InMemoryOrderRepository {
+        async fn save(&self, order: Order) -> Result<Order, OrderDomainError> {
+            self.orders.lock().unwrap().push(order.clone());
+            Ok(order)
+        }
+
+        async fn save_all(&self, orders: Vec<Order>) -> Result<Vec<Order>, OrderDomainError> {
+            self.orders.lock().unwrap().extend(orders.clone());
+            Ok(orders)
+        }
+
+        async fn update(&self, order: Order) -> Result<Order, OrderDomainError> {
+            let mut orders = self.orders.lock().unwrap();
+            match orders
+                .iter_mut()
+                .find(|stored| order_uuid(stored) == order_uuid(&order))
+            {
+                Some(stored) => {
+                    *stored = order.clone();
+                    Ok(order)
+                }
+                None => Err(OrderDomainError::OrderNotFound),
+            }
+        }
+
+        async fn find_by_id(&self, order_id: uuid::Uuid) -> Result<Order, OrderDomainError> {
+            self.find(|order| order_uuid(order) == order_id)
+                .pop()
+                .ok_or(OrderDomainError::OrderNotFound)
+        }
+
+        async fn find_by_tracking_id(&self, id: TrackingId) -> Result<Order, OrderDomainError> {
+            let tracking_id: uuid::Uuid = id.into();
+            self.find(|order| uuid::Uuid::from(order.tracking_id.clone()) == tracking_id)
+                .pop()
+                .ok_or(OrderDomainError::OrderNotFound)
+        }
+
+        async fn find_scheduled_due(
+            &self,
+            release_cutoff: DateTime<Utc>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            Ok(self.find(|order| {
+                order.order_status == OrderStatus::Scheduled
+                    && order
+                        .scheduled_for
+                        .is_some_and(|scheduled_for| scheduled_for <= release_cutoff)
+            }))
+        }
+
+        async fn find_stuck(
+            &self,
+            order_status: OrderStatus,
+            status_changed_before: DateTime<Utc>,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            Ok(self.find(|order| {
+                order.order_status == order_status
+                    && order
+                        .status_changed_at
+                        .is_none_or(|status_changed_at| status_changed_at <= status_changed_before)
+            }))
+        }
+
+        async fn find_by_checkout_id(
+            &self,
+            checkout_id: uuid::Uuid,
+        ) -> Result<Vec<Order>, OrderDomainError> {
+            Ok(self.find(|order| {
+                order
+                    .checkout
+                    .as_ref()
+                    .is_some_and(|checkout| checkout.id == checkout_id)
+            }))
+        }
+
+        async fn count_open_orders_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<u64, OrderDomainError> {
+            Ok(self
+                .find(|order| {
+                    uuid::Uuid::from(order.customer_id.clone()) == customer_id
+                        && order.order_status.is_open()
+                })
+                .len() as u64)
+        }
+
+        async fn count_kitchen_orders_by_restaurant(
+            &self,
+            restaurant_id: uuid::Uuid,
+            created_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, OrderDomainError> {
+            Ok(self
+                .find(|order| {
+                    uuid::Uuid::from(order.restaurant_id.clone()) == restaurant_id
+                        && OrderStatus::kitchen_statuses().contains(&order.order_status)
+                        && order
+                            .created_at
+                            .is_some_and(|created_at| created_at < created_before)
+                })
+                .len() as u64)
+        }
+
+        async fn count_orders_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<u64, OrderDomainError> {
+            Ok(self
+                .find(|order| uuid::Uuid::from(order.customer_id.clone()) == customer_id)
+                .len() as u64)
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct InMemoryRestaurantRepository {
+        pub restaurant: Arc<Mutex<Restaurant>>,
+    }
+
+    impl InMemoryRestaurantRepository {
+        pub fn new(restaurant: Restaurant) -> Self {
+            Self {
+                restaurant: Arc::new(Mutex::new(restaurant)),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl RestaurantRepository for InMemoryRestaurantRepository {
+        async fn find_restaurant_info(
+            &self,
+            restaurant: Restaurant,
+        ) -> Result<Restaurant, OrderDomainError> {
+            let mut stored = self.restaurant.lock().unwrap().clone();
+            let product_ids: Vec<uuid::Uuid> = restaurant
+                .products
+                .iter()
+                .map(|product| product.clone().into())
+                .collect();
+            let restaurant_id: uuid::Uuid = restaurant.into();
+            stored
+                .products
+                .retain(|product| product_ids.contains(&product.clone().into()));
+            if uuid::Uuid::from(stored.clone()) != restaurant_id || stored.products.is_empty() {
+                return Err(OrderDomainError::RestaurantNotFound);
+            }
+            Ok(stored)
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct RecordingPublisher<T> {
+        pub events: Arc<Mutex<Vec<T>>>,
+    }
+
+    impl<T> Default for RecordingPublisher<T> {
+        fn default() -> Self {
+            Self {
+                events: Arc::new(Mutex::new(vec![])),
+            }
+        }
+    }
+
+    impl<T: Clone> RecordingPublisher<T> {
+        pub fn published(&self) -> Vec<T> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<E, T: DomainEvent<E> + Send + 'static> DomainEventPublisher<E, T> for RecordingPublisher<T> {
+        async fn publish(&self, event: T) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    impl OrderCreatedPaymentRequestMessagePublisher for RecordingPublisher<OrderCreated> {}
+
+    impl CheckoutCreatedPaymentRequestMessagePublisher for RecordingPublisher<CheckoutCreated> {}
+
+    impl OrderRiskReviewRequestMessagePublisher for RecordingPublisher<OrderRiskReviewRequested> {}
+
+    pub fn customer(customer_id: uuid::Uuid) -> Customer {
+        CustomerBuilder::default()
+            .aggregate_root(
+                AggregateRootBuilder::default()
+                    .base_entity(
+                        BaseEntityBuilder::default()
+                            .id(customer_id.into())
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[derive(Clone, Default)]
+    pub struct InMemoryCustomerRepository {
+        pub customers: Arc<Mutex<Vec<Customer>>>,
+    }
+
+    impl InMemoryCustomerRepository {
+        pub fn with_customers(customers: Vec<Customer>) -> Self {
+            Self {
+                customers: Arc::new(Mutex::new(customers)),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl CustomerRepository for InMemoryCustomerRepository {
+        async fn find_customer(
+            &self,
+            customer_id: uuid::Uuid,
+        ) -> Result<Customer, OrderDomainError> {
+            self.customers
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|customer| uuid::Uuid::from((*customer).clone()) == customer_id)
+                .cloned()
+                .ok_or(OrderDomainError::CustomerNotFound)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    pub struct InMemoryRiskCheckRepository {
+        pub risk_checks: Arc<Mutex<Vec<RiskCheck>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl RiskCheckRepository for InMemoryRiskCheckRepository {
+        async fn save(&self, risk_check: RiskCheck) -> Result<RiskCheck, OrderDomainError> {
+            self.risk_checks.lock().unwrap().push(risk_check.clone());
+            Ok(risk_check)
+        }
+
+        async fn find_check_times_by_customer(
+            &self,
+            customer_id: uuid::Uuid,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<DateTime<Utc>>, OrderDomainError> {
+            Ok(self
+                .risk_checks
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|risk_check| {
+                    risk_check.customer_id == customer_id && risk_check.checked_at > since
+                })
+                .map(|risk_check| risk_check.checked_at)
+                .collect())
+        }
+
+        async fn find_check_times_by_address(
+            &self,
+            address_key: String,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<DateTime<Utc>>, OrderDomainError> {
+            Ok(self
+                .risk_checks
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|risk_check| {
+                    risk_check.address_key == address_key && risk_check.checked_at > since
+                })
+                .map(|risk_check| risk_check.checked_at)
+                .collect())
+        }
+    }
+
+    pub type TestOrderCreateHelper = OrderCreateHelper<
+        RecordingPublisher<OrderRiskReviewRequested>,
+        TestOrderDomainService,
+        InMemoryOrderRepository,
+        InMemoryCustomerRepository,
+        InMemoryRestaurantRepository,
+        InMemoryRiskCheckRepository,
+        SequentialIdGenerator,
+    >;
+
+    pub fn order_create_helper(
+        order_repository: InMemoryOrderRepository,
+        customer_repository: InMemoryCustomerRepository,
+        restaurant_repository: InMemoryRestaurantRepository,
+        order_limits: OrderLimits,
+    ) -> TestOrderCreateHelper {
+        OrderCreateHelper::new(
+            domain_service(),
+            order_repository,
+            customer_repository,
+            restaurant_repository,
+            InMemoryRiskCheckRepository::default(),
+            RecordingPublisher::default(),
+            SequentialIdGenerator::new(100),
+            order_limits,
+        )
+    }
+
+    impl OrderCancelledPaymentRequestMessagePublisher for RecordingPublisher<OrderCancelled> {}
+
+    impl OrderRefundRequestMessagePublisher for RecordingPublisher<OrderItemAdjusted> {}
+
+    impl OrderSubscriptionFailedNotificationMessagePublisher
+        for RecordingPublisher<OrderSubscriptionFailed>
+    {
+    }
+
+    #[derive(Clone, Default)]
+    pub struct InMemoryOrderSubscriptionRepository {
+        pub subscriptions: Arc<Mutex<Vec<OrderSubscription>>>,
+        pub claimed_occurrences: Arc<Mutex<Vec<(uuid::Uuid, chrono::NaiveDate)>>>,
+    }
+
+    impl InMemoryOrderSubscriptionRepository {
+        pub fn with_subscriptions(subscriptions: Vec<OrderSubscription>) -> Self {
+            Self {
+                subscriptions: Arc::new(Mutex::new(subscriptions)),
+                claimed_occurrences: Arc::new(Mutex::new(vec![])),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl OrderSubscriptionRepository for InMemoryOrderSubscriptionRepository {
+        async fn save(
+            &self,
+            subscription: OrderSubscription,
+        ) -> Result<OrderSubscription, OrderDomainError> {
+            self.subscriptions
+                .lock()
+                .unwrap()
+                .push(subscription.clone());
+            Ok(subscription)
+        }
+
+        async fn update(
+            &self,
+            subscription: OrderSubscription,
+        ) -> Result<OrderSubscription, OrderDomainError> {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            match subscriptions
+                .iter_mut()
+                .find(|stored| stored.id == subscription.id)
+            {
+                Some(stored) => {
+                    *stored = subscription.clone();
+                    Ok(subscription)
+                }
+                None => Err(OrderDomainError::SubscriptionNotFound),
+            }
+        }
+
+        async fn find_by_id(
+            &self,
+            subscription_id: uuid::Uuid,
+        ) -> Result<OrderSubscription, OrderDomainError> {
+            self.subscriptions
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|subscription| subscription.id == subscription_id)
+                .cloned()
+                .ok_or(OrderDomainError::SubscriptionNotFound)
+        }
+
+        async fn find_active(&self) -> Result<Vec<OrderSubscription>, OrderDomainError> {
+            Ok(self.subscriptions.lock().unwrap().clone())
+        }
+
+        async fn claim_occurrence(
+            &self,
+            subscription: OrderSubscription,
+            occurrence_on: chrono::NaiveDate,
+            _order_id: uuid::Uuid,
+        ) -> Result<OrderSubscription, OrderDomainError> {
+            {
+                let mut claimed_occurrences = self.claimed_occurrences.lock().unwrap();
+                if claimed_occurrences.contains(&(subscription.id, occurrence_on)) {
+                    return Err(OrderDomainError::SubscriptionOccurrenceClaimed(
+                        occurrence_on.to_string(),
+                    ));
+                }
+                claimed_occurrences.push((subscription.id, occurrence_on));
+            }
+            self.update(subscription).await
+        }
+    }
+
+    #[derive(Clone, Default)]
+    pub struct InMemoryGroupOrderRepository {
+        pub group_orders: Arc<Mutex<Vec<GroupOrder>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl GroupOrderRepository for InMemoryGroupOrderRepository {
+        async fn save(&self, group_order: GroupOrder) -> Result<GroupOrder, OrderDomainError> {
+            self.group_orders.lock().unwrap().push(group_order.clone());
+            Ok(group_order)
+        }
+
+        async fn update(
+            &self,
+            mut group_order: GroupOrder,
+        ) -> Result<GroupOrder, OrderDomainError> {
+            let mut group_orders = self.group_orders.lock().unwrap();
+            match group_orders
+                .iter_mut()
+                .find(|stored| stored.id == group_order.id)
+            {
+                Some(stored) if stored.version == group_order.version => {
+                    group_order.version += 1;
+                    *stored = group_order.clone();
+                    Ok(group_order)
+                }
+                Some(_) => Err(OrderDomainError::ConcurrentModification),
+                None => Err(OrderDomainError::OrderNotFound),
+            }
+        }
+
+        async fn find_by_id(
+            &self,
+            group_order_id: uuid::Uuid,
+        ) -> Result<GroupOrder, OrderDomainError> {
+            self.group_orders
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|group_order| group_order.id == group_order_id)
+                .cloned()
+                .ok_or(OrderDomainError::OrderNotFound)
+        }
+    }
 }
@@ -6,36 +6,228 @@ pub mod publisher {
     pub mod kafka {
 
         pub mod create_order_message_publisher {
-            use common::event::publisher::DomainEventPublisher;
+            use common::{event::publisher::DomainEventPublisher, id::IdGenerator};
             use domain_core::{entity::Order, event::OrderCreated};
             use service::ports::output::message::publisher::payment::OrderCreatedPaymentRequestMessagePublisher;
 
             use crate::mapper;
 
-            pub struct CreateOrderMessagePublisher<P: kafka::producer::KafkaProducer>
+            pub struct CreateOrderMessagePublisher<
+                P: kafka::producer::KafkaProducer,
+                G: IdGenerator,
+            >
             where
                 P: Send + Sync,
             {
                 producer: P,
+                id_generator: G,
+            }
+
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator> CreateOrderMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                pub fn new(producer: P, id_generator: G) -> Self {
+                    Self {
+                        producer,
+                        id_generator,
+                    }
+                }
             }
 
             #[async_trait::async_trait]
-            impl<P: kafka::producer::KafkaProducer> DomainEventPublisher<Order, OrderCreated>
-                for CreateOrderMessagePublisher<P>
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                DomainEventPublisher<Order, OrderCreated> for CreateOrderMessagePublisher<P, G>
             where
                 P: Send + Sync,
             {
                 async fn publish(&self, event: OrderCreated) {
+                    let payment_request_messages =
+                        mapper::DataMapper::order_created_event_to_payment_requests(
+                            event,
+                            &self.id_generator,
+                        );
+                    for payment_request_message in payment_request_messages {
+                        // TODO: Handle error
+                        let _x = self.producer.produce(payment_request_message).await;
+                    }
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                OrderCreatedPaymentRequestMessagePublisher for CreateOrderMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+            }
+        }
+
+        pub mod checkout_message_publisher {
+            use common::{event::publisher::DomainEventPublisher, id::IdGenerator};
+            use domain_core::{checkout::Checkout, event::CheckoutCreated};
+            use service::ports::output::message::publisher::payment::CheckoutCreatedPaymentRequestMessagePublisher;
+
+            use crate::mapper;
+
+            pub struct CheckoutMessagePublisher<P: kafka::producer::KafkaProducer, G: IdGenerator>
+            where
+                P: Send + Sync,
+            {
+                producer: P,
+                id_generator: G,
+            }
+
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator> CheckoutMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                pub fn new(producer: P, id_generator: G) -> Self {
+                    Self {
+                        producer,
+                        id_generator,
+                    }
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                DomainEventPublisher<Checkout, CheckoutCreated> for CheckoutMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                async fn publish(&self, event: CheckoutCreated) {
                     let payment_request_message =
-                        mapper::DataMapper::order_created_event_to_payment_request(event);
+                        mapper::DataMapper::checkout_created_event_to_payment_request(
+                            event,
+                            &self.id_generator,
+                        );
                     // TODO: Handle error
                     let _x = self.producer.produce(payment_request_message).await;
                 }
             }
 
             #[async_trait::async_trait]
-            impl<P: kafka::producer::KafkaProducer> OrderCreatedPaymentRequestMessagePublisher
-                for CreateOrderMessagePublisher<P>
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                CheckoutCreatedPaymentRequestMessagePublisher for CheckoutMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+            }
+        }
+
+        pub mod subscription_failure_message_publisher {
+            use common::{event::publisher::DomainEventPublisher, id::IdGenerator};
+            use domain_core::{event::OrderSubscriptionFailed, subscription::OrderSubscription};
+            use service::ports::output::message::publisher::notification::OrderSubscriptionFailedNotificationMessagePublisher;
+
+            use crate::mapper;
+
+            pub struct SubscriptionFailureMessagePublisher<
+                P: kafka::producer::KafkaProducer,
+                G: IdGenerator,
+            >
+            where
+                P: Send + Sync,
+            {
+                producer: P,
+                id_generator: G,
+            }
+
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator> SubscriptionFailureMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                pub fn new(producer: P, id_generator: G) -> Self {
+                    Self {
+                        producer,
+                        id_generator,
+                    }
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                DomainEventPublisher<OrderSubscription, OrderSubscriptionFailed>
+                for SubscriptionFailureMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                async fn publish(&self, event: OrderSubscriptionFailed) {
+                    let subscription_failure_notification_message =
+                        mapper::DataMapper::order_subscription_failed_event_to_notification(
+                            event,
+                            &self.id_generator,
+                        );
+                    // TODO: Handle error
+                    let _x = self
+                        .producer
+                        .produce(subscription_failure_notification_message)
+                        .await;
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                OrderSubscriptionFailedNotificationMessagePublisher
+                for SubscriptionFailureMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+            }
+        }
+
+        pub mod risk_review_message_publisher {
+            use common::{event::publisher::DomainEventPublisher, id::IdGenerator};
+            use domain_core::{entity::Order, event::OrderRiskReviewRequested};
+            use service::ports::output::message::publisher::risk_review::OrderRiskReviewRequestMessagePublisher;
+
+            use crate::mapper;
+
+            pub struct RiskReviewMessagePublisher<P: kafka::producer::KafkaProducer, G: IdGenerator>
+            where
+                P: Send + Sync,
+            {
+                producer: P,
+                id_generator: G,
+            }
+
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator> RiskReviewMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                pub fn new(producer: P, id_generator: G) -> Self {
+                    Self {
+                        producer,
+                        id_generator,
+                    }
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                DomainEventPublisher<Order, OrderRiskReviewRequested>
+                for RiskReviewMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                async fn publish(&self, event: OrderRiskReviewRequested) {
+                    let order_risk_review_request_message =
+                        mapper::DataMapper::order_risk_review_requested_event_to_review_request(
+                            event,
+                            &self.id_generator,
+                        );
+                    // TODO: Handle error
+                    let _x = self
+                        .producer
+                        .produce(order_risk_review_request_message)
+                        .await;
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                OrderRiskReviewRequestMessagePublisher for RiskReviewMessagePublisher<P, G>
             where
                 P: Send + Sync,
             {
@@ -43,36 +235,57 @@ pub mod publisher {
         }
 
         pub mod cancel_order_message_publisher {
-            use common::event::publisher::DomainEventPublisher;
+            use common::{event::publisher::DomainEventPublisher, id::IdGenerator};
             use domain_core::{entity::Order, event::OrderCancelled};
             use service::ports::output::message::publisher::payment::OrderCancelledPaymentRequestMessagePublisher;
 
             use crate::mapper;
 
-            pub struct CancelOrderMessagePublisher<P: kafka::producer::KafkaProducer>
+            pub struct CancelOrderMessagePublisher<
+                P: kafka::producer::KafkaProducer,
+                G: IdGenerator,
+            >
             where
                 P: Send + Sync,
             {
                 producer: P,
+                id_generator: G,
+            }
+
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator> CancelOrderMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                pub fn new(producer: P, id_generator: G) -> Self {
+                    Self {
+                        producer,
+                        id_generator,
+                    }
+                }
             }
 
             #[async_trait::async_trait]
-            impl<P: kafka::producer::KafkaProducer> DomainEventPublisher<Order, OrderCancelled>
-                for CancelOrderMessagePublisher<P>
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                DomainEventPublisher<Order, OrderCancelled> for CancelOrderMessagePublisher<P, G>
             where
                 P: Send + Sync,
             {
                 async fn publish(&self, event: OrderCancelled) {
-                    let payment_request_message =
-                        mapper::DataMapper::order_cancelled_event_to_payment_request(event);
-                    // TODO: Handle error
-                    let _x = self.producer.produce(payment_request_message).await;
+                    let payment_request_messages =
+                        mapper::DataMapper::order_cancelled_event_to_payment_requests(
+                            event,
+                            &self.id_generator,
+                        );
+                    for payment_request_message in payment_request_messages {
+                        // TODO: Handle error
+                        let _x = self.producer.produce(payment_request_message).await;
+                    }
                 }
             }
 
             #[async_trait::async_trait]
-            impl<P: kafka::producer::KafkaProducer> OrderCancelledPaymentRequestMessagePublisher
-                for CancelOrderMessagePublisher<P>
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                OrderCancelledPaymentRequestMessagePublisher for CancelOrderMessagePublisher<P, G>
             where
                 P: Send + Sync,
             {
@@ -81,36 +294,108 @@ pub mod publisher {
 
         pub mod pay_order_message_publisher {
 
-            use common::event::publisher::DomainEventPublisher;
+            use common::{event::publisher::DomainEventPublisher, id::IdGenerator};
             use domain_core::{entity::Order, event::OrderPaid};
             use service::ports::output::message::publisher::restaurant_approval::OrderPaidRestaurantRequestMessagePublisher;
 
             use crate::mapper;
 
-            pub struct PayOrderMessagePublisher<P: kafka::producer::KafkaProducer>
+            pub struct PayOrderMessagePublisher<P: kafka::producer::KafkaProducer, G: IdGenerator>
             where
                 P: Send + Sync,
             {
                 producer: P,
+                id_generator: G,
+            }
+
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator> PayOrderMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                pub fn new(producer: P, id_generator: G) -> Self {
+                    Self {
+                        producer,
+                        id_generator,
+                    }
+                }
             }
 
             #[async_trait::async_trait]
-            impl<P: kafka::producer::KafkaProducer> DomainEventPublisher<Order, OrderPaid>
-                for PayOrderMessagePublisher<P>
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                DomainEventPublisher<Order, OrderPaid> for PayOrderMessagePublisher<P, G>
             where
                 P: Send + Sync,
             {
                 async fn publish(&self, event: OrderPaid) {
                     let restaurant_request_message =
-                        mapper::DataMapper::order_paid_event_to_restaurant_request(event);
+                        mapper::DataMapper::order_paid_event_to_restaurant_request(
+                            event,
+                            &self.id_generator,
+                        );
                     // TODO: Handle error
                     let _x = self.producer.produce(restaurant_request_message).await;
                 }
             }
 
             #[async_trait::async_trait]
-            impl<P: kafka::producer::KafkaProducer> OrderPaidRestaurantRequestMessagePublisher
-                for PayOrderMessagePublisher<P>
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                OrderPaidRestaurantRequestMessagePublisher for PayOrderMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+            }
+        }
+
+        pub mod refund_order_message_publisher {
+            use common::{event::publisher::DomainEventPublisher, id::IdGenerator};
+            use domain_core::{entity::Order, event::OrderItemAdjusted};
+            use service::ports::output::message::publisher::payment::OrderRefundRequestMessagePublisher;
+
+            use crate::mapper;
+
+            pub struct RefundOrderMessagePublisher<
+                P: kafka::producer::KafkaProducer,
+                G: IdGenerator,
+            >
+            where
+                P: Send + Sync,
+            {
+                producer: P,
+                id_generator: G,
+            }
+
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator> RefundOrderMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                pub fn new(producer: P, id_generator: G) -> Self {
+                    Self {
+                        producer,
+                        id_generator,
+                    }
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                DomainEventPublisher<Order, OrderItemAdjusted> for RefundOrderMessagePublisher<P, G>
+            where
+                P: Send + Sync,
+            {
+                async fn publish(&self, event: OrderItemAdjusted) {
+                    let payment_refund_request_message =
+                        mapper::DataMapper::order_item_adjusted_event_to_payment_refund_request(
+                            event,
+                            &self.id_generator,
+                        );
+                    // TODO: Handle error
+                    let _x = self.producer.produce(payment_refund_request_message).await;
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl<P: kafka::producer::KafkaProducer, G: IdGenerator>
+                OrderRefundRequestMessagePublisher for RefundOrderMessagePublisher<P, G>
             where
                 P: Send + Sync,
             {
@@ -120,12 +405,29 @@ pub mod publisher {
 }
 
 pub mod mapper {
-    use domain_core::event::{OrderCancelled, OrderCreated, OrderPaid};
+    use common::{
+        event::EventEnvelope,
+        id::IdGenerator,
+        value_object::{money::Money, CustomerId},
+    };
+    use domain_core::{
+        entity::{Order, PaymentShare},
+        event::{
+            CheckoutCreated, OrderCancelled, OrderCreated, OrderItemAdjusted, OrderPaid,
+            OrderRiskReviewRequested, OrderSubscriptionFailed,
+        },
+        value_object::PaymentShareStatus,
+    };
     use kafka::{
         model::avro::{
-            payment_request::{PaymentRequest, PaymentRequestBuilder},
+            order_risk_review_request::{OrderRiskReviewRequest, OrderRiskReviewRequestBuilder},
+            payment_refund_request::{PaymentRefundRequest, PaymentRefundRequestBuilder},
+            payment_request::{PaymentOrderStatus, PaymentRequest, PaymentRequestBuilder},
             restaurant_approval_request::{
-                Product, RestaurantApprovalRequest, RestaurantApprovalRequestBuilder,
+                Product, ProductOption, RestaurantApprovalRequest, RestaurantApprovalRequestBuilder,
+            },
+            subscription_failure_notification::{
+                SubscriptionFailureNotification, SubscriptionFailureNotificationBuilder,
             },
         },
         Message, MessageBuilder,
@@ -134,22 +436,38 @@ pub mod mapper {
     pub struct DataMapper {}
 
     impl DataMapper {
-        pub fn order_created_event_to_payment_request(
-            order_created: OrderCreated,
+        fn payment_request<G: IdGenerator>(
+            order: &Order,
+            envelope: &EventEnvelope,
+            share: Option<&PaymentShare>,
+            payment_order_status: PaymentOrderStatus,
+            id_generator: &G,
         ) -> Message<PaymentRequest> {
-            let payment_request_message_id = uuid::Uuid::new_v4();
-            // Will change later
-            let saga_id = uuid::Uuid::new_v4();
+            let payment_request_message_id = id_generator.generate();
+            let (customer_id, price, tax, tip): (CustomerId, Money, Money, Money) = match share {
+                Some(share) => (
+                    share.customer_id.clone(),
+                    share.total_price.clone(),
+                    share.tax.clone(),
+                    share.tip.clone(),
+                ),
+                None => (
+                    order.customer_id.clone(),
+                    order.total_price(),
+                    order.tax.clone(),
+                    order.tip_amount(),
+                ),
+            };
             let payment_request_message = PaymentRequestBuilder::default()
                 .id(payment_request_message_id)
-                .saga_id(saga_id)
-                .customer_id(order_created.order.clone().customer_id.into())
-                .order_id(order_created.order.clone().into())
-                .price(order_created.order.clone().price.into())
-                .created_at(order_created.created_at.timestamp())
-                .payment_order_status(
-                    kafka::model::avro::payment_request::PaymentOrderStatus::PENDING,
-                )
+                .saga_id(envelope.correlation_id)
+                .customer_id(customer_id.into())
+                .order_id(order.payment_order_id())
+                .price(price.into())
+                .tax(tax.into())
+                .tip(tip.into())
+                .created_at(envelope.occurred_at.timestamp())
+                .payment_order_status(payment_order_status)
                 .build()
                 .unwrap();
             MessageBuilder::default()
@@ -160,22 +478,51 @@ pub mod mapper {
                 .unwrap()
         }
 
-        pub fn order_cancelled_event_to_payment_request(
-            order_cancelled: OrderCancelled,
+        pub fn order_created_event_to_payment_requests<G: IdGenerator>(
+            order_created: OrderCreated,
+            id_generator: &G,
+        ) -> Vec<Message<PaymentRequest>> {
+            let order = &order_created.order;
+            if !order.is_split_payment() {
+                return vec![Self::payment_request(
+                    order,
+                    &order_created.envelope,
+                    None,
+                    PaymentOrderStatus::PENDING,
+                    id_generator,
+                )];
+            }
+            order
+                .payment_shares
+                .iter()
+                .map(|share| {
+                    Self::payment_request(
+                        order,
+                        &order_created.envelope,
+                        Some(share),
+                        PaymentOrderStatus::PENDING,
+                        id_generator,
+                    )
+                })
+                .collect()
+        }
+
+        pub fn checkout_created_event_to_payment_request<G: IdGenerator>(
+            checkout_created: CheckoutCreated,
+            id_generator: &G,
         ) -> Message<PaymentRequest> {
-            let payment_request_message_id = uuid::Uuid::new_v4();
-            // Will change later
-            let saga_id = uuid::Uuid::new_v4();
+            let payment_request_message_id = id_generator.generate();
+            let checkout = &checkout_created.checkout;
             let payment_request_message = PaymentRequestBuilder::default()
                 .id(payment_request_message_id)
-                .saga_id(saga_id)
-                .customer_id(order_cancelled.order.clone().customer_id.into())
-                .order_id(order_cancelled.order.clone().into())
-                .price(order_cancelled.order.clone().price.into())
-                .created_at(order_cancelled.created_at.timestamp())
-                .payment_order_status(
-                    kafka::model::avro::payment_request::PaymentOrderStatus::CANCELLED,
-                )
+                .saga_id(checkout_created.envelope.correlation_id)
+                .customer_id(checkout.customer_id.clone().into())
+                .order_id(checkout.id)
+                .price(checkout.total_price().into())
+                .tax(checkout.tax().into())
+                .tip(checkout.tip_amount().into())
+                .created_at(checkout_created.envelope.occurred_at.timestamp())
+                .payment_order_status(PaymentOrderStatus::PENDING)
                 .build()
                 .unwrap();
             MessageBuilder::default()
@@ -186,12 +533,118 @@ pub mod mapper {
                 .unwrap()
         }
 
-        pub fn order_paid_event_to_restaurant_request(
+        pub fn order_cancelled_event_to_payment_requests<G: IdGenerator>(
+            order_cancelled: OrderCancelled,
+            id_generator: &G,
+        ) -> Vec<Message<PaymentRequest>> {
+            let order = &order_cancelled.order;
+            if !order.is_split_payment() {
+                return vec![Self::payment_request(
+                    order,
+                    &order_cancelled.envelope,
+                    None,
+                    PaymentOrderStatus::CANCELLED,
+                    id_generator,
+                )];
+            }
+            order
+                .payment_shares
+                .iter()
+                .filter(|share| share.status != PaymentShareStatus::Failed)
+                .map(|share| {
+                    Self::payment_request(
+                        order,
+                        &order_cancelled.envelope,
+                        Some(share),
+                        PaymentOrderStatus::CANCELLED,
+                        id_generator,
+                    )
+                })
+                .collect()
+        }
+
+        pub fn order_subscription_failed_event_to_notification<G: IdGenerator>(
+            order_subscription_failed: OrderSubscriptionFailed,
+            id_generator: &G,
+        ) -> Message<SubscriptionFailureNotification> {
+            let notification_message_id = id_generator.generate();
+            let subscription = order_subscription_failed.subscription;
+            let subscription_failure_notification_message =
+                SubscriptionFailureNotificationBuilder::default()
+                    .id(notification_message_id)
+                    .subscription_id(subscription.id)
+                    .customer_id(subscription.customer_id.into())
+                    .restaurant_id(subscription.restaurant_id.into())
+                    .occurrence_date(order_subscription_failed.occurrence.date.to_string())
+                    .failure_messages(order_subscription_failed.failure_messages)
+                    .created_at(order_subscription_failed.envelope.occurred_at.timestamp())
+                    .build()
+                    .unwrap();
+            MessageBuilder::default()
+                .topic(String::from("subscription-failure-notification")) //TODO: Should change hardcoding
+                .key(notification_message_id.to_string())
+                .value(subscription_failure_notification_message)
+                .build()
+                .unwrap()
+        }
+
+        pub fn order_risk_review_requested_event_to_review_request<G: IdGenerator>(
+            order_risk_review_requested: OrderRiskReviewRequested,
+            id_generator: &G,
+        ) -> Message<OrderRiskReviewRequest> {
+            let review_request_message_id = id_generator.generate();
+            let order = order_risk_review_requested.order;
+            let order_risk_review_request_message = OrderRiskReviewRequestBuilder::default()
+                .id(review_request_message_id)
+                .saga_id(order_risk_review_requested.envelope.correlation_id)
+                .order_id(order.clone().into())
+                .customer_id(order.customer_id.clone().into())
+                .restaurant_id(order.restaurant_id.clone().into())
+                .price(order.total_price().into())
+                .risk_reasons(order.risk_reasons)
+                .created_at(order_risk_review_requested.envelope.occurred_at.timestamp())
+                .build()
+                .unwrap();
+            MessageBuilder::default()
+                .topic(String::from("order-risk-review-request")) //TODO: Should change hardcoding
+                .key(review_request_message_id.to_string())
+                .value(order_risk_review_request_message)
+                .build()
+                .unwrap()
+        }
+
+        pub fn order_item_adjusted_event_to_payment_refund_request<G: IdGenerator>(
+            order_item_adjusted: OrderItemAdjusted,
+            id_generator: &G,
+        ) -> Message<PaymentRefundRequest> {
+            let payment_refund_request_message_id = id_generator.generate();
+            let saga_id = order_item_adjusted.envelope.correlation_id;
+            let payment_refund_request_message = PaymentRefundRequestBuilder::default()
+                .id(payment_refund_request_message_id)
+                .saga_id(saga_id)
+                .customer_id(order_item_adjusted.order.clone().customer_id.into())
+                .order_id(order_item_adjusted.order.payment_order_id())
+                .adjustment_id(order_item_adjusted.adjustment.id)
+                .refund_amount(order_item_adjusted.adjustment.refund_amount.clone().into())
+                .reason(order_item_adjusted.adjustment.reason.to_string())
+                .created_at(order_item_adjusted.envelope.occurred_at.timestamp())
+                .build()
+                .unwrap();
+            MessageBuilder::default()
+                .topic(String::from("payment-refund-request")) //TODO: Should change hardcoding
+                .key(payment_refund_request_message_id.to_string())
+                .value(payment_refund_request_message)
+                .build()
+                .unwrap()
+        }
+
+        pub fn order_paid_event_to_restaurant_request<G: IdGenerator>(
             order_paid: OrderPaid,
+            id_generator: &G,
         ) -> Message<RestaurantApprovalRequest> {
-            let restaurant_approval_request_message_id = uuid::Uuid::new_v4();
-            // Will change later
-            let saga_id = uuid::Uuid::new_v4();
+            let restaurant_approval_request_message_id = id_generator.generate();
+            let saga_id = order_paid.envelope.correlation_id;
+            let dietary_preferences = order_paid.order.declared_dietary_preferences();
             let restaurant_approval_request_message = RestaurantApprovalRequestBuilder::default()
                 .id(restaurant_approval_request_message_id)
                 .saga_id(saga_id)
@@ -204,15 +657,54 @@ pub mod mapper {
                         .clone()
                         .items
                         .into_iter()
-                        .map(|item| {
-                            return Product {
-                                id: item.product.into(),
-                                quantity: item.quantity as i64,
-                            };
+                        .map(|item| Product {
+                            id: item.product.into(),
+                            quantity: item.quantity as i64,
+                            options: item
+                                .selected_options
+                                .into_iter()
+                                .map(|selected_option| ProductOption {
+                                    id: selected_option.option_id,
+                                    group_id: selected_option.group_id,
+                                    name: selected_option.name,
+                                })
+                                .collect(),
                         })
                         .collect(),
                 )
-                .created_at(order_paid.created_at.timestamp())
+                .created_at(order_paid.envelope.occurred_at.timestamp())
+                .declared_allergies(
+                    dietary_preferences
+                        .as_ref()
+                        .map(|preferences| {
+                            preferences
+                                .allergies
+                                .iter()
+                                .map(|allergen| allergen.to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
+                .dietary_requirements(
+                    dietary_preferences
+                        .as_ref()
+                        .map(|preferences| {
+                            preferences
+                                .requirements
+                                .iter()
+                                .map(|tag| tag.to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
+                .dietary_conflicts(
+                    order_paid
+                        .order
+                        .dietary_conflicts
+                        .iter()
+                        .map(|conflict| conflict.to_string())
+                        .collect(),
+                )
                 .restaurant_order_status(
                     kafka::model::avro::restaurant_approval_request::RestaurantOrderStatus::PAID,
                 )
@@ -227,3 +719,183 @@ pub mod mapper {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod mapper {
+        use common::id::SequentialIdGenerator;
+        use domain_core::{
+            checkout::OrderCheckoutBuilder,
+            event::{OrderCancelledBuilder, OrderCreatedBuilder, OrderRiskReviewRequestedBuilder},
+        };
+        use kafka::model::avro::payment_request::PaymentOrderStatus;
+
+        use crate::fixtures;
+        use crate::mapper::DataMapper;
+
+        #[test]
+        fn payment_requests_use_the_injected_id_generator_and_event_time() {
+            let order_created = OrderCreatedBuilder::default()
+                .order(fixtures::order(vec![fixtures::order_item(1, 1000.0, 2)]))
+                .envelope(fixtures::envelope(1))
+                .build()
+                .unwrap();
+            let messages = DataMapper::order_created_event_to_payment_requests(
+                order_created,
+                &SequentialIdGenerator::new(500),
+            );
+            assert_eq!(messages.len(), 1);
+            let payment_request = messages[0].value();
+            assert_eq!(payment_request.id, fixtures::id(500));
+            assert_eq!(messages[0].key(), fixtures::id(500).to_string());
+            assert_eq!(payment_request.saga_id, fixtures::id(800));
+            assert_eq!(payment_request.price, 2000);
+            assert_eq!(payment_request.created_at, fixtures::now().timestamp());
+            assert!(matches!(
+                payment_request.payment_order_status,
+                PaymentOrderStatus::PENDING
+            ));
+        }
+
+        #[test]
+        fn risk_review_requests_carry_the_order_risk_reasons() {
+            let mut order = fixtures::order(vec![fixtures::order_item(1, 1000.0, 2)]);
+            order.risk_reasons = vec![String::from("HighTotal: order total 2000 is above 500")];
+            let order_risk_review_requested = OrderRiskReviewRequestedBuilder::default()
+                .order(order)
+                .envelope(fixtures::envelope(1))
+                .build()
+                .unwrap();
+            let message = DataMapper::order_risk_review_requested_event_to_review_request(
+                order_risk_review_requested,
+                &SequentialIdGenerator::new(500),
+            );
+            let review_request = message.value();
+            assert_eq!(review_request.id, fixtures::id(500));
+            assert_eq!(review_request.saga_id, fixtures::id(800));
+            assert_eq!(review_request.order_id, fixtures::id(1));
+            assert_eq!(review_request.price, 2000);
+            assert_eq!(
+                review_request.risk_reasons,
+                vec![String::from("HighTotal: order total 2000 is above 500")]
+            );
+        }
+
+        #[test]
+        fn checkout_order_cancellations_refund_the_order_share_of_the_checkout_charge() {
+            let mut order = fixtures::order(vec![fixtures::order_item(1, 1000.0, 2)]);
+            order.checkout = Some(
+                OrderCheckoutBuilder::default()
+                    .id(fixtures::id(60))
+                    .build()
+                    .unwrap(),
+            );
+            let order_cancelled = OrderCancelledBuilder::default()
+                .order(order)
+                .envelope(fixtures::envelope(3))
+                .build()
+                .unwrap();
+            let messages = DataMapper::order_cancelled_event_to_payment_requests(
+                order_cancelled,
+                &SequentialIdGenerator::new(500),
+            );
+            assert_eq!(messages.len(), 1);
+            let payment_request = messages[0].value();
+            assert_eq!(payment_request.order_id, fixtures::id(60));
+            assert_eq!(payment_request.price, 2000);
+            assert!(matches!(
+                payment_request.payment_order_status,
+                PaymentOrderStatus::CANCELLED
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod fixtures {
+    use chrono::{DateTime, TimeZone, Utc};
+    use common::entity::{AggregateRootBuilder, BaseEntityBuilder};
+    use common::event::{EventEnvelope, EventEnvelopeBuilder};
+    use common::value_object::{money::Money, OrderStatus};
+    use domain_core::entity::{Order, OrderBuilder, OrderItem, OrderItemBuilder, Product};
+    use domain_core::value_object::StreetAddressBuilder;
+
+    pub fn id(n: u128) -> uuid::Uuid {
+        uuid::Uuid::from_u128(n)
+    }
+
+    pub fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap()
+    }
+
+    pub fn envelope(aggregate_version: i64) -> EventEnvelope {
+        EventEnvelopeBuilder::default()
+            .event_id(id(700))
+            .aggregate_id(id(1))
+            .aggregate_version(aggregate_version)
+            .occurred_at(now())
+            .correlation_id(id(800))
+            .build()
+            .unwrap()
+    }
+
+    pub fn order_item(item_id: i64, price: f64, quantity: u64) -> OrderItem {
+        let product = Product::new(
+            id(10 + item_id as u128),
+            format!("product {}", item_id),
+            Money::new(price),
+        );
+        OrderItemBuilder::default()
+            .base_entity(
+                BaseEntityBuilder::default()
+                    .id(item_id.into())
+                    .build()
+                    .unwrap(),
+            )
+            .order_id(id(1).into())
+            .price(product.price.clone())
+            .sub_total(product.price.clone() * quantity)
+            .product(product)
+            .quantity(quantity)
+            .build()
+            .unwrap()
+    }
+
+    pub fn order(items: Vec<OrderItem>) -> Order {
+        let mut price = Money::default();
+        for item in items.iter() {
+            price += item.sub_total.clone();
+        }
+        OrderBuilder::default()
+            .aggregate_root(
+                AggregateRootBuilder::default()
+                    .base_entity(
+                        BaseEntityBuilder::default()
+                            .id(id(1).into())
+                            .build()
+                            .unwrap(),
+                    )
+                    .version(1)
+                    .build()
+                    .unwrap(),
+            )
+            .customer_id(id(2).into())
+            .restaurant_id(id(3).into())
+            .street_address(
+                StreetAddressBuilder::default()
+                    .id(id(900))
+                    .street(String::from("1 Main Street"))
+                    .postal_code(String::from("1011AB"))
+                    .city(String::from("Amsterdam"))
+                    .build()
+                    .unwrap(),
+            )
+            .price(price)
+            .items(items)
+            .tracking_id(id(4).into())
+            .order_status(OrderStatus::Pending)
+            .failure_messages(vec![])
+            .build()
+            .unwrap()
+    }
+}